@@ -0,0 +1,4 @@
+-- Add migration script here
+ALTER TABLE users
+ADD COLUMN IF NOT EXISTS role VARCHAR(16) NOT NULL DEFAULT 'viewer'
+CONSTRAINT users_role_check CHECK (role IN ('viewer', 'creator', 'admin'));
@@ -0,0 +1,15 @@
+-- Add migration script here
+ALTER TABLE users ADD COLUMN IF NOT EXISTS email_verified_at TIMESTAMP WITH TIME ZONE;
+
+CREATE TABLE IF NOT EXISTS email_verification_tokens (
+    id uuid PRIMARY KEY,
+    user_id uuid NOT NULL,
+    -- sha256 of the token, the token itself is never stored.
+    token_hash VARCHAR(64) UNIQUE NOT NULL,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    used_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT fk_user_id
+    FOREIGN KEY(user_id) REFERENCES users(id)
+    ON DELETE NO ACTION
+);
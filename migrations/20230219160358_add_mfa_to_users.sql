@@ -0,0 +1,26 @@
+-- Add migration script here
+ALTER TABLE users
+-- base32 totp secret, set when the user starts the enrollment.
+ADD COLUMN IF NOT EXISTS totp_secret VARCHAR(64),
+-- Set when the user confirms the enrollment, two-factor authentication
+-- is required to log in from then on.
+ADD COLUMN IF NOT EXISTS mfa_enabled_at TIMESTAMP WITH TIME ZONE,
+-- Step of the last accepted totp code, codes cannot be used twice.
+ADD COLUMN IF NOT EXISTS totp_last_used_step BIGINT;
+
+ALTER TABLE refresh_tokens
+ADD COLUMN IF NOT EXISTS mfa_authenticated BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE IF NOT EXISTS mfa_recovery_codes (
+    id uuid PRIMARY KEY,
+    user_id uuid NOT NULL,
+    -- sha256 of the code, the code itself is never stored.
+    code_hash VARCHAR(64) NOT NULL,
+    used_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT fk_user_id
+    FOREIGN KEY(user_id) REFERENCES users(id)
+    ON DELETE NO ACTION
+);
+
+CREATE INDEX IF NOT EXISTS mfa_recovery_codes_user_id_idx ON mfa_recovery_codes(user_id);
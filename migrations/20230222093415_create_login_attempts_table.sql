@@ -0,0 +1,14 @@
+-- Add migration script here
+-- Failed login attempts, tracked per account and per ip address.
+CREATE TABLE IF NOT EXISTS login_attempts (
+    -- 'account' or 'ip'.
+    scope VARCHAR(16) NOT NULL,
+    -- Email of the account or the ip address, depending on the scope.
+    key VARCHAR(255) NOT NULL,
+    failed_count INTEGER NOT NULL DEFAULT 0,
+    last_failed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- Logins are rejected until this moment.
+    locked_until TIMESTAMP WITH TIME ZONE,
+    PRIMARY KEY (scope, key),
+    CONSTRAINT login_attempts_scope_check CHECK (scope IN ('account', 'ip'))
+);
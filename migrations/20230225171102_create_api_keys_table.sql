@@ -0,0 +1,21 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS api_keys (
+    id uuid PRIMARY KEY,
+    user_id uuid NOT NULL,
+    -- Chosen by the user to tell keys apart.
+    name VARCHAR(100) NOT NULL,
+    -- Beginning of the key, shown when listing keys.
+    prefix VARCHAR(16) NOT NULL,
+    -- sha256 of the key, the key itself is never stored.
+    secret_hash VARCHAR(64) UNIQUE NOT NULL,
+    -- Empty when the key can be used for every endpoint that accepts api keys.
+    scopes VARCHAR(32)[] NOT NULL DEFAULT '{}',
+    last_used_at TIMESTAMP WITH TIME ZONE,
+    revoked_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT fk_user_id
+    FOREIGN KEY(user_id) REFERENCES users(id)
+    ON DELETE NO ACTION
+);
+
+CREATE INDEX IF NOT EXISTS api_keys_user_id_idx ON api_keys(user_id);
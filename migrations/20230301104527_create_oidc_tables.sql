@@ -0,0 +1,28 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS oidc_identities (
+    id uuid PRIMARY KEY,
+    user_id uuid NOT NULL,
+    issuer VARCHAR(255) NOT NULL,
+    -- The `sub` claim, unique per issuer.
+    subject VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT oidc_identities_issuer_subject_key UNIQUE (issuer, subject),
+    CONSTRAINT fk_user_id
+    FOREIGN KEY(user_id) REFERENCES users(id)
+    ON DELETE NO ACTION
+);
+
+CREATE INDEX IF NOT EXISTS oidc_identities_user_id_idx ON oidc_identities(user_id);
+
+-- Logins that have been sent to the issuer and are waiting for the callback.
+CREATE TABLE IF NOT EXISTS oidc_authorization_requests (
+    id uuid PRIMARY KEY,
+    -- sha256 of the state parameter.
+    state_hash VARCHAR(64) UNIQUE NOT NULL,
+    nonce VARCHAR(64) NOT NULL,
+    -- Sent to the token endpoint to prove we started the login.
+    code_verifier VARCHAR(128) NOT NULL,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    used_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,25 @@
+-- Add migration script here
+ALTER TABLE users
+ADD COLUMN IF NOT EXISTS display_name VARCHAR(50),
+ADD COLUMN IF NOT EXISTS bio VARCHAR(500),
+ADD COLUMN IF NOT EXISTS links VARCHAR(255)[] NOT NULL DEFAULT '{}',
+ADD COLUMN IF NOT EXISTS avatar_url VARCHAR(1024),
+ADD COLUMN IF NOT EXISTS banner_url VARCHAR(1024);
+
+-- Images the user has been allowed to upload but that are not
+-- attached to the profile until the upload is confirmed.
+CREATE TABLE IF NOT EXISTS profile_image_uploads (
+    id uuid PRIMARY KEY,
+    user_id uuid NOT NULL,
+    kind VARCHAR(16) NOT NULL CONSTRAINT profile_image_uploads_kind_check CHECK (kind IN ('avatar', 'banner')),
+    object_key VARCHAR(255) NOT NULL,
+    content_type VARCHAR(32) NOT NULL,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    confirmed_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT fk_user_id
+    FOREIGN KEY(user_id) REFERENCES users(id)
+    ON DELETE NO ACTION
+);
+
+CREATE INDEX IF NOT EXISTS profile_image_uploads_user_id_idx ON profile_image_uploads(user_id);
@@ -0,0 +1,33 @@
+-- Add migration script here
+-- NFKC case folded username, two users cannot have usernames
+-- that only differ in case or in compatibility characters.
+ALTER TABLE users
+ADD COLUMN IF NOT EXISTS username_normalized VARCHAR(255);
+
+UPDATE users
+SET username_normalized = LOWER(NORMALIZE(username, NFKC))
+WHERE username_normalized IS NULL;
+
+-- Usernames that collide once normalized are renamed, the oldest account
+-- keeps its username and the others get a suffix taken from their id.
+WITH duplicates AS (
+    SELECT id, ROW_NUMBER() OVER (
+        PARTITION BY username_normalized
+        ORDER BY created_at, id
+    ) AS position
+    FROM users
+)
+UPDATE users
+SET username = LEFT(users.username, 21) || '_' || LEFT(REPLACE(users.id::text, '-', ''), 8)
+FROM duplicates
+WHERE duplicates.id = users.id
+AND duplicates.position > 1;
+
+UPDATE users
+SET username_normalized = LOWER(NORMALIZE(username, NFKC))
+WHERE username_normalized <> LOWER(NORMALIZE(username, NFKC));
+
+ALTER TABLE users
+ALTER COLUMN username_normalized SET NOT NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS users_username_normalized_idx ON users(username_normalized);
@@ -0,0 +1,18 @@
+-- Add migration script here
+-- Objects that must be removed from object storage once `purge_after` has passed,
+-- such as the media of deleted accounts and expired personal data exports.
+CREATE TABLE IF NOT EXISTS object_purges (
+    id uuid PRIMARY KEY,
+    user_id uuid NOT NULL,
+    bucket VARCHAR(255) NOT NULL,
+    -- Every object whose key starts with it is removed.
+    key_prefix VARCHAR(1024) NOT NULL,
+    purge_after TIMESTAMP WITH TIME ZONE NOT NULL,
+    purged_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT fk_user_id
+    FOREIGN KEY(user_id) REFERENCES users(id)
+    ON DELETE NO ACTION
+);
+
+CREATE INDEX IF NOT EXISTS object_purges_purge_after_idx ON object_purges(purge_after) WHERE purged_at IS NULL;
@@ -0,0 +1,20 @@
+-- Add migration script here
+-- Every ban and unban, users.banned_at only has the current state.
+CREATE TABLE IF NOT EXISTS user_bans (
+    id uuid PRIMARY KEY,
+    user_id uuid NOT NULL,
+    -- The admin that banned or unbanned the user.
+    admin_id uuid NOT NULL,
+    action VARCHAR(16) NOT NULL,
+    reason VARCHAR(500) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT fk_user_id
+    FOREIGN KEY(user_id) REFERENCES users(id)
+    ON DELETE NO ACTION,
+    CONSTRAINT fk_admin_id
+    FOREIGN KEY(admin_id) REFERENCES users(id)
+    ON DELETE NO ACTION,
+    CONSTRAINT user_bans_action_check CHECK (action IN ('ban', 'unban'))
+);
+
+CREATE INDEX IF NOT EXISTS user_bans_user_id_idx ON user_bans(user_id);
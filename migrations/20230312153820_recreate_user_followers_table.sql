@@ -0,0 +1,20 @@
+-- Add migration script here
+-- The table had no followee so it could not hold any follow, it is recreated.
+DROP TABLE IF EXISTS user_followers;
+
+CREATE TABLE IF NOT EXISTS user_followers (
+    follower_id uuid NOT NULL,
+    followee_id uuid NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (follower_id, followee_id),
+    CONSTRAINT fk_follower_id
+    FOREIGN KEY(follower_id) REFERENCES users(id)
+    ON DELETE NO ACTION,
+    CONSTRAINT fk_followee_id
+    FOREIGN KEY(followee_id) REFERENCES users(id)
+    ON DELETE NO ACTION,
+    CONSTRAINT user_followers_not_self_check CHECK (follower_id <> followee_id)
+);
+
+-- The primary key already covers lookups by follower.
+CREATE INDEX IF NOT EXISTS user_followers_followee_id_idx ON user_followers(followee_id, created_at);
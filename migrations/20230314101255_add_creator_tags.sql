@@ -0,0 +1,31 @@
+-- Add migration script here
+-- Tag names are stored normalized, as the lowercase name of the tag,
+-- the same way tags sent by creators are normalized.
+UPDATE user_tags SET name = LOWER(TRIM(NORMALIZE(name, NFKC)));
+
+-- Tags used to be free text, only the curated ones are kept.
+-- Keep the list in sync with `CreatorTag`.
+DELETE FROM user_tags WHERE name NOT IN ('fitness', 'cooking', 'music');
+
+DELETE FROM user_tags a
+USING user_tags b
+WHERE a.user_id = b.user_id AND a.name = b.name AND a.id > b.id;
+
+ALTER TABLE user_tags
+    ADD COLUMN IF NOT EXISTS created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP;
+
+CREATE UNIQUE INDEX IF NOT EXISTS user_tags_user_id_name_idx ON user_tags(user_id, name);
+
+CREATE INDEX IF NOT EXISTS user_tags_name_idx ON user_tags(name);
+
+-- Number of creators with each tag, kept up to date when creators change their tags
+-- so listing tags does not count every user_tags row.
+CREATE TABLE IF NOT EXISTS tag_counts (
+    name VARCHAR(255) PRIMARY KEY,
+    creators_count INTEGER NOT NULL DEFAULT 0,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO tag_counts (name, creators_count)
+SELECT name, COUNT(*) FROM user_tags GROUP BY name
+ON CONFLICT (name) DO NOTHING;
@@ -0,0 +1,32 @@
+-- Add migration script here
+-- Blocked users cannot see or interact with the blocker, and the other way around.
+CREATE TABLE IF NOT EXISTS user_blocks (
+    blocker_id uuid NOT NULL,
+    blocked_id uuid NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (blocker_id, blocked_id),
+    CONSTRAINT fk_blocker_id
+    FOREIGN KEY(blocker_id) REFERENCES users(id)
+    ON DELETE NO ACTION,
+    CONSTRAINT fk_blocked_id
+    FOREIGN KEY(blocked_id) REFERENCES users(id)
+    ON DELETE NO ACTION,
+    CONSTRAINT user_blocks_not_self_check CHECK (blocker_id <> blocked_id)
+);
+
+CREATE INDEX IF NOT EXISTS user_blocks_blocked_id_idx ON user_blocks(blocked_id);
+
+-- Muted users are only hidden from the timeline of the muter.
+CREATE TABLE IF NOT EXISTS user_mutes (
+    muter_id uuid NOT NULL,
+    muted_id uuid NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (muter_id, muted_id),
+    CONSTRAINT fk_muter_id
+    FOREIGN KEY(muter_id) REFERENCES users(id)
+    ON DELETE NO ACTION,
+    CONSTRAINT fk_muted_id
+    FOREIGN KEY(muted_id) REFERENCES users(id)
+    ON DELETE NO ACTION,
+    CONSTRAINT user_mutes_not_self_check CHECK (muter_id <> muted_id)
+);
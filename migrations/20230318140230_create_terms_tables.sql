@@ -0,0 +1,35 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS terms_versions (
+    version VARCHAR(32) PRIMARY KEY,
+    -- Users must accept the version with the most recent published_at
+    -- that is not in the future.
+    published_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS terms_versions_published_at_idx ON terms_versions(published_at);
+
+-- Every time a user accepted a version of the terms.
+CREATE TABLE IF NOT EXISTS terms_acceptances (
+    id uuid PRIMARY KEY,
+    user_id uuid NOT NULL,
+    terms_version VARCHAR(32) NOT NULL,
+    accepted_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT fk_user_id
+    FOREIGN KEY(user_id) REFERENCES users(id)
+    ON DELETE NO ACTION,
+    CONSTRAINT fk_terms_version
+    FOREIGN KEY(terms_version) REFERENCES terms_versions(version)
+    ON DELETE NO ACTION
+);
+
+CREATE INDEX IF NOT EXISTS terms_acceptances_user_id_terms_version_idx ON terms_acceptances(user_id, terms_version);
+
+-- The terms users accepted before versions were tracked.
+INSERT INTO terms_versions (version, published_at)
+VALUES ('2023-01-11', '2023-01-11T00:00:00Z')
+ON CONFLICT (version) DO NOTHING;
+
+INSERT INTO terms_acceptances (id, user_id, terms_version, accepted_at)
+SELECT uuid_generate_v4(), id, '2023-01-11', accepted_terms_at AT TIME ZONE 'UTC'
+FROM users;
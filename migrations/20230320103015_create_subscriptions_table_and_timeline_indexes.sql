@@ -0,0 +1,23 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS subscriptions (
+    id uuid PRIMARY KEY,
+    subscriber_id uuid NOT NULL,
+    creator_id uuid NOT NULL,
+    started_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- The subscription is active until it expires.
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    CONSTRAINT fk_subscriber_id
+    FOREIGN KEY(subscriber_id) REFERENCES users(id)
+    ON DELETE NO ACTION,
+    CONSTRAINT fk_creator_id
+    FOREIGN KEY(creator_id) REFERENCES users(id)
+    ON DELETE NO ACTION
+);
+
+CREATE INDEX IF NOT EXISTS subscriptions_subscriber_id_creator_id_expires_at_idx ON subscriptions(subscriber_id, creator_id, expires_at);
+
+-- The explore timeline walks every post from the newest one.
+CREATE INDEX IF NOT EXISTS posts_created_at_id_idx ON posts(created_at DESC, id DESC);
+
+-- The following timeline walks the posts of each followed creator from the newest one.
+CREATE INDEX IF NOT EXISTS posts_creator_id_created_at_id_idx ON posts(creator_id, created_at DESC, id DESC);
@@ -0,0 +1,4 @@
+-- Add migration script here
+-- Shown to viewers that cannot watch paid posts.
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS thumbnail_url VARCHAR(255);
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS duration_secs INT;
@@ -0,0 +1,11 @@
+-- Add migration script here
+-- Drafts are not published yet, and scheduled posts are published in the future.
+-- Only the creator can see them until they are published.
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS published_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP;
+
+UPDATE posts SET published_at = created_at;
+
+-- Pinned posts come first in the creator feed.
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS pinned_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX IF NOT EXISTS posts_creator_id_pinned_at_idx ON posts(creator_id, pinned_at) WHERE pinned_at IS NOT NULL;
@@ -0,0 +1,7 @@
+-- Add migration script here
+-- The videos bucket is private, viewers get a short lived url from the api
+-- to watch a video, so posts only keep the key of the video in the bucket.
+ALTER TABLE posts RENAME COLUMN video_url TO video_key;
+
+-- Videos are stored under their id at the root of the bucket.
+UPDATE posts SET video_key = regexp_replace(video_key, '^.*/', '');
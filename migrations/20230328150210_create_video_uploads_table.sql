@@ -0,0 +1,19 @@
+-- Add migration script here
+-- Videos the user has been allowed to upload, a post can be created
+-- with each of them once the video and its thumbnail have been uploaded.
+-- The id is the key of the video in the videos bucket.
+CREATE TABLE IF NOT EXISTS video_uploads (
+    id uuid PRIMARY KEY,
+    user_id uuid NOT NULL,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    post_id uuid,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT fk_user_id
+    FOREIGN KEY(user_id) REFERENCES users(id)
+    ON DELETE NO ACTION,
+    CONSTRAINT fk_post_id
+    FOREIGN KEY(post_id) REFERENCES posts(id)
+    ON DELETE NO ACTION
+);
+
+CREATE INDEX IF NOT EXISTS video_uploads_user_id_idx ON video_uploads(user_id);
@@ -0,0 +1,14 @@
+-- Add migration script here
+-- Timelines are ordered by the time posts were published, so scheduled posts
+-- show up at the top of the timelines when they go live.
+DROP INDEX IF EXISTS posts_created_at_id_idx;
+DROP INDEX IF EXISTS posts_creator_id_created_at_id_idx;
+
+-- The explore timeline walks every post from the most recently published one.
+CREATE INDEX IF NOT EXISTS posts_published_at_id_idx ON posts(published_at DESC, id DESC);
+
+-- The following timeline walks the posts of each followed creator from the most recently published one.
+CREATE INDEX IF NOT EXISTS posts_creator_id_published_at_id_idx ON posts(creator_id, published_at DESC, id DESC);
+
+-- The creator feed also has drafts, which are ordered by the time they were created.
+CREATE INDEX IF NOT EXISTS posts_creator_id_feed_idx ON posts(creator_id, COALESCE(published_at, created_at) DESC, id DESC);
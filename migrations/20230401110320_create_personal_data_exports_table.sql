@@ -0,0 +1,19 @@
+-- Add migration script here
+-- Personal data exports are built in the background, the archive can be
+-- downloaded once `completed_at` is set and until `expires_at` has passed.
+CREATE TABLE IF NOT EXISTS personal_data_exports (
+    id uuid PRIMARY KEY,
+    user_id uuid NOT NULL,
+    object_key VARCHAR(1024),
+    expires_at TIMESTAMP WITH TIME ZONE,
+    completed_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT fk_user_id
+    FOREIGN KEY(user_id) REFERENCES users(id)
+    ON DELETE NO ACTION
+);
+
+CREATE INDEX IF NOT EXISTS personal_data_exports_user_id_created_at_idx ON personal_data_exports(user_id, created_at);
+
+-- Users have at most one export waiting to be built.
+CREATE UNIQUE INDEX IF NOT EXISTS personal_data_exports_pending_user_id_idx ON personal_data_exports(user_id) WHERE completed_at IS NULL;
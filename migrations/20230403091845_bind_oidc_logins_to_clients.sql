@@ -0,0 +1,7 @@
+-- Add migration script here
+-- sha256 of the verifier kept by the client that started the login,
+-- the callback must send it along with the state.
+ALTER TABLE oidc_authorization_requests ADD COLUMN IF NOT EXISTS verifier_hash VARCHAR(64);
+
+-- Set when the login was started by a logged in user to link the identity to their account.
+ALTER TABLE oidc_authorization_requests ADD COLUMN IF NOT EXISTS link_user_id uuid REFERENCES users(id) ON DELETE NO ACTION;
@@ -0,0 +1,13 @@
+-- Add migration script here
+-- Mfa tokens that were exchanged for a session, so they cannot be exchanged again.
+CREATE TABLE IF NOT EXISTS used_mfa_tokens (
+    -- The `jti` claim of the token.
+    jti uuid PRIMARY KEY,
+    user_id uuid NOT NULL,
+    -- The token is rejected after this anyway, the row can be removed then.
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    used_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT fk_user_id
+    FOREIGN KEY(user_id) REFERENCES users(id)
+    ON DELETE NO ACTION
+);
@@ -1,6 +1,6 @@
-use std::{env::VarError, str::FromStr, time::Duration};
+use std::{env::VarError, fmt::Debug, path::PathBuf, str::FromStr, time::Duration};
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 
 #[derive(Debug)]
 pub struct Config {
@@ -10,6 +10,16 @@ pub struct Config {
     pub database_ro_url: Option<String>,
     pub database_rw_url: Option<String>,
     pub database_max_connections: u32,
+    pub jwt: JwtConfig,
+    pub cursor: CursorConfig,
+    pub mailer: MailerConfig,
+    /// Number of proxies in front of the api that append to the X-Forwarded-For header.
+    /// The client address is the one added by the outermost of them, entries before it
+    /// are sent by the client and can have any value.
+    /// Zero when the api is not behind a proxy, the header is ignored then.
+    pub trusted_proxy_hops: usize,
+    /// OpenID Connect login is disabled when not configured.
+    pub oidc: Option<OidcConfig>,
 }
 
 #[derive(Debug)]
@@ -24,13 +34,154 @@ pub struct S3Config {
     pub presigned_url_expires_in_secs: Duration,
 }
 
+#[derive(Debug)]
+pub struct JwtConfig {
+    /// Keys that may have been used to sign tokens that are still valid.
+    pub signing_keys: Vec<SigningKey>,
+    /// Id of the key used to sign new tokens.
+    pub active_key_id: String,
+}
+
+pub struct SigningKey {
+    /// Goes in the `kid` header of the tokens signed with this key.
+    pub kid: String,
+    pub secret: String,
+}
+
+impl Debug for SigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SigningKey")
+            .field("kid", &self.kid)
+            .field("secret", &"<redacted>")
+            .finish()
+    }
+}
+
+pub struct CursorConfig {
+    /// Signs the pagination cursors handed to clients so they cannot be tampered with.
+    pub secret: String,
+}
+
+impl Debug for CursorConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CursorConfig")
+            .field("secret", &"<redacted>")
+            .finish()
+    }
+}
+
+#[derive(Debug)]
+pub struct MailerConfig {
+    /// Address that goes in the From header of every email we send.
+    pub from: String,
+    /// Required outside of the local env.
+    pub smtp: Option<SmtpConfig>,
+    /// Emails are written to this directory instead of being sent
+    /// when smtp is not configured in the local env.
+    pub drop_dir: PathBuf,
+}
+
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+}
+
+impl Debug for SmtpConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SmtpConfig")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("username", &self.username)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
+
+#[derive(Clone)]
+pub struct OidcConfig {
+    /// The provider metadata is discovered from `{issuer_url}/.well-known/openid-configuration`.
+    pub issuer_url: String,
+    pub client_id: String,
+    /// Not needed by public clients.
+    pub client_secret: Option<String>,
+    /// Where the issuer sends the user back to with the authorization code.
+    pub redirect_uri: String,
+}
+
+impl Debug for OidcConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OidcConfig")
+            .field("issuer_url", &self.issuer_url)
+            .field("client_id", &self.client_id)
+            .field("client_secret", &"<redacted>")
+            .field("redirect_uri", &self.redirect_uri)
+            .finish()
+    }
+}
+
 pub const LOCAL_ENV: &str = "local";
 
+/// Used to sign tokens in the local env when no key is configured.
+const LOCAL_SIGNING_KEY: (&str, &str) = ("local", "some-secret");
+
+/// Used to sign cursors in the local env when no secret is configured.
+const LOCAL_CURSOR_SECRET: &str = "some-cursor-secret";
+
 impl Config {
     #[tracing::instrument(name = "Config::from_env", skip_all)]
     pub fn from_env() -> Result<Self> {
-        Ok(Self {
-            env: env("ENV")?,
+        let env_name: String = env("ENV")?;
+
+        let mut signing_keys = match opt_env::<String>("JWT_SIGNING_KEYS")? {
+            None => vec![],
+            Some(value) => parse_signing_keys(&value)?,
+        };
+
+        if signing_keys.is_empty() && env_name.eq_ignore_ascii_case(LOCAL_ENV) {
+            signing_keys.push(SigningKey {
+                kid: LOCAL_SIGNING_KEY.0.to_owned(),
+                secret: LOCAL_SIGNING_KEY.1.to_owned(),
+            });
+        }
+
+        let active_key_id = match opt_env("JWT_ACTIVE_KEY_ID")? {
+            Some(kid) => kid,
+            None => signing_keys
+                .first()
+                .map(|key| key.kid.clone())
+                .unwrap_or_default(),
+        };
+
+        let cursor_secret = match opt_env::<String>("CURSOR_SECRET")? {
+            Some(secret) => secret,
+            None if env_name.eq_ignore_ascii_case(LOCAL_ENV) => LOCAL_CURSOR_SECRET.to_owned(),
+            None => String::new(),
+        };
+
+        let smtp = match opt_env::<String>("SMTP_HOST")? {
+            None => None,
+            Some(host) => Some(SmtpConfig {
+                host,
+                port: opt_env("SMTP_PORT")?.unwrap_or(465),
+                username: env("SMTP_USERNAME")?,
+                password: env("SMTP_PASSWORD")?,
+            }),
+        };
+
+        let oidc = match opt_env::<String>("OIDC_ISSUER_URL")? {
+            None => None,
+            Some(issuer_url) => Some(OidcConfig {
+                issuer_url,
+                client_id: env("OIDC_CLIENT_ID")?,
+                client_secret: opt_env("OIDC_CLIENT_SECRET")?,
+                redirect_uri: env("OIDC_REDIRECT_URI")?,
+            }),
+        };
+
+        let config = Self {
+            env: env_name,
             aws: AwsConfig {
                 region: env("AWS_REGION")?,
                 local_endpoint: opt_env("AWS_LOCAL_ENDPOINT")?,
@@ -44,7 +195,61 @@ impl Config {
             database_ro_url: opt_env("DATABASE_RO_URL")?,
             database_rw_url: opt_env("DATABASE_RW_URL")?,
             database_max_connections: env("DATABASE_MAX_CONNECTIONS")?,
-        })
+            jwt: JwtConfig {
+                signing_keys,
+                active_key_id,
+            },
+            cursor: CursorConfig {
+                secret: cursor_secret,
+            },
+            mailer: MailerConfig {
+                from: env("MAILER_FROM")?,
+                smtp,
+                drop_dir: opt_env("MAILER_DROP_DIR")?
+                    .unwrap_or_else(|| std::env::temp_dir().join("betarme-mail")),
+            },
+            trusted_proxy_hops: opt_env("TRUSTED_PROXY_HOPS")?.unwrap_or(0),
+            oidc,
+        };
+
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    #[tracing::instrument(name = "Config::validate", skip_all)]
+    fn validate(&self) -> Result<()> {
+        if self.jwt.signing_keys.is_empty() {
+            bail!("no jwt signing key configured. key=JWT_SIGNING_KEYS");
+        }
+
+        if !self
+            .jwt
+            .signing_keys
+            .iter()
+            .any(|key| key.kid == self.jwt.active_key_id)
+        {
+            bail!(
+                "active jwt signing key is not in JWT_SIGNING_KEYS. kid={}",
+                self.jwt.active_key_id
+            );
+        }
+
+        if self.cursor.secret.is_empty() {
+            bail!("no cursor secret configured. key=CURSOR_SECRET");
+        }
+
+        if self.mailer.smtp.is_none() && !self.is_local_env() {
+            bail!("smtp must be configured outside of the local env. key=SMTP_HOST");
+        }
+
+        if let Some(oidc) = &self.oidc {
+            if !oidc.issuer_url.starts_with("https://") && !self.is_local_env() {
+                bail!("oidc issuer must use https outside of the local env. key=OIDC_ISSUER_URL");
+            }
+        }
+
+        Ok(())
     }
 
     #[tracing::instrument(name = "Config::is_local_env", skip_all, fields(local))]
@@ -55,6 +260,36 @@ impl Config {
     }
 }
 
+/// Parses keys in the format `kid1:secret1,kid2:secret2`.
+fn parse_signing_keys(value: &str) -> Result<Vec<SigningKey>> {
+    let mut keys: Vec<SigningKey> = Vec::new();
+
+    for pair in value
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+    {
+        let (kid, secret) = pair
+            .split_once(':')
+            .context("jwt signing key must be in the format kid:secret")?;
+
+        if kid.is_empty() || secret.is_empty() {
+            bail!("jwt signing key must have a kid and a secret");
+        }
+
+        if keys.iter().any(|key| key.kid == kid) {
+            bail!("duplicated jwt signing key. kid={kid}");
+        }
+
+        keys.push(SigningKey {
+            kid: kid.to_owned(),
+            secret: secret.to_owned(),
+        });
+    }
+
+    Ok(keys)
+}
+
 #[tracing::instrument(name = "config::env", skip_all, fields(key = %key))]
 fn env<T: FromStr>(key: &str) -> Result<T>
 where
@@ -0,0 +1,97 @@
+use crate::{
+    domain::{
+        constants::API_KEY_PREFIX,
+        contracts::{context::Context, deps::Deps},
+        value_objects::{api_key_scope::ApiKeyScope, opaque_token::OpaqueToken, role::Role},
+    },
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+use tracing::info;
+
+/// Number of characters of the key that are stored in plain text
+/// so users can tell their keys apart.
+const DISPLAYED_PREFIX_LEN: usize = 8;
+
+#[derive(Debug)]
+pub struct CreateApiKeyInput {
+    pub user_id: Uuid,
+    pub role: Role,
+    /// Api keys skip the second factor, so only sessions created
+    /// with it can create them.
+    pub mfa_authenticated: bool,
+    pub name: String,
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+pub struct CreateApiKeyOutput {
+    pub id: Uuid,
+    /// Returned only once, we store just its hash.
+    pub key: String,
+    pub prefix: String,
+}
+
+#[derive(Debug)]
+pub struct NewApiKey {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub prefix: String,
+    pub secret_hash: String,
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CreateApiKeyError {
+    #[error("user is not allowed to create api keys")]
+    UserNotAllowedToCreateApiKeys,
+    #[error("two-factor authentication is required to create api keys")]
+    MfaRequired,
+}
+
+#[tracing::instrument(name = "commands::api_key::create", skip_all, fields(
+    ctx = ?ctx,
+    user_id = %input.user_id,
+))]
+pub async fn create(
+    deps: &Deps,
+    ctx: &Context,
+    input: CreateApiKeyInput,
+) -> Result<CreateApiKeyOutput> {
+    if input.role != Role::Creator {
+        info!("user is not allowed to create api keys");
+        return Err(CreateApiKeyError::UserNotAllowedToCreateApiKeys.into());
+    }
+
+    if !input.mfa_authenticated {
+        info!("session was not created using two-factor authentication");
+        return Err(CreateApiKeyError::MfaRequired.into());
+    }
+
+    let secret = OpaqueToken::generate();
+
+    let key = format!("{API_KEY_PREFIX}{}", secret.expose());
+
+    let prefix = key[..API_KEY_PREFIX.len() + DISPLAYED_PREFIX_LEN].to_owned();
+
+    let id = Uuid::new_v4();
+
+    deps.repos
+        .api_keys
+        .create(
+            &mut deps.db.write().await?,
+            NewApiKey {
+                id,
+                user_id: input.user_id,
+                name: input.name,
+                prefix: prefix.clone(),
+                secret_hash: secret.hash(),
+                scopes: input.scopes,
+            },
+        )
+        .await?;
+
+    info!(api_key_id = %id, "api key created");
+
+    Ok(CreateApiKeyOutput { id, key, prefix })
+}
@@ -0,0 +1,7 @@
+mod create;
+mod record_usage;
+mod revoke;
+
+pub use create::*;
+pub use record_usage::*;
+pub use revoke::*;
@@ -0,0 +1,13 @@
+use crate::{domain::contracts::deps::Deps, infra::uuid::Uuid};
+use anyhow::Result;
+
+/// Updates when the key was last used, so users can find keys they do not need anymore.
+#[tracing::instrument(name = "commands::api_key::record_usage", skip_all, fields(
+    api_key_id = %api_key_id
+))]
+pub async fn record_usage(deps: &Deps, api_key_id: Uuid) -> Result<()> {
+    deps.repos
+        .api_keys
+        .mark_as_used(&mut deps.db.write().await?, api_key_id)
+        .await
+}
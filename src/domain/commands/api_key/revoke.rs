@@ -0,0 +1,41 @@
+use crate::{
+    domain::contracts::{context::Context, deps::Deps},
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+use tracing::info;
+
+#[derive(Debug)]
+pub struct RevokeApiKeyInput {
+    /// Users can only revoke their own keys.
+    pub user_id: Uuid,
+    pub api_key_id: Uuid,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RevokeApiKeyError {
+    #[error("api key not found")]
+    NotFound,
+}
+
+/// Requests using the key are rejected from now on.
+#[tracing::instrument(name = "commands::api_key::revoke", skip_all, fields(
+    ctx = ?ctx,
+    input = ?input,
+))]
+pub async fn revoke(deps: &Deps, ctx: &Context, input: RevokeApiKeyInput) -> Result<()> {
+    let revoked = deps
+        .repos
+        .api_keys
+        .revoke(&mut deps.db.write().await?, input.user_id, input.api_key_id)
+        .await?;
+
+    if !revoked {
+        info!("api key does not exist, belongs to another user or is already revoked");
+        return Err(RevokeApiKeyError::NotFound.into());
+    }
+
+    info!("api key revoked");
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use crate::{
+    domain::{
+        contracts::{context::Context, deps::Deps},
+        value_objects::{creator_tag::CreatorTag, role::Role},
+    },
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+use tracing::info;
+
+#[derive(Debug)]
+pub struct SetCreatorTagsInput {
+    pub user_id: Uuid,
+    pub role: Role,
+    /// Replaces every tag of the creator.
+    pub tags: Vec<CreatorTag>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SetCreatorTagsError {
+    #[error("only creators can have tags")]
+    UserNotAllowedToHaveTags,
+}
+
+/// Replaces the tags of the creator and updates the number of creators
+/// with each tag. Returns the tags the creator has now.
+#[tracing::instrument(name = "commands::creator_tag::set", skip_all, fields(
+    ctx = ?ctx,
+    input = ?input
+))]
+pub async fn set(
+    deps: &Deps,
+    ctx: &Context,
+    input: SetCreatorTagsInput,
+) -> Result<Vec<CreatorTag>> {
+    if input.role != Role::Creator {
+        info!("user is not allowed to have tags");
+        return Err(SetCreatorTagsError::UserNotAllowedToHaveTags.into());
+    }
+
+    let mut tx = deps.db.write().await?.transaction().await?;
+
+    let current = deps
+        .repos
+        .creator_tags
+        .list_by_user(&mut tx, input.user_id)
+        .await?;
+
+    let to_remove: Vec<CreatorTag> = current
+        .iter()
+        .filter(|tag| !input.tags.contains(tag))
+        .copied()
+        .collect();
+
+    let to_add: Vec<CreatorTag> = input
+        .tags
+        .iter()
+        .filter(|tag| !current.contains(tag))
+        .copied()
+        .collect();
+
+    // Only the rows this transaction changed are counted, so concurrent
+    // requests of the same creator do not count a tag twice.
+    let removed = deps
+        .repos
+        .creator_tags
+        .remove(&mut tx, input.user_id, &to_remove)
+        .await?;
+
+    let added = deps
+        .repos
+        .creator_tags
+        .add(&mut tx, input.user_id, &to_add)
+        .await?;
+
+    deps.repos
+        .creator_tags
+        .update_counts(&mut tx, &removed, -1)
+        .await?;
+
+    deps.repos
+        .creator_tags
+        .update_counts(&mut tx, &added, 1)
+        .await?;
+
+    let tags = deps
+        .repos
+        .creator_tags
+        .list_by_user(&mut tx, input.user_id)
+        .await?;
+
+    tx.commit().await?;
+
+    info!(?added, ?removed, "creator tags updated");
+
+    Ok(tags)
+}
@@ -0,0 +1,105 @@
+use crate::{
+    domain::{
+        contracts::{context::Context, deps::Deps},
+        value_objects::role::Role,
+    },
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+use tracing::info;
+
+#[derive(Debug)]
+pub struct FollowInput {
+    pub follower_id: Uuid,
+    pub followee_id: Uuid,
+}
+
+#[derive(Debug)]
+pub struct UnfollowInput {
+    pub follower_id: Uuid,
+    pub followee_id: Uuid,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FollowError {
+    #[error("user not found")]
+    NotFound,
+    #[error("only creators can be followed")]
+    NotACreator,
+    #[error("users cannot follow themselves")]
+    CannotFollowYourself,
+}
+
+/// Following a creator that is already followed does nothing.
+#[tracing::instrument(name = "commands::follow::follow", skip_all, fields(
+    ctx = ?ctx,
+    input = ?input
+))]
+pub async fn follow(deps: &Deps, ctx: &Context, input: FollowInput) -> Result<()> {
+    if input.follower_id == input.followee_id {
+        info!("user tried to follow themselves");
+        return Err(FollowError::CannotFollowYourself.into());
+    }
+
+    let mut executor = deps.db.write().await?;
+
+    let followee = match deps
+        .repos
+        .users
+        .get_by_id(&mut executor, input.followee_id)
+        .await?
+    {
+        None => {
+            info!("followee does not exist");
+            return Err(FollowError::NotFound.into());
+        }
+        Some(followee) => followee,
+    };
+
+    // Users that blocked each other cannot see each other.
+    if deps
+        .repos
+        .user_blocks
+        .is_blocked_between(&mut executor, input.follower_id, input.followee_id)
+        .await?
+    {
+        info!("users blocked each other");
+        return Err(FollowError::NotFound.into());
+    }
+
+    if followee.role != Role::Creator {
+        info!("followee is not a creator");
+        return Err(FollowError::NotACreator.into());
+    }
+
+    let created = deps
+        .repos
+        .user_followers
+        .create(&mut executor, input.follower_id, input.followee_id)
+        .await?;
+
+    info!(created, "user followed");
+
+    Ok(())
+}
+
+/// Unfollowing a user that is not followed does nothing.
+#[tracing::instrument(name = "commands::follow::unfollow", skip_all, fields(
+    ctx = ?ctx,
+    input = ?input
+))]
+pub async fn unfollow(deps: &Deps, ctx: &Context, input: UnfollowInput) -> Result<()> {
+    let deleted = deps
+        .repos
+        .user_followers
+        .delete(
+            &mut deps.db.write().await?,
+            input.follower_id,
+            input.followee_id,
+        )
+        .await?;
+
+    info!(deleted, "user unfollowed");
+
+    Ok(())
+}
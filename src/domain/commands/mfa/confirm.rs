@@ -0,0 +1,116 @@
+use crate::{
+    domain::{
+        constants::MFA_RECOVERY_CODES_COUNT,
+        contracts::{context::Context, deps::Deps},
+        value_objects::recovery_code::RecoveryCode,
+    },
+    infra::uuid::Uuid,
+};
+use anyhow::{Context as _, Result};
+use chrono::Utc;
+use tracing::info;
+
+pub struct ConfirmTotpInput {
+    pub user_id: Uuid,
+    /// Code generated by the authenticator app using the secret returned by the enrollment.
+    pub code: String,
+}
+
+pub struct ConfirmTotpOutput {
+    /// Shown only once, we store just their hashes.
+    pub recovery_codes: Vec<RecoveryCode>,
+}
+
+#[derive(Debug)]
+pub struct NewMfaRecoveryCode {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub code_hash: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfirmTotpError {
+    #[error("two-factor authentication enrollment has not been started")]
+    NotEnrolled,
+    #[error("two-factor authentication is already enabled")]
+    AlreadyEnabled,
+    #[error("two-factor authentication code is invalid")]
+    InvalidCode,
+}
+
+/// Enables two-factor authentication once the user proves their
+/// authenticator app generates valid codes. Sessions created from now on
+/// require the second factor.
+#[tracing::instrument(name = "commands::mfa::confirm", skip_all, fields(
+    ctx = ?ctx,
+    user_id = %input.user_id,
+))]
+pub async fn confirm(
+    deps: &Deps,
+    ctx: &Context,
+    input: ConfirmTotpInput,
+) -> Result<ConfirmTotpOutput> {
+    let mut tx = deps.db.write().await?.transaction().await?;
+
+    let user = deps
+        .repos
+        .users
+        .get_mfa_for_update(&mut tx, input.user_id)
+        .await?
+        .context("user does not exist")?;
+
+    if user.mfa_enabled {
+        info!("mfa is already enabled");
+        return Err(ConfirmTotpError::AlreadyEnabled.into());
+    }
+
+    let secret = match user.totp_secret {
+        None => {
+            info!("mfa enrollment has not been started");
+            return Err(ConfirmTotpError::NotEnrolled.into());
+        }
+        Some(secret) => secret,
+    };
+
+    let step = match secret.verify(&input.code, Utc::now().timestamp()) {
+        None => {
+            info!("invalid totp code");
+            return Err(ConfirmTotpError::InvalidCode.into());
+        }
+        Some(step) => step,
+    };
+
+    deps.repos
+        .users
+        .enable_mfa(&mut tx, input.user_id, step)
+        .await?;
+
+    deps.repos
+        .mfa_recovery_codes
+        .delete_all_for_user(&mut tx, input.user_id)
+        .await?;
+
+    let recovery_codes: Vec<RecoveryCode> = (0..MFA_RECOVERY_CODES_COUNT)
+        .map(|_| RecoveryCode::generate())
+        .collect();
+
+    for code in recovery_codes.iter() {
+        deps.repos
+            .mfa_recovery_codes
+            .create(
+                &mut tx,
+                NewMfaRecoveryCode {
+                    id: Uuid::new_v4(),
+                    user_id: input.user_id,
+                    code_hash: code.hash(),
+                },
+            )
+            .await?;
+    }
+
+    tx.commit().await?;
+
+    info!("mfa has been enabled");
+
+    Ok(ConfirmTotpOutput { recovery_codes })
+}
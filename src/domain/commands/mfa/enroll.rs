@@ -0,0 +1,98 @@
+use crate::{
+    domain::{
+        constants::TOTP_ISSUER,
+        contracts::{context::Context, deps::Deps},
+        value_objects::{role::Role, totp::TotpSecret},
+    },
+    infra::uuid::Uuid,
+};
+use anyhow::{Context as _, Result};
+use qrcode::{render::svg, QrCode};
+use tracing::info;
+
+#[derive(Debug)]
+pub struct EnrollTotpInput {
+    pub user_id: Uuid,
+    pub role: Role,
+}
+
+pub struct EnrollTotpOutput {
+    /// Shown to users that cannot scan the qr code.
+    pub secret: TotpSecret,
+    pub otpauth_uri: String,
+    /// The otpauth uri rendered as a qr code.
+    pub qrcode_svg: String,
+}
+
+/// Two-factor authentication state of a user.
+pub struct UserMfa {
+    pub email: String,
+    /// Set when the user starts the enrollment.
+    pub totp_secret: Option<TotpSecret>,
+    /// True after the user confirms the enrollment with a valid code.
+    pub mfa_enabled: bool,
+    /// Step of the last totp code accepted, used to reject codes that were already used.
+    pub totp_last_used_step: Option<i64>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EnrollTotpError {
+    #[error("user is not allowed to enable two-factor authentication")]
+    UserNotAllowedToEnableMfa,
+    #[error("two-factor authentication is already enabled")]
+    AlreadyEnabled,
+}
+
+/// Generates a new totp secret for the user. Two-factor authentication is
+/// enabled only after the user confirms they can generate codes with it.
+#[tracing::instrument(name = "commands::mfa::enroll", skip_all, fields(
+    ctx = ?ctx,
+    input = ?input,
+))]
+pub async fn enroll(
+    deps: &Deps,
+    ctx: &Context,
+    input: EnrollTotpInput,
+) -> Result<EnrollTotpOutput> {
+    // Creators hold revenue, admins can ban users.
+    if !matches!(input.role, Role::Creator | Role::Admin) {
+        info!("user is not allowed to enable mfa");
+        return Err(EnrollTotpError::UserNotAllowedToEnableMfa.into());
+    }
+
+    let mut tx = deps.db.write().await?.transaction().await?;
+
+    let user = deps
+        .repos
+        .users
+        .get_mfa_for_update(&mut tx, input.user_id)
+        .await?
+        .context("user does not exist")?;
+
+    if user.mfa_enabled {
+        info!("mfa is already enabled");
+        return Err(EnrollTotpError::AlreadyEnabled.into());
+    }
+
+    // Starting the enrollment again replaces the secret that was not confirmed.
+    let secret = TotpSecret::generate();
+
+    deps.repos
+        .users
+        .set_totp_secret(&mut tx, input.user_id, &secret)
+        .await?;
+
+    tx.commit().await?;
+
+    let otpauth_uri = secret.otpauth_uri(TOTP_ISSUER, &user.email);
+
+    let code = QrCode::new(otpauth_uri.as_bytes()).context("generating qrcode")?;
+
+    let qrcode_svg = code.render::<svg::Color>().min_dimensions(200, 200).build();
+
+    Ok(EnrollTotpOutput {
+        secret,
+        otpauth_uri,
+        qrcode_svg,
+    })
+}
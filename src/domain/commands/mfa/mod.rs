@@ -0,0 +1,5 @@
+mod confirm;
+mod enroll;
+
+pub use confirm::*;
+pub use enroll::*;
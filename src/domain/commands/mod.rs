@@ -1,3 +1,14 @@
+pub mod api_key;
+pub mod creator_tag;
+pub mod follow;
+pub mod mfa;
+pub mod moderation;
+pub mod object_purge;
+pub mod password_reset;
 pub mod pix_payment;
+pub mod post;
+pub mod relationship;
+pub mod session;
+pub mod terms;
 pub mod user;
 pub mod video;
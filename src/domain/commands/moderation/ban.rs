@@ -0,0 +1,206 @@
+use crate::{
+    domain::{
+        contracts::{context::Context, deps::Deps},
+        value_objects::role::Role,
+    },
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+use tracing::info;
+
+#[derive(Debug)]
+pub struct BanUserInput {
+    pub admin_id: Uuid,
+    pub role: Role,
+    pub user_id: Uuid,
+    /// Kept in the ban history.
+    pub reason: String,
+}
+
+#[derive(Debug)]
+pub struct UnbanUserInput {
+    pub admin_id: Uuid,
+    pub role: Role,
+    pub user_id: Uuid,
+    /// Kept in the ban history.
+    pub reason: String,
+}
+
+/// What we need to know about the user before banning or unbanning them.
+#[derive(Debug)]
+pub struct UserBanStatus {
+    pub role: Role,
+    pub banned: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserBanAction {
+    Ban,
+    Unban,
+}
+
+impl UserBanAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserBanAction::Ban => "ban",
+            UserBanAction::Unban => "unban",
+        }
+    }
+}
+
+/// An entry of the ban history.
+#[derive(Debug)]
+pub struct NewUserBan {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub admin_id: Uuid,
+    pub action: UserBanAction,
+    pub reason: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BanUserError {
+    #[error("user is not allowed to ban users")]
+    NotAllowed,
+    #[error("user not found")]
+    NotFound,
+    #[error("admins cannot be banned")]
+    CannotBanAdmins,
+    #[error("user is already banned")]
+    AlreadyBanned,
+    #[error("user is not banned")]
+    NotBanned,
+}
+
+/// Bans the user. Their sessions and api keys are revoked, and requests
+/// made with access tokens issued before the ban are rejected.
+#[tracing::instrument(name = "commands::moderation::ban_user", skip_all, fields(
+    ctx = ?ctx,
+    admin_id = %input.admin_id,
+    user_id = %input.user_id,
+))]
+pub async fn ban_user(deps: &Deps, ctx: &Context, input: BanUserInput) -> Result<()> {
+    if input.role != Role::Admin {
+        info!("user is not allowed to ban users");
+        return Err(BanUserError::NotAllowed.into());
+    }
+
+    let mut tx = deps.db.write().await?.transaction().await?;
+
+    let status = match deps
+        .repos
+        .users
+        .get_ban_status_for_update(&mut tx, input.user_id)
+        .await?
+    {
+        None => {
+            info!("user does not exist");
+            return Err(BanUserError::NotFound.into());
+        }
+        Some(status) => status,
+    };
+
+    if status.role == Role::Admin {
+        info!("tried to ban an admin");
+        return Err(BanUserError::CannotBanAdmins.into());
+    }
+
+    if status.banned {
+        info!("user is already banned");
+        return Err(BanUserError::AlreadyBanned.into());
+    }
+
+    deps.repos
+        .users
+        .set_banned(&mut tx, input.user_id, true)
+        .await?;
+
+    deps.repos
+        .refresh_tokens
+        .revoke_all_for_user(&mut tx, input.user_id)
+        .await?;
+
+    deps.repos
+        .api_keys
+        .revoke_all_for_user(&mut tx, input.user_id)
+        .await?;
+
+    deps.repos
+        .user_bans
+        .create(
+            &mut tx,
+            NewUserBan {
+                id: Uuid::new_v4(),
+                user_id: input.user_id,
+                admin_id: input.admin_id,
+                action: UserBanAction::Ban,
+                reason: input.reason,
+            },
+        )
+        .await?;
+
+    tx.commit().await?;
+
+    info!("user banned");
+
+    Ok(())
+}
+
+/// Lifts the ban. The user has to log in again because their sessions
+/// were revoked when they were banned.
+#[tracing::instrument(name = "commands::moderation::unban_user", skip_all, fields(
+    ctx = ?ctx,
+    admin_id = %input.admin_id,
+    user_id = %input.user_id,
+))]
+pub async fn unban_user(deps: &Deps, ctx: &Context, input: UnbanUserInput) -> Result<()> {
+    if input.role != Role::Admin {
+        info!("user is not allowed to unban users");
+        return Err(BanUserError::NotAllowed.into());
+    }
+
+    let mut tx = deps.db.write().await?.transaction().await?;
+
+    let status = match deps
+        .repos
+        .users
+        .get_ban_status_for_update(&mut tx, input.user_id)
+        .await?
+    {
+        None => {
+            info!("user does not exist");
+            return Err(BanUserError::NotFound.into());
+        }
+        Some(status) => status,
+    };
+
+    if !status.banned {
+        info!("user is not banned");
+        return Err(BanUserError::NotBanned.into());
+    }
+
+    deps.repos
+        .users
+        .set_banned(&mut tx, input.user_id, false)
+        .await?;
+
+    deps.repos
+        .user_bans
+        .create(
+            &mut tx,
+            NewUserBan {
+                id: Uuid::new_v4(),
+                user_id: input.user_id,
+                admin_id: input.admin_id,
+                action: UserBanAction::Unban,
+                reason: input.reason,
+            },
+        )
+        .await?;
+
+    tx.commit().await?;
+
+    info!("user unbanned");
+
+    Ok(())
+}
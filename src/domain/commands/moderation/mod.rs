@@ -0,0 +1,3 @@
+mod ban;
+
+pub use ban::*;
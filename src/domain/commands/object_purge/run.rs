@@ -0,0 +1,61 @@
+use crate::domain::constants::OBJECT_PURGE_BATCH_SIZE;
+use crate::domain::contracts::{context::Context, deps::Deps};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use tracing::info;
+
+#[derive(Debug)]
+pub struct NewObjectPurge {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub bucket: String,
+    /// Every object whose key starts with it is removed.
+    pub key_prefix: String,
+    pub purge_after: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct ObjectPurge {
+    pub id: Uuid,
+    pub bucket: String,
+    pub key_prefix: String,
+}
+
+/// Removes the objects of the purges whose time has come.
+/// Returns the number of purges that were run.
+///
+/// Purges are locked while they run so more than one instance
+/// of the api can run them at the same time.
+#[tracing::instrument(name = "commands::object_purge::run_due", skip_all, fields(ctx = ?ctx))]
+pub async fn run_due(deps: &Deps, ctx: &Context) -> Result<usize> {
+    let mut tx = deps.db.write().await?.transaction().await?;
+
+    let purges = deps
+        .repos
+        .object_purges
+        .get_due_for_update(&mut tx, OBJECT_PURGE_BATCH_SIZE)
+        .await?;
+
+    for purge in purges.iter() {
+        let keys = deps
+            .object_storage
+            .list_keys(&purge.bucket, &purge.key_prefix)
+            .await?;
+
+        for key in keys.iter() {
+            deps.object_storage.delete(&purge.bucket, key).await?;
+        }
+
+        deps.repos
+            .object_purges
+            .mark_as_purged(&mut tx, purge.id)
+            .await?;
+
+        info!(purge_id = %purge.id, objects = keys.len(), "objects purged");
+    }
+
+    tx.commit().await?;
+
+    Ok(purges.len())
+}
@@ -0,0 +1,71 @@
+use crate::domain::contracts::{context::Context, deps::Deps};
+use crate::domain::value_objects::{opaque_token::OpaqueToken, password::Password};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use tracing::info;
+
+pub struct ConfirmPasswordResetInput {
+    pub token: OpaqueToken,
+    /// Checked against the password policy once we know who the token belongs to.
+    pub new_password: String,
+}
+
+#[derive(Debug)]
+pub struct PasswordResetToken {
+    pub user_id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfirmPasswordResetError {
+    #[error("password reset token is invalid or has expired")]
+    InvalidToken,
+}
+
+/// Changes the password of the user that requested the reset and
+/// logs the user out of every session.
+#[tracing::instrument(name = "commands::password_reset::confirm", skip_all, fields(ctx = ?ctx))]
+pub async fn confirm(deps: &Deps, ctx: &Context, input: ConfirmPasswordResetInput) -> Result<()> {
+    let mut tx = deps.db.write().await?.transaction().await?;
+
+    let token = match deps
+        .repos
+        .password_reset_tokens
+        .get_by_token_hash_for_update(&mut tx, &input.token.hash())
+        .await?
+    {
+        Some(token) if token.used_at.is_none() && token.expires_at > Utc::now() => token,
+        _ => {
+            info!("password reset token is invalid, used or expired");
+            return Err(ConfirmPasswordResetError::InvalidToken.into());
+        }
+    };
+
+    let new_password = Password::new(&input.new_password, &token.username, &token.email)?;
+
+    deps.repos
+        .users
+        .update_password(&mut tx, token.user_id, new_password)
+        .await?;
+
+    // Other tokens sent to the user stop working as well.
+    deps.repos
+        .password_reset_tokens
+        .mark_all_as_used_for_user(&mut tx, token.user_id)
+        .await?;
+
+    deps.repos
+        .refresh_tokens
+        .revoke_all_for_user(&mut tx, token.user_id)
+        .await?;
+
+    tx.commit().await?;
+
+    info!(user_id = %token.user_id, "password has been reset");
+
+    Ok(())
+}
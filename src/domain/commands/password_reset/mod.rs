@@ -0,0 +1,5 @@
+mod confirm;
+mod request;
+
+pub use confirm::*;
+pub use request::*;
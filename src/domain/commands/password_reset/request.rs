@@ -0,0 +1,102 @@
+use crate::domain::constants::PASSWORD_RESET_TOKEN_EXPIRES_IN_SECS;
+use crate::domain::contracts::mailer::{Mail, Mailer};
+use crate::domain::contracts::repository::{Database, PasswordResetTokenRepository};
+use crate::domain::contracts::{context::Context, deps::Deps};
+use crate::domain::value_objects::opaque_token::OpaqueToken;
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
+use tracing::{error, info};
+
+pub struct RequestPasswordResetInput {
+    pub email: String,
+}
+
+#[derive(Debug)]
+pub struct NewPasswordResetToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Sends an email containing a token that can be used to choose a new password.
+/// Nothing happens if there's no user with the email.
+/// The email is sent after the function returns.
+#[tracing::instrument(name = "commands::password_reset::request", skip_all, fields(ctx = ?ctx))]
+pub async fn request(deps: &Deps, ctx: &Context, input: RequestPasswordResetInput) -> Result<()> {
+    let credentials = deps
+        .repos
+        .users
+        .get_credentials_by_email(&mut deps.db.read().await?, &input.email)
+        .await?;
+
+    let user_id = match credentials {
+        None => {
+            info!("password reset requested for unknown email");
+            return Ok(());
+        }
+        Some(credentials) => credentials.id,
+    };
+
+    // The token is stored and sent in the background so the response takes
+    // as long as it does for unknown emails and does not reveal which accounts exist.
+    let db = Arc::clone(&deps.db);
+    let password_reset_tokens = Arc::clone(&deps.repos.password_reset_tokens);
+    let mailer = Arc::clone(&deps.mailer);
+
+    tokio::spawn(async move {
+        let result = send_token(
+            db.as_ref(),
+            password_reset_tokens.as_ref(),
+            mailer.as_ref(),
+            user_id,
+            input.email,
+        )
+        .await;
+
+        if let Err(error) = result {
+            error!(?error, %user_id, "unable to send password reset token");
+        }
+    });
+
+    info!(%user_id, "password reset requested");
+
+    Ok(())
+}
+
+async fn send_token(
+    db: &dyn Database,
+    password_reset_tokens: &dyn PasswordResetTokenRepository,
+    mailer: &dyn Mailer,
+    user_id: Uuid,
+    email: String,
+) -> Result<()> {
+    let token = OpaqueToken::generate();
+
+    password_reset_tokens
+        .create(
+            &mut db.write().await?,
+            NewPasswordResetToken {
+                id: Uuid::new_v4(),
+                user_id,
+                token_hash: token.hash(),
+                expires_at: Utc::now() + Duration::seconds(PASSWORD_RESET_TOKEN_EXPIRES_IN_SECS),
+            },
+        )
+        .await?;
+
+    mailer
+        .send(Mail {
+            to: email,
+            subject: "Reset your password".to_owned(),
+            body: format!(
+                "Use the code below to choose a new password. It expires in {} minutes.\n\
+                If you did not ask to reset your password you can ignore this email.\n\n{}\n",
+                PASSWORD_RESET_TOKEN_EXPIRES_IN_SECS / 60,
+                token.expose()
+            ),
+        })
+        .await
+}
@@ -4,9 +4,14 @@ use crate::{
 };
 use anyhow::{Context as anyhowContext, Result};
 use qrcode::{Color, QrCode};
+use tracing::info;
 
 #[derive(Debug)]
 pub struct StartPixPaymentInput {
+    /// Id of the user that is paying.
+    pub user_id: Uuid,
+    /// Users must verify their email before paying.
+    pub email_verified: bool,
     pub creator_id: Uuid,
     pub subscription: SubscriptionInput,
 }
@@ -21,15 +26,38 @@ pub struct StartPixPaymentOutput {
     pub qrcode: Vec<Color>,
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum StartPixPaymentError {
+    #[error("email must be verified before starting a payment")]
+    EmailNotVerified,
+    #[error("cannot subscribe to this creator")]
+    Blocked,
+}
+
 #[tracing::instrument(name = "commands::pix_payment::start_payment", skip_all, fields(
     ctx = ?ctx,
     input = ?input
 ))]
 pub async fn start_payment(
-    _deps: &Deps,
+    deps: &Deps,
     ctx: &Context,
     input: StartPixPaymentInput,
 ) -> Result<StartPixPaymentOutput> {
+    if !input.email_verified {
+        info!("user has not verified their email");
+        return Err(StartPixPaymentError::EmailNotVerified.into());
+    }
+
+    if deps
+        .repos
+        .user_blocks
+        .is_blocked_between(&mut deps.db.read().await?, input.user_id, input.creator_id)
+        .await?
+    {
+        info!("user and creator blocked each other");
+        return Err(StartPixPaymentError::Blocked.into());
+    }
+
     let code =
         QrCode::new(b"https://www.youtube.com/watch?v=zAmHQk-OW3k").context("generating qrcode")?;
 
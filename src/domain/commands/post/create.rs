@@ -0,0 +1,136 @@
+use crate::domain::commands::video::thumbnail_key;
+use crate::domain::constants::THUMBNAIL_MAX_BYTES;
+use crate::domain::contracts::{context::Context, deps::Deps};
+use crate::domain::value_objects::{profile_image::ImageContentType, role::Role};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use chrono::Utc;
+use tracing::info;
+
+#[derive(Debug)]
+pub struct CreatePostInput {
+    pub user_id: Uuid,
+    pub role: Role,
+    /// Returned by `start_video_upload`, the video and its thumbnail
+    /// must have been uploaded already.
+    pub video_id: Uuid,
+    pub description: String,
+    pub paid: bool,
+    /// Shown in the preview of the post, even to viewers that cannot watch it.
+    pub duration_secs: i32,
+}
+
+#[derive(Debug)]
+pub struct CreatePostOutput {
+    pub id: Uuid,
+}
+
+#[derive(Debug)]
+pub struct NewPost {
+    pub id: Uuid,
+    pub creator_id: Uuid,
+    pub description: String,
+    pub video_key: String,
+    pub paid: bool,
+    pub thumbnail_url: String,
+    pub duration_secs: i32,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CreatePostError {
+    #[error("user is not allowed to create posts")]
+    UserNotAllowedToCreatePosts,
+    #[error("video upload not found")]
+    UploadNotFound,
+    #[error("the video has not been uploaded yet")]
+    VideoNotUploaded,
+    #[error("the thumbnail has not been uploaded yet")]
+    ThumbnailNotUploaded,
+    #[error("the thumbnail must be a jpeg image")]
+    InvalidThumbnail,
+}
+
+/// Publishes a video uploaded by the creator. Each upload can only be used once.
+///
+/// The thumbnail is checked before it is shown in the preview of the post,
+/// since the content type sent to object storage by the client cannot be trusted.
+#[tracing::instrument(name = "commands::post::create_post", skip_all, fields(
+    ctx = ?ctx,
+    input = ?input
+))]
+pub async fn create_post(
+    deps: &Deps,
+    ctx: &Context,
+    input: CreatePostInput,
+) -> Result<CreatePostOutput> {
+    if input.role != Role::Creator {
+        info!("only creators can create posts");
+        return Err(CreatePostError::UserNotAllowedToCreatePosts.into());
+    }
+
+    let mut tx = deps.db.write().await?.transaction().await?;
+
+    let upload = deps
+        .repos
+        .video_uploads
+        .get_for_update(&mut tx, input.user_id, input.video_id)
+        .await?;
+
+    let upload = match upload {
+        Some(upload) if upload.post_id.is_none() && upload.expires_at > Utc::now() => upload,
+        _ => return Err(CreatePostError::UploadNotFound.into()),
+    };
+
+    let bucket = &deps.config.s3.videos_bucket;
+
+    let video_key = upload.id.to_string();
+
+    if !deps.object_storage.exists(bucket, &video_key).await? {
+        return Err(CreatePostError::VideoNotUploaded.into());
+    }
+
+    let thumbnail_key = thumbnail_key(input.user_id, upload.id);
+
+    let thumbnail = deps
+        .object_storage
+        .get(bucket, &thumbnail_key)
+        .await?
+        .ok_or(CreatePostError::ThumbnailNotUploaded)?;
+
+    if thumbnail.len() as u64 > THUMBNAIL_MAX_BYTES || !ImageContentType::Jpeg.matches(&thumbnail) {
+        info!(
+            size = thumbnail.len(),
+            "uploaded thumbnail is not a valid jpeg"
+        );
+        return Err(CreatePostError::InvalidThumbnail.into());
+    }
+
+    let post_id = Uuid::new_v4();
+
+    deps.repos
+        .posts
+        .create(
+            &mut tx,
+            NewPost {
+                id: post_id,
+                creator_id: input.user_id,
+                description: input.description,
+                video_key,
+                paid: input.paid,
+                thumbnail_url: deps.object_storage.object_url(bucket, &thumbnail_key),
+                duration_secs: input.duration_secs,
+            },
+        )
+        .await?;
+
+    deps.repos
+        .video_uploads
+        .set_post_id(&mut tx, upload.id, post_id)
+        .await?;
+
+    tx.commit().await?;
+
+    info!(%post_id, "post created");
+
+    Ok(CreatePostOutput { id: post_id })
+}
@@ -0,0 +1,98 @@
+use crate::{
+    domain::contracts::{context::Context, deps::Deps},
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+use tracing::info;
+
+use super::RelationshipError;
+
+#[derive(Debug)]
+pub struct BlockInput {
+    pub blocker_id: Uuid,
+    pub blocked_id: Uuid,
+}
+
+#[derive(Debug)]
+pub struct UnblockInput {
+    pub blocker_id: Uuid,
+    pub blocked_id: Uuid,
+}
+
+/// Blocking a user that is already blocked does nothing.
+///
+/// The users stop following each other, their subscriptions to each other end,
+/// and they cannot see or interact with each other until the block is lifted.
+#[tracing::instrument(name = "commands::relationship::block", skip_all, fields(
+    ctx = ?ctx,
+    input = ?input
+))]
+pub async fn block(deps: &Deps, ctx: &Context, input: BlockInput) -> Result<()> {
+    if input.blocker_id == input.blocked_id {
+        info!("user tried to block themselves");
+        return Err(RelationshipError::CannotTargetYourself.into());
+    }
+
+    let mut tx = deps.db.write().await?.transaction().await?;
+
+    if deps
+        .repos
+        .users
+        .get_by_id(&mut tx, input.blocked_id)
+        .await?
+        .is_none()
+    {
+        info!("blocked user does not exist");
+        return Err(RelationshipError::NotFound.into());
+    }
+
+    let created = deps
+        .repos
+        .user_blocks
+        .create(&mut tx, input.blocker_id, input.blocked_id)
+        .await?;
+
+    deps.repos
+        .user_followers
+        .delete(&mut tx, input.blocker_id, input.blocked_id)
+        .await?;
+
+    deps.repos
+        .user_followers
+        .delete(&mut tx, input.blocked_id, input.blocker_id)
+        .await?;
+
+    let subscriptions_ended = deps
+        .repos
+        .subscriptions
+        .end_between(&mut tx, input.blocker_id, input.blocked_id)
+        .await?;
+
+    tx.commit().await?;
+
+    info!(created, subscriptions_ended, "user blocked");
+
+    Ok(())
+}
+
+/// Unblocking a user that is not blocked does nothing.
+/// Follows and subscriptions ended by the block are not restored.
+#[tracing::instrument(name = "commands::relationship::unblock", skip_all, fields(
+    ctx = ?ctx,
+    input = ?input
+))]
+pub async fn unblock(deps: &Deps, ctx: &Context, input: UnblockInput) -> Result<()> {
+    let deleted = deps
+        .repos
+        .user_blocks
+        .delete(
+            &mut deps.db.write().await?,
+            input.blocker_id,
+            input.blocked_id,
+        )
+        .await?;
+
+    info!(deleted, "user unblocked");
+
+    Ok(())
+}
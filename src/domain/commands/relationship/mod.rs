@@ -0,0 +1,13 @@
+mod block;
+mod mute;
+
+pub use block::*;
+pub use mute::*;
+
+#[derive(Debug, thiserror::Error)]
+pub enum RelationshipError {
+    #[error("user not found")]
+    NotFound,
+    #[error("users cannot block or mute themselves")]
+    CannotTargetYourself,
+}
@@ -0,0 +1,74 @@
+use crate::{
+    domain::contracts::{context::Context, deps::Deps},
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+use tracing::info;
+
+use super::RelationshipError;
+
+#[derive(Debug)]
+pub struct MuteInput {
+    pub muter_id: Uuid,
+    pub muted_id: Uuid,
+}
+
+#[derive(Debug)]
+pub struct UnmuteInput {
+    pub muter_id: Uuid,
+    pub muted_id: Uuid,
+}
+
+/// Muting a user that is already muted does nothing.
+/// The posts of the muted user are hidden from the timeline of the muter,
+/// the muted user is not told about it.
+#[tracing::instrument(name = "commands::relationship::mute", skip_all, fields(
+    ctx = ?ctx,
+    input = ?input
+))]
+pub async fn mute(deps: &Deps, ctx: &Context, input: MuteInput) -> Result<()> {
+    if input.muter_id == input.muted_id {
+        info!("user tried to mute themselves");
+        return Err(RelationshipError::CannotTargetYourself.into());
+    }
+
+    let mut executor = deps.db.write().await?;
+
+    if deps
+        .repos
+        .users
+        .get_by_id(&mut executor, input.muted_id)
+        .await?
+        .is_none()
+    {
+        info!("muted user does not exist");
+        return Err(RelationshipError::NotFound.into());
+    }
+
+    let created = deps
+        .repos
+        .user_mutes
+        .create(&mut executor, input.muter_id, input.muted_id)
+        .await?;
+
+    info!(created, "user muted");
+
+    Ok(())
+}
+
+/// Unmuting a user that is not muted does nothing.
+#[tracing::instrument(name = "commands::relationship::unmute", skip_all, fields(
+    ctx = ?ctx,
+    input = ?input
+))]
+pub async fn unmute(deps: &Deps, ctx: &Context, input: UnmuteInput) -> Result<()> {
+    let deleted = deps
+        .repos
+        .user_mutes
+        .delete(&mut deps.db.write().await?, input.muter_id, input.muted_id)
+        .await?;
+
+    info!(deleted, "user unmuted");
+
+    Ok(())
+}
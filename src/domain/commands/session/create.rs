@@ -0,0 +1,238 @@
+use crate::{
+    domain::{
+        constants::{
+            ACCESS_TOKEN_EXPIRES_IN_SECS, MFA_PENDING_TOKEN_EXPIRES_IN_SECS,
+            REFRESH_TOKEN_EXPIRES_IN_SECS,
+        },
+        contracts::{context::Context, deps::Deps, repository::Executor},
+        value_objects::{opaque_token::OpaqueToken, password::Password},
+    },
+    infra::{jwt, uuid::Uuid},
+};
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use std::net::IpAddr;
+use tracing::info;
+
+use super::lockout::{self, LoginAttemptKey};
+
+pub struct CreateSessionInput {
+    pub email: String,
+    pub password: String,
+    /// Address of the client, failed attempts are also counted per ip when it is known.
+    pub ip: Option<IpAddr>,
+}
+
+/// The data needed to check if a user is who they say they are.
+pub struct UserCredentials {
+    pub id: Uuid,
+    pub password: Password,
+    /// Users with two-factor authentication enabled must send a second factor
+    /// before getting a session.
+    pub mfa_enabled: bool,
+}
+
+/// Users that have two-factor authentication enabled get a challenge
+/// instead of a session after sending their password.
+#[derive(Debug)]
+pub enum CreateSessionResult {
+    Created(CreateSessionOutput),
+    MfaRequired(MfaChallenge),
+}
+
+#[derive(Debug)]
+pub struct MfaChallenge {
+    /// Exchanged for a session along with the second factor.
+    pub mfa_token: String,
+    /// Number of seconds until the mfa token expires.
+    pub expires_in: i64,
+}
+
+#[derive(Debug)]
+pub struct CreateSessionOutput {
+    pub access_token: String,
+    /// Number of seconds until the access token expires.
+    pub expires_in: i64,
+    /// Used to get a new access token once the current one expires.
+    pub refresh_token: OpaqueToken,
+}
+
+#[derive(Debug)]
+pub struct NewRefreshToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub family_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    /// True when the session was created using a second factor.
+    pub mfa_authenticated: bool,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CreateSessionError {
+    #[error("email or password is incorrect")]
+    InvalidCredentials,
+    #[error("user is banned")]
+    UserBanned,
+}
+
+/// Accounts and ip addresses with too many failed attempts are locked
+/// for a while, even if the password is correct.
+#[tracing::instrument(name = "commands::session::create", skip_all, fields(ctx = ?ctx))]
+pub async fn create(
+    deps: &Deps,
+    ctx: &Context,
+    input: CreateSessionInput,
+) -> Result<CreateSessionResult> {
+    let mut executor = deps.db.write().await?;
+
+    let mut attempt_keys = vec![LoginAttemptKey::account(&input.email)];
+
+    if let Some(ip) = input.ip {
+        attempt_keys.push(LoginAttemptKey::ip(ip));
+    }
+
+    lockout::ensure_not_locked(deps, &mut executor, &attempt_keys).await?;
+
+    let credentials = deps
+        .repos
+        .users
+        .get_credentials_by_email(&mut executor, &input.email)
+        .await?;
+
+    let credentials = match credentials {
+        Some(credentials) if credentials.password.verify(&input.password) => credentials,
+        credentials => {
+            info!("invalid credentials");
+
+            lockout::record_failure(
+                deps,
+                &mut executor,
+                &attempt_keys,
+                credentials.map(|credentials| credentials.id),
+            )
+            .await?;
+
+            return Err(CreateSessionError::InvalidCredentials.into());
+        }
+    };
+
+    if credentials.mfa_enabled {
+        info!(user_id = %credentials.id, "second factor required");
+
+        return Ok(CreateSessionResult::MfaRequired(
+            mfa_challenge(deps, &mut executor, credentials.id).await?,
+        ));
+    }
+
+    // Users with mfa enabled have their failed attempts cleared
+    // only after sending the second factor.
+    lockout::clear_account(deps, &mut executor, &input.email).await?;
+
+    let output = issue_tokens(deps, &mut executor, credentials.id, Uuid::new_v4(), false).await?;
+
+    Ok(CreateSessionResult::Created(output))
+}
+
+/// Signs the token exchanged for a session along with the second factor.
+pub(super) async fn mfa_challenge<'c>(
+    deps: &Deps,
+    executor: &mut Executor<'c>,
+    user_id: Uuid,
+) -> Result<MfaChallenge> {
+    ensure_not_banned(deps, executor, user_id).await?;
+
+    let now = Utc::now().timestamp();
+
+    let mfa_token = jwt::sign(
+        &deps.config.jwt,
+        &jwt::MfaPendingClaims {
+            sub: user_id,
+            jti: Uuid::new_v4(),
+            iat: now,
+            exp: now + MFA_PENDING_TOKEN_EXPIRES_IN_SECS,
+            mfa_pending: true,
+        },
+    )?;
+
+    Ok(MfaChallenge {
+        mfa_token,
+        expires_in: MFA_PENDING_TOKEN_EXPIRES_IN_SECS,
+    })
+}
+
+/// Stores a new refresh token in the session identified by `family_id`
+/// and signs an access token bound to the same session.
+#[tracing::instrument(name = "commands::session::issue_tokens", skip_all, fields(
+    user_id = %user_id,
+    family_id = %family_id,
+    mfa_authenticated = %mfa_authenticated
+))]
+pub(super) async fn issue_tokens<'c>(
+    deps: &Deps,
+    executor: &mut Executor<'c>,
+    user_id: Uuid,
+    family_id: Uuid,
+    mfa_authenticated: bool,
+) -> Result<CreateSessionOutput> {
+    ensure_not_banned(deps, executor, user_id).await?;
+
+    let refresh_token = OpaqueToken::generate();
+
+    deps.repos
+        .refresh_tokens
+        .create(
+            executor,
+            NewRefreshToken {
+                id: Uuid::new_v4(),
+                user_id,
+                family_id,
+                token_hash: refresh_token.hash(),
+                expires_at: Utc::now() + Duration::seconds(REFRESH_TOKEN_EXPIRES_IN_SECS),
+                mfa_authenticated,
+            },
+        )
+        .await?;
+
+    let now = Utc::now().timestamp();
+
+    let access_token = jwt::sign(
+        &deps.config.jwt,
+        &jwt::Claims {
+            sub: user_id,
+            sid: family_id,
+            iat: now,
+            exp: now + ACCESS_TOKEN_EXPIRES_IN_SECS,
+            mfa: mfa_authenticated,
+        },
+    )?;
+
+    Ok(CreateSessionOutput {
+        access_token,
+        expires_in: ACCESS_TOKEN_EXPIRES_IN_SECS,
+        refresh_token,
+    })
+}
+
+/// Banned users cannot get new sessions or refresh the ones they have
+/// until an admin lifts the ban.
+async fn ensure_not_banned<'c>(
+    deps: &Deps,
+    executor: &mut Executor<'c>,
+    user_id: Uuid,
+) -> Result<()> {
+    let banned = deps
+        .repos
+        .users
+        .get_authenticated_user(executor, user_id)
+        .await?
+        .map(|user| user.banned)
+        .unwrap_or_default();
+
+    if banned {
+        info!(%user_id, "user is banned");
+        return Err(CreateSessionError::UserBanned.into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use crate::{
+    domain::contracts::{context::Context, deps::Deps},
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+
+#[derive(Debug)]
+pub struct DeleteSessionInput {
+    pub session_id: Uuid,
+}
+
+#[derive(Debug)]
+pub struct DeleteAllSessionsInput {
+    pub user_id: Uuid,
+}
+
+/// Revokes the session, access tokens that belong to it stop being accepted.
+#[tracing::instrument(name = "commands::session::delete", skip_all, fields(
+    ctx = ?ctx,
+    input = ?input
+))]
+pub async fn delete(deps: &Deps, ctx: &Context, input: DeleteSessionInput) -> Result<()> {
+    deps.repos
+        .refresh_tokens
+        .revoke_family(&mut deps.db.write().await?, input.session_id)
+        .await?;
+
+    Ok(())
+}
+
+/// Revokes every session of the user.
+#[tracing::instrument(name = "commands::session::delete_all", skip_all, fields(
+    ctx = ?ctx,
+    input = ?input
+))]
+pub async fn delete_all(deps: &Deps, ctx: &Context, input: DeleteAllSessionsInput) -> Result<()> {
+    deps.repos
+        .refresh_tokens
+        .revoke_all_for_user(&mut deps.db.write().await?, input.user_id)
+        .await?;
+
+    Ok(())
+}
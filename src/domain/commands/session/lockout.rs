@@ -0,0 +1,159 @@
+use std::net::IpAddr;
+
+use crate::domain::{
+    constants::{
+        LOGIN_FAILED_ATTEMPTS_WINDOW_SECS, LOGIN_LOCKOUT_BASE_SECS, LOGIN_LOCKOUT_MAX_SECS,
+        LOGIN_MAX_FAILED_ATTEMPTS_PER_ACCOUNT, LOGIN_MAX_FAILED_ATTEMPTS_PER_IP,
+    },
+    contracts::{deps::Deps, repository::Executor},
+};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use tracing::warn;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginAttemptScope {
+    Account,
+    Ip,
+}
+
+impl LoginAttemptScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LoginAttemptScope::Account => "account",
+            LoginAttemptScope::Ip => "ip",
+        }
+    }
+
+    fn max_failed_attempts(&self) -> i32 {
+        match self {
+            LoginAttemptScope::Account => LOGIN_MAX_FAILED_ATTEMPTS_PER_ACCOUNT,
+            LoginAttemptScope::Ip => LOGIN_MAX_FAILED_ATTEMPTS_PER_IP,
+        }
+    }
+}
+
+/// Identifies what failed login attempts are counted against.
+#[derive(Debug, Clone)]
+pub struct LoginAttemptKey {
+    pub scope: LoginAttemptScope,
+    /// Email of the account or the ip address.
+    pub key: String,
+}
+
+impl LoginAttemptKey {
+    /// Emails that do not belong to any user are tracked as well, so the
+    /// responses do not tell whether an account exists.
+    pub fn account(email: &str) -> Self {
+        Self {
+            scope: LoginAttemptScope::Account,
+            key: email.trim().to_lowercase(),
+        }
+    }
+
+    pub fn ip(ip: IpAddr) -> Self {
+        Self {
+            scope: LoginAttemptScope::Ip,
+            key: ip.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("too many failed login attempts, try again in {retry_after_secs} seconds")]
+pub struct LoginLockedError {
+    pub retry_after_secs: i64,
+}
+
+/// Returns an error when any of the keys is locked.
+#[tracing::instrument(name = "commands::session::lockout::ensure_not_locked", skip_all)]
+pub(super) async fn ensure_not_locked<'c>(
+    deps: &Deps,
+    executor: &mut Executor<'c>,
+    keys: &[LoginAttemptKey],
+) -> Result<()> {
+    let locked_until = deps
+        .repos
+        .login_attempts
+        .get_locked_until(executor, keys)
+        .await?;
+
+    match locked_until {
+        Some(locked_until) if locked_until > Utc::now() => {
+            let retry_after_secs = (locked_until - Utc::now()).num_seconds().max(1);
+            Err(LoginLockedError { retry_after_secs }.into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Counts a failed attempt against every key and locks the keys
+/// that went over the limit.
+#[tracing::instrument(name = "commands::session::lockout::record_failure", skip_all, fields(
+    user_id = ?user_id
+))]
+pub(super) async fn record_failure<'c>(
+    deps: &Deps,
+    executor: &mut Executor<'c>,
+    keys: &[LoginAttemptKey],
+    user_id: Option<Uuid>,
+) -> Result<()> {
+    for key in keys {
+        let failed_count = deps
+            .repos
+            .login_attempts
+            .record_failure(executor, key, LOGIN_FAILED_ATTEMPTS_WINDOW_SECS)
+            .await?;
+
+        if let Some(duration) = lockout_duration(failed_count, key.scope.max_failed_attempts()) {
+            let locked_until = Utc::now() + duration;
+
+            deps.repos
+                .login_attempts
+                .lock(executor, key, locked_until)
+                .await?;
+
+            warn!(
+                user_id = ?user_id,
+                scope = key.scope.as_str(),
+                failed_count,
+                %locked_until,
+                "login locked after too many failed attempts"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Forgets the failed attempts of the account after a successful login.
+/// Ip addresses are not cleared, otherwise an attacker could reset
+/// the counter by logging into their own account.
+#[tracing::instrument(name = "commands::session::lockout::clear_account", skip_all)]
+pub(super) async fn clear_account<'c>(
+    deps: &Deps,
+    executor: &mut Executor<'c>,
+    email: &str,
+) -> Result<()> {
+    deps.repos
+        .login_attempts
+        .clear(executor, &LoginAttemptKey::account(email))
+        .await
+}
+
+/// The first lockout happens when the limit is reached and
+/// every failed attempt after that doubles its duration.
+fn lockout_duration(failed_count: i32, max_failed_attempts: i32) -> Option<Duration> {
+    if failed_count < max_failed_attempts {
+        return None;
+    }
+
+    let exponent = (failed_count - max_failed_attempts).min(16) as u32;
+
+    let secs = LOGIN_LOCKOUT_BASE_SECS
+        .saturating_mul(2_i64.pow(exponent))
+        .min(LOGIN_LOCKOUT_MAX_SECS);
+
+    Some(Duration::seconds(secs))
+}
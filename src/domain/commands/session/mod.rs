@@ -0,0 +1,13 @@
+mod create;
+mod delete;
+mod lockout;
+mod oidc;
+mod refresh;
+mod verify_mfa;
+
+pub use create::*;
+pub use delete::*;
+pub use lockout::*;
+pub use oidc::*;
+pub use refresh::*;
+pub use verify_mfa::*;
@@ -0,0 +1,372 @@
+use crate::{
+    domain::{
+        commands::user::CreateUserInput,
+        constants::OIDC_AUTHORIZATION_REQUEST_EXPIRES_IN_SECS,
+        contracts::{
+            context::Context,
+            deps::Deps,
+            identity_provider::{AuthorizationRequest, IdTokenClaims, IdentityProvider},
+            repository::Executor,
+        },
+        value_objects::{
+            email::Email, opaque_token::OpaqueToken, password::Password, username::Username,
+        },
+    },
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tracing::info;
+
+use super::create::{issue_tokens, mfa_challenge, CreateSessionResult};
+
+#[derive(Debug)]
+pub struct StartOidcLoginInput {
+    /// Set when a logged in user starts the login to link the identity to their account.
+    pub link_user_id: Option<Uuid>,
+}
+
+#[derive(Debug)]
+pub struct StartOidcLoginOutput {
+    /// Where the user must be sent to log in at the issuer.
+    pub authorization_url: String,
+    /// Kept by the client and sent back with the state, so a state
+    /// cannot be used by a client other than the one that started the login.
+    pub verifier: OpaqueToken,
+    /// Number of seconds the user has to come back with the authorization code.
+    pub expires_in: i64,
+}
+
+#[derive(Debug)]
+pub struct NewOidcAuthorizationRequest {
+    pub id: Uuid,
+    pub state_hash: String,
+    pub verifier_hash: String,
+    pub nonce: String,
+    pub code_verifier: String,
+    pub link_user_id: Option<Uuid>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct OidcAuthorizationRequest {
+    pub id: Uuid,
+    /// None for logins started before verifiers were required.
+    pub verifier_hash: Option<String>,
+    pub nonce: String,
+    pub code_verifier: String,
+    pub link_user_id: Option<Uuid>,
+    pub expires_at: DateTime<Utc>,
+    pub used: bool,
+}
+
+pub struct CompleteOidcLoginInput {
+    /// Sent back by the issuer along with the code.
+    pub state: OpaqueToken,
+    /// Returned to the client when the login was started.
+    pub verifier: OpaqueToken,
+    pub code: String,
+}
+
+#[derive(Debug)]
+pub struct NewOidcIdentity {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub issuer: String,
+    pub subject: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum OidcLoginError {
+    #[error("openid connect login is not configured")]
+    NotConfigured,
+    #[error("login state is invalid or has expired")]
+    InvalidState,
+    #[error("id token nonce does not match the login")]
+    NonceMismatch,
+    #[error("the issuer did not send a verified email")]
+    EmailNotVerified,
+    #[error("an account with this email already exists, log in to it to link the identity")]
+    EmailAlreadyInUse,
+    #[error("the identity is linked to another account")]
+    IdentityLinkedToAnotherUser,
+}
+
+/// Stores the state, nonce and pkce code verifier of a new login
+/// and returns the url of the issuer the user must be sent to.
+#[tracing::instrument(name = "commands::session::start_oidc_login", skip_all, fields(
+    ctx = ?ctx,
+    input = ?input
+))]
+pub async fn start_oidc_login(
+    deps: &Deps,
+    ctx: &Context,
+    input: StartOidcLoginInput,
+) -> Result<StartOidcLoginOutput> {
+    let identity_provider = identity_provider(deps)?;
+
+    let state = OpaqueToken::generate();
+    let verifier = OpaqueToken::generate();
+    let nonce = OpaqueToken::generate();
+    let code_verifier = OpaqueToken::generate();
+
+    let authorization_url = identity_provider
+        .authorization_url(&AuthorizationRequest {
+            state: state.expose().to_owned(),
+            nonce: nonce.expose().to_owned(),
+            code_challenge: code_challenge(code_verifier.expose()),
+        })
+        .await?;
+
+    deps.repos
+        .oidc
+        .create_authorization_request(
+            &mut deps.db.write().await?,
+            NewOidcAuthorizationRequest {
+                id: Uuid::new_v4(),
+                state_hash: state.hash(),
+                verifier_hash: verifier.hash(),
+                nonce: nonce.expose().to_owned(),
+                code_verifier: code_verifier.expose().to_owned(),
+                link_user_id: input.link_user_id,
+                expires_at: Utc::now()
+                    + Duration::seconds(OIDC_AUTHORIZATION_REQUEST_EXPIRES_IN_SECS),
+            },
+        )
+        .await?;
+
+    Ok(StartOidcLoginOutput {
+        authorization_url,
+        verifier,
+        expires_in: OIDC_AUTHORIZATION_REQUEST_EXPIRES_IN_SECS,
+    })
+}
+
+/// Exchanges the authorization code for an id token and logs in the user
+/// linked to the identity in it.
+///
+/// Identities seen for the first time are only linked to an existing user when
+/// that user started the login, otherwise a new user is created if the issuer
+/// verified the email and no user has it.
+/// Users with two-factor authentication enabled still have to send the second factor.
+#[tracing::instrument(name = "commands::session::complete_oidc_login", skip_all, fields(ctx = ?ctx))]
+pub async fn complete_oidc_login(
+    deps: &Deps,
+    ctx: &Context,
+    input: CompleteOidcLoginInput,
+) -> Result<CreateSessionResult> {
+    let identity_provider = identity_provider(deps)?;
+
+    // The state is used up before talking to the issuer
+    // so it cannot be tried again with another code.
+    let mut tx = deps.db.write().await?.transaction().await?;
+
+    let request = deps
+        .repos
+        .oidc
+        .get_authorization_request_for_update(&mut tx, &input.state.hash())
+        .await?;
+
+    let request = match request {
+        Some(request)
+            if !request.used
+                && request.expires_at > Utc::now()
+                && request.verifier_hash.as_deref() == Some(input.verifier.hash().as_str()) =>
+        {
+            request
+        }
+        _ => return Err(OidcLoginError::InvalidState.into()),
+    };
+
+    deps.repos
+        .oidc
+        .mark_authorization_request_as_used(&mut tx, request.id)
+        .await?;
+
+    tx.commit().await?;
+
+    let claims = identity_provider
+        .exchange_code(&input.code, &request.code_verifier)
+        .await?;
+
+    if claims.nonce.as_deref() != Some(request.nonce.as_str()) {
+        return Err(OidcLoginError::NonceMismatch.into());
+    }
+
+    let mut tx = deps.db.write().await?.transaction().await?;
+
+    let linked_user_id = deps
+        .repos
+        .oidc
+        .get_user_id_by_identity(&mut tx, &claims.issuer, &claims.subject)
+        .await?;
+
+    let user_id = match (linked_user_id, request.link_user_id) {
+        (Some(user_id), None) => user_id,
+        (Some(user_id), Some(link_user_id)) => {
+            if user_id != link_user_id {
+                info!(user_id = %user_id, "identity is linked to another user");
+                return Err(OidcLoginError::IdentityLinkedToAnotherUser.into());
+            }
+            user_id
+        }
+        (None, Some(link_user_id)) => {
+            create_identity(deps, &mut tx, link_user_id, &claims).await?;
+
+            info!(user_id = %link_user_id, "identity linked by the user");
+
+            link_user_id
+        }
+        (None, None) => create_user_from_identity(deps, &mut tx, &claims).await?,
+    };
+
+    let mfa_enabled = deps
+        .repos
+        .users
+        .get_mfa_for_update(&mut tx, user_id)
+        .await?
+        .map(|mfa| mfa.mfa_enabled)
+        .unwrap_or_default();
+
+    let result = if mfa_enabled {
+        info!(user_id = %user_id, "second factor required");
+
+        CreateSessionResult::MfaRequired(mfa_challenge(deps, &mut tx, user_id).await?)
+    } else {
+        CreateSessionResult::Created(
+            issue_tokens(deps, &mut tx, user_id, Uuid::new_v4(), false).await?,
+        )
+    };
+
+    tx.commit().await?;
+
+    Ok(result)
+}
+
+/// Creates a user with the verified email of the identity and links the identity to it.
+/// Returns the id of the user.
+///
+/// Identities are not linked to an existing user with the same email, since the
+/// issuer could let anyone use an email they do not own. The owner of the account
+/// has to log in and start the login from there to link the identity.
+#[tracing::instrument(name = "commands::session::create_user_from_identity", skip_all)]
+async fn create_user_from_identity<'c>(
+    deps: &Deps,
+    executor: &mut Executor<'c>,
+    claims: &IdTokenClaims,
+) -> Result<Uuid> {
+    let email = match &claims.email {
+        Some(email) if claims.email_verified => Email::try_from(email.as_str())?,
+        _ => return Err(OidcLoginError::EmailNotVerified.into()),
+    };
+
+    let existing_user = deps
+        .repos
+        .users
+        .get_credentials_by_email(executor, email.expose())
+        .await?;
+
+    if let Some(credentials) = existing_user {
+        info!(user_id = %credentials.id, "identity has the email of an existing user");
+        return Err(OidcLoginError::EmailAlreadyInUse.into());
+    }
+
+    let username = username_from_email(&email)?;
+
+    // The user can set a password later by resetting it.
+    let password = Password::new(
+        OpaqueToken::generate().expose(),
+        username.normalized(),
+        email.expose(),
+    )?;
+
+    let user_id = deps
+        .repos
+        .users
+        .create(
+            executor,
+            CreateUserInput {
+                username,
+                email,
+                password,
+                accepted_terms_at: Utc::now(),
+                // The user is asked to accept the terms on their first request.
+                accepted_terms_version: None,
+            },
+        )
+        .await?;
+
+    deps.repos
+        .users
+        .mark_email_as_verified(executor, user_id)
+        .await?;
+
+    create_identity(deps, executor, user_id, claims).await?;
+
+    info!(user_id = %user_id, "created user from identity");
+
+    Ok(user_id)
+}
+
+async fn create_identity<'c>(
+    deps: &Deps,
+    executor: &mut Executor<'c>,
+    user_id: Uuid,
+    claims: &IdTokenClaims,
+) -> Result<()> {
+    deps.repos
+        .oidc
+        .create_identity(
+            executor,
+            NewOidcIdentity {
+                id: Uuid::new_v4(),
+                user_id,
+                issuer: claims.issuer.clone(),
+                subject: claims.subject.clone(),
+            },
+        )
+        .await
+}
+
+fn identity_provider(deps: &Deps) -> Result<&Arc<dyn IdentityProvider>> {
+    Ok(deps
+        .identity_provider
+        .as_ref()
+        .ok_or(OidcLoginError::NotConfigured)?)
+}
+
+/// S256 pkce challenge as defined in rfc 7636.
+fn code_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+/// Uses the part of the email before the @ with a random suffix,
+/// since the username must be unique.
+fn username_from_email(email: &Email) -> Result<Username> {
+    let local_part: String = email
+        .split('@')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .take(20)
+        .collect::<String>()
+        .to_lowercase();
+
+    // Usernames must start with a letter or a number.
+    let local_part = local_part.trim_start_matches('_');
+
+    let local_part = if local_part.is_empty() {
+        "user"
+    } else {
+        local_part
+    };
+
+    let suffix: u32 = rand::thread_rng().gen_range(0..1_000_000);
+
+    Ok(Username::try_from(format!("{local_part}{suffix:06}"))?)
+}
@@ -0,0 +1,103 @@
+use crate::{
+    domain::{
+        contracts::{context::Context, deps::Deps},
+        value_objects::opaque_token::OpaqueToken,
+    },
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use tracing::{info, warn};
+
+use super::create::{issue_tokens, CreateSessionOutput};
+
+#[derive(Debug)]
+pub struct RefreshSessionInput {
+    pub refresh_token: OpaqueToken,
+}
+
+#[derive(Debug)]
+pub struct RefreshToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub family_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+    /// Set when the token is exchanged for a new one.
+    pub used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    /// Sessions created using a second factor stay that way when refreshed.
+    pub mfa_authenticated: bool,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RefreshSessionError {
+    #[error("refresh token is invalid")]
+    InvalidRefreshToken,
+    #[error("refresh token has already been used, the session has been revoked")]
+    RefreshTokenReused,
+}
+
+/// Exchanges a refresh token for a new access token and a new refresh token.
+/// A refresh token can be used only once, using it again means it has probably
+/// been stolen so every token in the session is revoked.
+#[tracing::instrument(name = "commands::session::refresh", skip_all, fields(ctx = ?ctx))]
+pub async fn refresh(
+    deps: &Deps,
+    ctx: &Context,
+    input: RefreshSessionInput,
+) -> Result<CreateSessionOutput> {
+    let mut tx = deps.db.write().await?.transaction().await?;
+
+    let token = match deps
+        .repos
+        .refresh_tokens
+        .get_by_token_hash_for_update(&mut tx, &input.refresh_token.hash())
+        .await?
+    {
+        None => {
+            info!("refresh token not found");
+            return Err(RefreshSessionError::InvalidRefreshToken.into());
+        }
+        Some(token) => token,
+    };
+
+    if token.revoked_at.is_some() || token.expires_at <= Utc::now() {
+        info!(family_id = %token.family_id, "refresh token is revoked or expired");
+        return Err(RefreshSessionError::InvalidRefreshToken.into());
+    }
+
+    if token.used_at.is_some() {
+        warn!(
+            user_id = %token.user_id,
+            family_id = %token.family_id,
+            "refresh token reused, revoking session"
+        );
+
+        deps.repos
+            .refresh_tokens
+            .revoke_family(&mut tx, token.family_id)
+            .await?;
+
+        tx.commit().await?;
+
+        return Err(RefreshSessionError::RefreshTokenReused.into());
+    }
+
+    deps.repos
+        .refresh_tokens
+        .mark_as_used(&mut tx, token.id)
+        .await?;
+
+    let output = issue_tokens(
+        deps,
+        &mut tx,
+        token.user_id,
+        token.family_id,
+        token.mfa_authenticated,
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(output)
+}
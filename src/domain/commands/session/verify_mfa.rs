@@ -0,0 +1,142 @@
+use crate::{
+    domain::{
+        contracts::{context::Context, deps::Deps},
+        value_objects::recovery_code::RecoveryCode,
+    },
+    infra::{jwt, uuid::Uuid},
+};
+use anyhow::Result;
+use chrono::{TimeZone, Utc};
+use std::net::IpAddr;
+use tracing::info;
+
+use super::create::{issue_tokens, CreateSessionOutput};
+use super::lockout::{self, LoginAttemptKey};
+
+pub struct VerifyMfaInput {
+    /// Token returned after the user sent the correct password.
+    pub mfa_token: String,
+    pub code: MfaCode,
+    /// Address of the client, failed attempts are also counted per ip when it is known.
+    pub ip: Option<IpAddr>,
+}
+
+/// The second factor sent by the user.
+pub enum MfaCode {
+    /// Code generated by the authenticator app.
+    Totp(String),
+    /// Used when the user does not have access to the authenticator app.
+    Recovery(RecoveryCode),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VerifyMfaError {
+    #[error("mfa token is invalid or has expired")]
+    InvalidMfaToken,
+    #[error("two-factor authentication code is invalid")]
+    InvalidCode,
+}
+
+/// Second login step of users that have two-factor authentication enabled.
+/// Exchanges the mfa token and a totp or recovery code for a session.
+/// Invalid codes count as failed login attempts.
+#[tracing::instrument(name = "commands::session::verify_mfa", skip_all, fields(ctx = ?ctx))]
+pub async fn verify_mfa(
+    deps: &Deps,
+    ctx: &Context,
+    input: VerifyMfaInput,
+) -> Result<CreateSessionOutput> {
+    let claims = match jwt::verify::<jwt::MfaPendingClaims>(&deps.config.jwt, &input.mfa_token) {
+        Ok(claims) if claims.mfa_pending => claims,
+        _ => {
+            info!("mfa token is invalid or expired");
+            return Err(VerifyMfaError::InvalidMfaToken.into());
+        }
+    };
+
+    let mut tx = deps.db.write().await?.transaction().await?;
+
+    let user = match deps
+        .repos
+        .users
+        .get_mfa_for_update(&mut tx, claims.sub)
+        .await?
+    {
+        Some(user) if user.mfa_enabled => user,
+        _ => {
+            info!(user_id = %claims.sub, "user does not exist or does not have mfa enabled");
+            return Err(VerifyMfaError::InvalidMfaToken.into());
+        }
+    };
+
+    let mut attempt_keys = vec![LoginAttemptKey::account(&user.email)];
+
+    if let Some(ip) = input.ip {
+        attempt_keys.push(LoginAttemptKey::ip(ip));
+    }
+
+    lockout::ensure_not_locked(deps, &mut tx, &attempt_keys).await?;
+
+    let valid = match input.code {
+        MfaCode::Totp(code) => {
+            let step = user
+                .totp_secret
+                .as_ref()
+                .and_then(|secret| secret.verify(&code, Utc::now().timestamp()));
+
+            // A code cannot be used again, even if it is still valid.
+            match step {
+                Some(step) if user.totp_last_used_step.is_none_or(|last| step > last) => {
+                    deps.repos
+                        .users
+                        .set_totp_last_used_step(&mut tx, claims.sub, step)
+                        .await?;
+
+                    true
+                }
+                _ => false,
+            }
+        }
+        MfaCode::Recovery(code) => {
+            deps.repos
+                .mfa_recovery_codes
+                .use_code(&mut tx, claims.sub, &code.hash())
+                .await?
+        }
+    };
+
+    if !valid {
+        info!(user_id = %claims.sub, "invalid mfa code");
+
+        lockout::record_failure(deps, &mut tx, &attempt_keys, Some(claims.sub)).await?;
+
+        tx.commit().await?;
+
+        return Err(VerifyMfaError::InvalidCode.into());
+    }
+
+    // Used up in the transaction that creates the session, so the token
+    // cannot be exchanged for another session with the next code.
+    let expires_at = Utc
+        .timestamp_opt(claims.exp, 0)
+        .single()
+        .unwrap_or_else(Utc::now);
+
+    if !deps
+        .repos
+        .used_mfa_tokens
+        .use_token(&mut tx, claims.jti, claims.sub, expires_at)
+        .await?
+    {
+        info!(user_id = %claims.sub, "mfa token was already used");
+        return Err(VerifyMfaError::InvalidMfaToken.into());
+    }
+
+    lockout::clear_account(deps, &mut tx, &user.email).await?;
+
+    let output = issue_tokens(deps, &mut tx, claims.sub, Uuid::new_v4(), true).await?;
+
+    tx.commit().await?;
+
+    Ok(output)
+}
@@ -0,0 +1,73 @@
+use crate::{
+    domain::contracts::{context::Context, deps::Deps, repository::Executor},
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+use tracing::info;
+
+#[derive(Debug)]
+pub struct AcceptTermsInput {
+    pub user_id: Uuid,
+    /// Must be the current version, so users cannot accept terms they have not seen.
+    pub version: String,
+}
+
+#[derive(Debug)]
+pub struct NewTermsAcceptance {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub terms_version: String,
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum AcceptTermsError {
+    #[error("only the current version of the terms can be accepted")]
+    NotCurrentVersion,
+}
+
+/// Records that the user accepted the current version of the terms.
+/// Requests made by the user are rejected until they do.
+#[tracing::instrument(name = "commands::terms::accept_terms", skip_all, fields(
+    ctx = ?ctx,
+    user_id = %input.user_id,
+    version = %input.version
+))]
+pub async fn accept_terms(deps: &Deps, ctx: &Context, input: AcceptTermsInput) -> Result<()> {
+    let mut tx = deps.db.write().await?.transaction().await?;
+
+    record_acceptance(deps, &mut tx, input.user_id, input.version).await?;
+
+    tx.commit().await?;
+
+    info!("terms accepted");
+
+    Ok(())
+}
+
+/// Adds the acceptance to the history after checking `version` is the current one.
+#[tracing::instrument(name = "commands::terms::record_acceptance", skip_all)]
+pub async fn record_acceptance<'c>(
+    deps: &Deps,
+    executor: &mut Executor<'c>,
+    user_id: Uuid,
+    version: String,
+) -> Result<()> {
+    let current = deps.repos.terms.get_current(executor).await?;
+
+    if current.map(|current| current.version) != Some(version.clone()) {
+        info!("tried to accept a version that is not the current one");
+        return Err(AcceptTermsError::NotCurrentVersion.into());
+    }
+
+    deps.repos
+        .terms
+        .create_acceptance(
+            executor,
+            NewTermsAcceptance {
+                id: Uuid::new_v4(),
+                user_id,
+                terms_version: version,
+            },
+        )
+        .await
+}
@@ -0,0 +1,3 @@
+mod accept;
+
+pub use accept::*;
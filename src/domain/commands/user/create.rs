@@ -1,21 +1,97 @@
+use crate::domain::commands;
+use crate::domain::constants::EMAIL_VERIFICATION_TOKEN_EXPIRES_IN_SECS;
+use crate::domain::contracts::mailer::Mail;
+use crate::domain::contracts::repository::Executor;
 use crate::domain::contracts::{context::Context, deps::Deps};
-use crate::domain::value_objects::{email::Email, password::Password};
+use crate::domain::value_objects::opaque_token::OpaqueToken;
+use crate::domain::value_objects::{email::Email, password::Password, username::Username};
+use crate::infra::uuid::Uuid;
 use anyhow::Result;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 
 pub struct CreateUserInput {
-    pub username: String,
+    pub username: Username,
     pub email: Email,
     pub password: Password,
     pub accepted_terms_at: DateTime<Utc>,
+    /// None when the user has not accepted any version of the terms yet,
+    /// like users created from an identity provider.
+    pub accepted_terms_version: Option<String>,
 }
 
+#[derive(Debug)]
+pub struct NewEmailVerificationToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Returned when the username or the email belongs to another user.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum CreateUserError {
+    #[error("the username is already taken")]
+    UsernameTaken,
+    #[error("the email is already registered")]
+    EmailTaken,
+}
+
+/// Creates the user and sends an email containing the token
+/// used to verify the email address.
 #[tracing::instrument(name = "commands::user::create", skip_all, fields(ctx = ?ctx))]
 pub async fn create(deps: &Deps, ctx: &Context, input: CreateUserInput) -> Result<()> {
+    let email = input.email.expose().to_owned();
+    let accepted_terms_version = input.accepted_terms_version.clone();
+
+    let mut tx = deps.db.write().await?.transaction().await?;
+
+    let user_id = deps.repos.users.create(&mut tx, input).await?;
+
+    if let Some(version) = accepted_terms_version {
+        commands::terms::record_acceptance(deps, &mut tx, user_id, version).await?;
+    }
+
+    // The email is sent before committing so the user can try
+    // to register again if it cannot be sent.
+    send_verification_token(deps, &mut tx, user_id, email).await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Stores a new verification token for the user and emails it to `email`.
+pub(super) async fn send_verification_token<'c>(
+    deps: &Deps,
+    executor: &mut Executor<'c>,
+    user_id: Uuid,
+    email: String,
+) -> Result<()> {
+    let token = OpaqueToken::generate();
+
     deps.repos
-        .users
-        .create(&mut deps.db.write().await?, input)
+        .email_verification_tokens
+        .create(
+            executor,
+            NewEmailVerificationToken {
+                id: Uuid::new_v4(),
+                user_id,
+                token_hash: token.hash(),
+                expires_at: Utc::now()
+                    + Duration::seconds(EMAIL_VERIFICATION_TOKEN_EXPIRES_IN_SECS),
+            },
+        )
         .await?;
 
-    Ok(())
+    deps.mailer
+        .send(Mail {
+            to: email,
+            subject: "Verify your email address".to_owned(),
+            body: format!(
+                "Use the code below to verify your email address. It expires in {} hours.\n\n{}\n",
+                EMAIL_VERIFICATION_TOKEN_EXPIRES_IN_SECS / 60 / 60,
+                token.expose()
+            ),
+        })
+        .await
 }
@@ -0,0 +1,104 @@
+use crate::domain::commands::object_purge::NewObjectPurge;
+use crate::domain::commands::video::thumbnails_key_prefix;
+use crate::domain::constants::ACCOUNT_DELETION_PURGE_DELAY_SECS;
+use crate::domain::contracts::{context::Context, deps::Deps};
+use crate::domain::value_objects::creator_tag::CreatorTag;
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use tracing::info;
+
+use super::export::personal_data_exports_key_prefix;
+use super::profile_image::profile_images_key_prefix;
+
+#[derive(Debug)]
+pub struct DeleteAccountInput {
+    pub user_id: Uuid,
+}
+
+/// Soft deletes the account, revokes every session and api key of the user
+/// and removes their tags.
+///
+/// The videos, thumbnails, profile images and data exports of the user are purged
+/// from object storage after `ACCOUNT_DELETION_PURGE_DELAY_SECS`.
+#[tracing::instrument(name = "commands::user::delete_account", skip_all, fields(
+    ctx = ?ctx,
+    input = ?input
+))]
+pub async fn delete_account(deps: &Deps, ctx: &Context, input: DeleteAccountInput) -> Result<()> {
+    let mut tx = deps.db.write().await?.transaction().await?;
+
+    deps.repos.users.soft_delete(&mut tx, input.user_id).await?;
+
+    deps.repos
+        .refresh_tokens
+        .revoke_all_for_user(&mut tx, input.user_id)
+        .await?;
+
+    deps.repos
+        .api_keys
+        .revoke_all_for_user(&mut tx, input.user_id)
+        .await?;
+
+    let removed_tags = deps
+        .repos
+        .creator_tags
+        .remove(&mut tx, input.user_id, &CreatorTag::ALL)
+        .await?;
+
+    deps.repos
+        .creator_tags
+        .update_counts(&mut tx, &removed_tags, -1)
+        .await?;
+
+    let posts = deps
+        .repos
+        .posts
+        .list_by_creator(&mut tx, input.user_id)
+        .await?;
+
+    let bucket = &deps.config.s3.videos_bucket;
+
+    let mut key_prefixes = vec![
+        profile_images_key_prefix(input.user_id),
+        thumbnails_key_prefix(input.user_id),
+        personal_data_exports_key_prefix(input.user_id),
+    ];
+
+    key_prefixes.extend(posts.iter().filter_map(|post| video_key(&post.video_key)));
+
+    let purge_after = Utc::now() + Duration::seconds(ACCOUNT_DELETION_PURGE_DELAY_SECS);
+
+    for key_prefix in key_prefixes {
+        deps.repos
+            .object_purges
+            .schedule(
+                &mut tx,
+                NewObjectPurge {
+                    id: Uuid::new_v4(),
+                    user_id: input.user_id,
+                    bucket: bucket.clone(),
+                    key_prefix,
+                    purge_after,
+                },
+            )
+            .await?;
+    }
+
+    tx.commit().await?;
+
+    info!(%purge_after, "account deleted");
+
+    Ok(())
+}
+
+/// Returns the key of the video in the videos bucket,
+/// None when it is not a video id.
+///
+/// Videos are stored under their id. Anything else is ignored since the key
+/// is used as a prefix when purging and could match the objects of other users.
+pub(super) fn video_key(key: &str) -> Option<String> {
+    Uuid::parse_str(key)
+        .ok()
+        .map(|video_id| video_id.to_string())
+}
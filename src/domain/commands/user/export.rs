@@ -0,0 +1,422 @@
+use crate::domain::commands::object_purge::NewObjectPurge;
+use crate::domain::commands::video::thumbnails_key_prefix;
+use crate::domain::constants::{
+    PERSONAL_DATA_EXPORT_BATCH_SIZE, PERSONAL_DATA_EXPORT_EXPIRES_IN_SECS,
+    PERSONAL_DATA_EXPORT_MIN_INTERVAL_SECS,
+};
+use crate::domain::contracts::{context::Context, deps::Deps};
+use crate::infra::uuid::Uuid;
+use anyhow::{Context as _, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use tracing::{error, info};
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+use super::delete::video_key;
+use super::profile_image::profile_images_key_prefix;
+
+#[derive(Debug)]
+pub struct RequestPersonalDataExportInput {
+    pub user_id: Uuid,
+}
+
+#[derive(Debug)]
+pub struct RequestPersonalDataExportOutput {
+    /// Used to check whether the export is ready.
+    pub id: Uuid,
+}
+
+#[derive(Debug)]
+pub struct NewPersonalDataExport {
+    pub id: Uuid,
+    pub user_id: Uuid,
+}
+
+#[derive(Debug)]
+pub struct PendingPersonalDataExport {
+    pub id: Uuid,
+    pub user_id: Uuid,
+}
+
+#[derive(Debug)]
+pub struct PersonalDataExport {
+    pub id: Uuid,
+    /// Key of the archive, None until the export is completed.
+    pub object_key: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("a personal data export was requested recently, try again in {retry_after_secs} seconds")]
+pub struct PersonalDataExportRateLimitedError {
+    pub retry_after_secs: i64,
+}
+
+/// Everything we hold about the user, written to `personal_data.json` in the archive.
+#[derive(Debug, Serialize)]
+pub struct PersonalData {
+    pub exported_at: DateTime<Utc>,
+    pub profile: PersonalDataProfile,
+    pub posts: Vec<PersonalDataPost>,
+    pub sessions: Vec<PersonalDataSession>,
+    pub api_keys: Vec<PersonalDataApiKey>,
+    pub identities: Vec<PersonalDataIdentity>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PersonalDataProfile {
+    pub id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub role: String,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub links: Vec<String>,
+    pub avatar_url: Option<String>,
+    pub banner_url: Option<String>,
+    pub email_verified_at: Option<DateTime<Utc>>,
+    pub mfa_enabled: bool,
+    pub accepted_terms_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PersonalDataPost {
+    pub id: Uuid,
+    pub description: Option<String>,
+    pub video_key: String,
+    pub likes: i32,
+    pub paid: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PersonalDataSession {
+    pub id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub last_refreshed_at: DateTime<Utc>,
+    pub revoked: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PersonalDataApiKey {
+    pub name: String,
+    pub prefix: String,
+    pub scopes: Vec<String>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PersonalDataIdentity {
+    pub issuer: String,
+    pub subject: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Schedules the export of the personal data of the user, the archive is built
+/// in the background by `run_pending_exports`.
+///
+/// Building an archive is expensive, so users can only request an export
+/// every `PERSONAL_DATA_EXPORT_MIN_INTERVAL_SECS`.
+#[tracing::instrument(name = "commands::user::request_personal_data_export", skip_all, fields(
+    ctx = ?ctx,
+    input = ?input
+))]
+pub async fn request_personal_data_export(
+    deps: &Deps,
+    ctx: &Context,
+    input: RequestPersonalDataExportInput,
+) -> Result<RequestPersonalDataExportOutput> {
+    let mut executor = deps.db.write().await?;
+
+    let latest = deps
+        .repos
+        .personal_data_exports
+        .get_latest_by_user(&mut executor, input.user_id)
+        .await?;
+
+    if let Some(latest) = latest {
+        let allowed_at =
+            latest.created_at + Duration::seconds(PERSONAL_DATA_EXPORT_MIN_INTERVAL_SECS);
+
+        if allowed_at > Utc::now() {
+            info!(latest_export_id = %latest.id, "personal data export requested too soon");
+            return Err(PersonalDataExportRateLimitedError {
+                retry_after_secs: (allowed_at - Utc::now()).num_seconds().max(1),
+            }
+            .into());
+        }
+    }
+
+    let id = Uuid::new_v4();
+
+    let created = deps
+        .repos
+        .personal_data_exports
+        .create(
+            &mut executor,
+            NewPersonalDataExport {
+                id,
+                user_id: input.user_id,
+            },
+        )
+        .await?;
+
+    // Another request created an export in the meantime.
+    if !created {
+        return Err(PersonalDataExportRateLimitedError {
+            retry_after_secs: PERSONAL_DATA_EXPORT_MIN_INTERVAL_SECS,
+        }
+        .into());
+    }
+
+    info!(export_id = %id, "personal data export requested");
+
+    Ok(RequestPersonalDataExportOutput { id })
+}
+
+/// Builds the archives of the exports that were requested, at most
+/// `PERSONAL_DATA_EXPORT_BATCH_SIZE` of them. Returns the number of exports built.
+///
+/// Exports are locked while they are built so more than one instance
+/// of the api can run them at the same time.
+#[tracing::instrument(name = "commands::user::run_pending_exports", skip_all, fields(ctx = ?ctx))]
+pub async fn run_pending_exports(deps: &Deps, ctx: &Context) -> Result<usize> {
+    let mut completed = 0;
+
+    for _ in 0..PERSONAL_DATA_EXPORT_BATCH_SIZE {
+        let mut tx = deps.db.write().await?.transaction().await?;
+
+        let export = match deps
+            .repos
+            .personal_data_exports
+            .get_pending_for_update(&mut tx)
+            .await?
+        {
+            None => break,
+            Some(export) => export,
+        };
+
+        let key = build_archive(deps, &export).await?;
+
+        let expires_at = Utc::now() + Duration::seconds(PERSONAL_DATA_EXPORT_EXPIRES_IN_SECS);
+
+        deps.repos
+            .personal_data_exports
+            .mark_as_completed(&mut tx, export.id, &key, expires_at)
+            .await?;
+
+        deps.repos
+            .object_purges
+            .schedule(
+                &mut tx,
+                NewObjectPurge {
+                    id: Uuid::new_v4(),
+                    user_id: export.user_id,
+                    bucket: deps.config.s3.videos_bucket.clone(),
+                    key_prefix: key,
+                    purge_after: expires_at,
+                },
+            )
+            .await?;
+
+        tx.commit().await?;
+
+        info!(export_id = %export.id, "personal data exported");
+
+        completed += 1;
+    }
+
+    Ok(completed)
+}
+
+/// Writes a zip archive with the personal data of the user and the media
+/// they uploaded and stores it in object storage. Returns the key of the archive.
+///
+/// Media can be large, so the archive is written to a temporary file,
+/// one object at a time, and uploaded from there.
+#[tracing::instrument(name = "commands::user::build_archive", skip_all, fields(
+    export_id = %export.id
+))]
+async fn build_archive(deps: &Deps, export: &PendingPersonalDataExport) -> Result<String> {
+    let path = std::env::temp_dir().join(format!("personal-data-export-{}.zip", export.id));
+
+    let result = write_archive(deps, export, path.clone()).await;
+
+    let result = match result {
+        Err(error) => Err(error),
+        Ok(()) => {
+            let key = format!(
+                "{}{}.zip",
+                personal_data_exports_key_prefix(export.user_id),
+                export.id
+            );
+
+            deps.object_storage
+                .put_file(
+                    &deps.config.s3.videos_bucket,
+                    &key,
+                    &path,
+                    "application/zip",
+                )
+                .await
+                .map(|_| key)
+        }
+    };
+
+    if let Err(error) = tokio::fs::remove_file(&path).await {
+        error!(?error, ?path, "unable to remove personal data export file");
+    }
+
+    result
+}
+
+async fn write_archive(
+    deps: &Deps,
+    export: &PendingPersonalDataExport,
+    path: PathBuf,
+) -> Result<()> {
+    let personal_data = collect_personal_data(deps, export.user_id).await?;
+
+    let bucket = &deps.config.s3.videos_bucket;
+
+    let mut media = vec![];
+
+    for key in deps
+        .object_storage
+        .list_keys(bucket, &profile_images_key_prefix(export.user_id))
+        .await?
+    {
+        let file_name = key.rsplit('/').next().unwrap_or_default().to_owned();
+        media.push((format!("media/profile-images/{file_name}"), key));
+    }
+
+    for key in deps
+        .object_storage
+        .list_keys(bucket, &thumbnails_key_prefix(export.user_id))
+        .await?
+    {
+        let file_name = key.rsplit('/').next().unwrap_or_default().to_owned();
+        media.push((format!("media/thumbnails/{file_name}.jpg"), key));
+    }
+
+    for post in personal_data.posts.iter() {
+        if let Some(key) = video_key(&post.video_key) {
+            media.push((format!("media/videos/{key}"), key));
+        }
+    }
+
+    let mut archive = tokio::task::spawn_blocking(move || -> Result<_> {
+        Ok(ZipWriter::new(File::create(path)?))
+    })
+    .await??;
+
+    archive = write_entry(
+        archive,
+        "personal_data.json".to_owned(),
+        serde_json::to_vec_pretty(&personal_data)?,
+        CompressionMethod::Deflated,
+    )
+    .await?;
+
+    for (path, key) in media {
+        // Objects that were removed in the meantime are left out.
+        if let Some(bytes) = deps.object_storage.get(bucket, &key).await? {
+            // Images and videos are already compressed.
+            archive = write_entry(archive, path, bytes, CompressionMethod::Stored).await?;
+        }
+    }
+
+    tokio::task::spawn_blocking(move || -> Result<()> {
+        archive.finish()?;
+        Ok(())
+    })
+    .await?
+}
+
+/// The archive is a file, so it is written to without blocking the runtime.
+async fn write_entry(
+    mut archive: ZipWriter<File>,
+    path: String,
+    bytes: Vec<u8>,
+    compression_method: CompressionMethod,
+) -> Result<ZipWriter<File>> {
+    tokio::task::spawn_blocking(move || -> Result<_> {
+        archive.start_file(
+            path,
+            FileOptions::default().compression_method(compression_method),
+        )?;
+        archive.write_all(&bytes)?;
+        Ok(archive)
+    })
+    .await?
+}
+
+/// Every personal data export of the user is stored under this prefix.
+pub(super) fn personal_data_exports_key_prefix(user_id: Uuid) -> String {
+    format!("exports/{user_id}/")
+}
+
+#[tracing::instrument(name = "commands::user::collect_personal_data", skip_all)]
+async fn collect_personal_data(deps: &Deps, user_id: Uuid) -> Result<PersonalData> {
+    let mut executor = deps.db.read().await?;
+
+    let profile = deps
+        .repos
+        .users
+        .get_personal_data(&mut executor, user_id)
+        .await?
+        .context("user not found")?;
+
+    let posts = deps
+        .repos
+        .posts
+        .list_by_creator(&mut executor, user_id)
+        .await?;
+
+    let sessions = deps
+        .repos
+        .refresh_tokens
+        .list_sessions_by_user(&mut executor, user_id)
+        .await?;
+
+    let api_keys = deps
+        .repos
+        .api_keys
+        .list_by_user(&mut executor, user_id)
+        .await?
+        .into_iter()
+        .map(|key| PersonalDataApiKey {
+            name: key.name,
+            prefix: key.prefix,
+            scopes: key
+                .scopes
+                .iter()
+                .map(|scope| scope.as_str().to_owned())
+                .collect(),
+            last_used_at: key.last_used_at,
+            created_at: key.created_at,
+        })
+        .collect();
+
+    let identities = deps
+        .repos
+        .oidc
+        .list_identities_by_user(&mut executor, user_id)
+        .await?;
+
+    Ok(PersonalData {
+        exported_at: Utc::now(),
+        profile,
+        posts,
+        sessions,
+        api_keys,
+        identities,
+    })
+}
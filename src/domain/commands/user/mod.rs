@@ -1,3 +1,15 @@
 mod create;
+mod delete;
+mod export;
+mod profile_image;
+mod resend_verification_email;
+mod update_profile;
+mod verify_email;
 
 pub use create::*;
+pub use delete::*;
+pub use export::*;
+pub use profile_image::*;
+pub use resend_verification_email::*;
+pub use update_profile::*;
+pub use verify_email::*;
@@ -0,0 +1,178 @@
+use crate::domain::constants::{
+    PROFILE_IMAGE_MAX_BYTES, PROFILE_IMAGE_MIN_BYTES, PROFILE_IMAGE_UPLOAD_EXPIRES_IN_SECS,
+};
+use crate::domain::contracts::object_storage::{GetPresignedPostUrlOutput, PostPolicy};
+use crate::domain::contracts::{context::Context, deps::Deps};
+use crate::domain::value_objects::profile_image::{ImageContentType, ProfileImageKind};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use tracing::info;
+
+#[derive(Debug)]
+pub struct StartProfileImageUploadInput {
+    pub user_id: Uuid,
+    pub kind: ProfileImageKind,
+    /// The object storage rejects uploads with another content type.
+    pub content_type: ImageContentType,
+}
+
+#[derive(Debug)]
+pub struct StartProfileImageUploadOutput {
+    /// Sent to confirm the upload once the image has been uploaded.
+    pub upload_id: Uuid,
+    pub presigned_url: GetPresignedPostUrlOutput,
+}
+
+#[derive(Debug)]
+pub struct NewProfileImageUpload {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: ProfileImageKind,
+    pub object_key: String,
+    pub content_type: ImageContentType,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct ProfileImageUpload {
+    pub id: Uuid,
+    pub kind: ProfileImageKind,
+    pub object_key: String,
+    pub content_type: ImageContentType,
+    pub expires_at: DateTime<Utc>,
+    pub confirmed: bool,
+}
+
+#[derive(Debug)]
+pub struct ConfirmProfileImageUploadInput {
+    pub user_id: Uuid,
+    pub upload_id: Uuid,
+}
+
+#[derive(Debug)]
+pub struct ConfirmProfileImageUploadOutput {
+    pub kind: ProfileImageKind,
+    /// Where the image can be downloaded from.
+    pub url: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ProfileImageUploadError {
+    #[error("upload not found")]
+    NotFound,
+    #[error("the image has not been uploaded yet")]
+    NotUploaded,
+    #[error("the uploaded file is not a valid image")]
+    InvalidImage,
+}
+
+/// Returns a presigned url that only accepts images of the given content type
+/// that are smaller than the videos accepted by `start_video_upload`.
+#[tracing::instrument(name = "commands::user::start_profile_image_upload", skip_all, fields(
+    ctx = ?ctx,
+    input = ?input
+))]
+pub async fn start_profile_image_upload(
+    deps: &Deps,
+    ctx: &Context,
+    input: StartProfileImageUploadInput,
+) -> Result<StartProfileImageUploadOutput> {
+    let upload_id = Uuid::new_v4();
+
+    let object_key = format!("{}{upload_id}", profile_images_key_prefix(input.user_id));
+
+    let presigned_url = deps
+        .object_storage
+        .get_presigned_post_url(
+            &deps.config.s3.videos_bucket,
+            &object_key,
+            &PostPolicy {
+                content_type: Some(input.content_type.as_str().to_owned()),
+                content_length_range: (PROFILE_IMAGE_MIN_BYTES, PROFILE_IMAGE_MAX_BYTES),
+            },
+        )
+        .await?;
+
+    deps.repos
+        .profile_image_uploads
+        .create(
+            &mut deps.db.write().await?,
+            NewProfileImageUpload {
+                id: upload_id,
+                user_id: input.user_id,
+                kind: input.kind,
+                object_key,
+                content_type: input.content_type,
+                expires_at: Utc::now() + Duration::seconds(PROFILE_IMAGE_UPLOAD_EXPIRES_IN_SECS),
+            },
+        )
+        .await?;
+
+    Ok(StartProfileImageUploadOutput {
+        upload_id,
+        presigned_url,
+    })
+}
+
+/// Checks that the uploaded object is an image of the declared content type
+/// before attaching it to the profile.
+#[tracing::instrument(name = "commands::user::confirm_profile_image_upload", skip_all, fields(
+    ctx = ?ctx,
+    input = ?input
+))]
+pub async fn confirm_profile_image_upload(
+    deps: &Deps,
+    ctx: &Context,
+    input: ConfirmProfileImageUploadInput,
+) -> Result<ConfirmProfileImageUploadOutput> {
+    let mut tx = deps.db.write().await?.transaction().await?;
+
+    let upload = deps
+        .repos
+        .profile_image_uploads
+        .get_for_update(&mut tx, input.user_id, input.upload_id)
+        .await?;
+
+    let upload = match upload {
+        Some(upload) if !upload.confirmed && upload.expires_at > Utc::now() => upload,
+        _ => return Err(ProfileImageUploadError::NotFound.into()),
+    };
+
+    let bytes = deps
+        .object_storage
+        .get(&deps.config.s3.videos_bucket, &upload.object_key)
+        .await?
+        .ok_or(ProfileImageUploadError::NotUploaded)?;
+
+    if bytes.len() as u64 > PROFILE_IMAGE_MAX_BYTES || !upload.content_type.matches(&bytes) {
+        info!(size = bytes.len(), "uploaded object is not a valid image");
+        return Err(ProfileImageUploadError::InvalidImage.into());
+    }
+
+    let url = deps
+        .object_storage
+        .object_url(&deps.config.s3.videos_bucket, &upload.object_key);
+
+    deps.repos
+        .users
+        .set_profile_image(&mut tx, input.user_id, upload.kind, &url)
+        .await?;
+
+    deps.repos
+        .profile_image_uploads
+        .mark_as_confirmed(&mut tx, upload.id)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(ConfirmProfileImageUploadOutput {
+        kind: upload.kind,
+        url,
+    })
+}
+
+/// Every profile image uploaded by the user is stored under this prefix.
+pub(super) fn profile_images_key_prefix(user_id: Uuid) -> String {
+    format!("profile-images/{user_id}/")
+}
@@ -0,0 +1,64 @@
+use super::create::send_verification_token;
+use crate::domain::contracts::{context::Context, deps::Deps};
+use crate::infra::uuid::Uuid;
+use anyhow::{Context as _, Result};
+use tracing::info;
+
+#[derive(Debug)]
+pub struct ResendVerificationEmailInput {
+    pub user_id: Uuid,
+}
+
+/// What we need to know about the user before sending a new verification token.
+#[derive(Debug)]
+pub struct UserEmail {
+    pub email: String,
+    pub verified: bool,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ResendVerificationEmailError {
+    #[error("the email is already verified")]
+    AlreadyVerified,
+}
+
+/// Sends a new verification token to the user.
+/// Tokens sent before stop working.
+#[tracing::instrument(name = "commands::user::resend_verification_email", skip_all, fields(
+    ctx = ?ctx,
+    user_id = %input.user_id
+))]
+pub async fn resend_verification_email(
+    deps: &Deps,
+    ctx: &Context,
+    input: ResendVerificationEmailInput,
+) -> Result<()> {
+    let mut tx = deps.db.write().await?.transaction().await?;
+
+    // The user row stays locked until the transaction ends so concurrent
+    // requests cannot leave more than one token usable.
+    let user = deps
+        .repos
+        .users
+        .get_email_for_update(&mut tx, input.user_id)
+        .await?
+        .context("user does not exist")?;
+
+    if user.verified {
+        info!("email is already verified");
+        return Err(ResendVerificationEmailError::AlreadyVerified.into());
+    }
+
+    deps.repos
+        .email_verification_tokens
+        .mark_all_as_used_for_user(&mut tx, input.user_id)
+        .await?;
+
+    // The email is sent before committing so the previous token
+    // keeps working if it cannot be sent.
+    send_verification_token(deps, &mut tx, input.user_id, user.email).await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
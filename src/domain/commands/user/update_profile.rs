@@ -0,0 +1,28 @@
+use crate::domain::contracts::{context::Context, deps::Deps};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+
+/// Fields that are None are left unchanged, empty strings clear the field.
+#[derive(Debug, Default)]
+pub struct ProfileChanges {
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub links: Option<Vec<String>>,
+}
+
+#[derive(Debug)]
+pub struct UpdateProfileInput {
+    pub user_id: Uuid,
+    pub changes: ProfileChanges,
+}
+
+#[tracing::instrument(name = "commands::user::update_profile", skip_all, fields(
+    ctx = ?ctx,
+    user_id = %input.user_id
+))]
+pub async fn update_profile(deps: &Deps, ctx: &Context, input: UpdateProfileInput) -> Result<()> {
+    deps.repos
+        .users
+        .update_profile(&mut deps.db.write().await?, input.user_id, input.changes)
+        .await
+}
@@ -0,0 +1,57 @@
+use crate::domain::contracts::{context::Context, deps::Deps};
+use crate::domain::value_objects::opaque_token::OpaqueToken;
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use tracing::info;
+
+#[derive(Debug)]
+pub struct VerifyEmailInput {
+    pub token: OpaqueToken,
+}
+
+#[derive(Debug)]
+pub struct EmailVerificationToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VerifyEmailError {
+    #[error("verification token is invalid or has expired")]
+    InvalidToken,
+}
+
+#[tracing::instrument(name = "commands::user::verify_email", skip_all, fields(ctx = ?ctx))]
+pub async fn verify_email(deps: &Deps, ctx: &Context, input: VerifyEmailInput) -> Result<()> {
+    let mut tx = deps.db.write().await?.transaction().await?;
+
+    let token = match deps
+        .repos
+        .email_verification_tokens
+        .get_by_token_hash_for_update(&mut tx, &input.token.hash())
+        .await?
+    {
+        Some(token) if token.used_at.is_none() && token.expires_at > Utc::now() => token,
+        _ => {
+            info!("verification token is invalid, used or expired");
+            return Err(VerifyEmailError::InvalidToken.into());
+        }
+    };
+
+    deps.repos
+        .email_verification_tokens
+        .mark_as_used(&mut tx, token.id)
+        .await?;
+
+    deps.repos
+        .users
+        .mark_email_as_verified(&mut tx, token.user_id)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
@@ -1,15 +1,26 @@
 
 use crate::{
-    domain::{contracts::{context::Context, deps::Deps}, self},
+    domain::{constants::{THUMBNAIL_MAX_BYTES, THUMBNAIL_MIN_BYTES, VIDEO_UPLOAD_EXPIRES_IN_SECS, VIDEO_UPLOAD_MAX_BYTES, VIDEO_UPLOAD_MIN_BYTES}, contracts::{context::Context, deps::Deps, object_storage::PostPolicy}, self, value_objects::{api_key_scope::ApiKeyScope, profile_image::ImageContentType, role::Role}},
     infra::uuid::Uuid,
 };
 use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
 use tracing::info;
 
 #[derive(Debug)]
 pub struct StartVideoUploadInput {
     /// Id of the user that wants to upload a video.
     pub user_id: Uuid,
+    /// Role of the user that wants to upload a video.
+    pub role: Role,
+    /// Users must verify their email before uploading videos.
+    pub email_verified: bool,
+    /// Creators that enabled two-factor authentication must log in
+    /// using it before uploading videos.
+    pub mfa_enabled: bool,
+    pub mfa_authenticated: bool,
+    /// Scopes of the api key used to start the upload, None when an access token was used.
+    pub api_key_scopes: Option<Vec<ApiKeyScope>>,
 }
 
 #[derive(Debug)]
@@ -18,14 +29,39 @@ pub struct StartVideoUploadOutput {
     /// direct to our object storage without passing through our servers.
     pub presigned_url: domain::contracts::object_storage::GetPresignedPostUrlOutput,
 
+    /// Endpoint the jpeg thumbnail shown in the preview of the post is uploaded to.
+    pub thumbnail_presigned_url: domain::contracts::object_storage::GetPresignedPostUrlOutput,
+
     /// Id identifiying the video in the object storage.
     pub video_id: Uuid
 }
 
+#[derive(Debug)]
+pub struct NewVideoUpload {
+    /// Also the key of the video in the videos bucket.
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct VideoUpload {
+    pub id: Uuid,
+    pub expires_at: DateTime<Utc>,
+    /// Some once a post has been created with the video.
+    pub post_id: Option<Uuid>,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum UploadVideoError {
     #[error("user is not allowed to upload videos")]
     UserNotAllowedToUploadVideos,
+    #[error("email must be verified before uploading videos")]
+    EmailNotVerified,
+    #[error("two-factor authentication is required to upload videos")]
+    MfaRequired,
+    #[error("api key does not have the videos:upload scope")]
+    ApiKeyMissingScope,
 }
 
 #[tracing::instrument(name = "commands::video::start_video_upload", skip_all, fields(
@@ -37,31 +73,91 @@ pub async fn start_video_upload(
     ctx: &Context,
     input: StartVideoUploadInput,
 ) -> Result<StartVideoUploadOutput> {
-    if !is_user_allowed_to_upload_videos(deps, input.user_id) {
+    if !is_user_allowed_to_upload_videos(&input) {
         info!("user is not allowed to upload videos");
         return Err(UploadVideoError::UserNotAllowedToUploadVideos.into());
     }
 
+    if !input.email_verified {
+        info!("user has not verified their email");
+        return Err(UploadVideoError::EmailNotVerified.into());
+    }
+
+    if input.mfa_enabled && !input.mfa_authenticated {
+        info!("session was not created using two-factor authentication");
+        return Err(UploadVideoError::MfaRequired.into());
+    }
+
+    if let Some(scopes) = &input.api_key_scopes {
+        if !ApiKeyScope::VideosUpload.is_allowed_by(scopes) {
+            info!("api key does not have the videos:upload scope");
+            return Err(UploadVideoError::ApiKeyMissingScope.into());
+        }
+    }
+
     let video_id = Uuid::new_v4();
 
+    let policy = PostPolicy {
+        content_type: None,
+        content_length_range: (VIDEO_UPLOAD_MIN_BYTES, VIDEO_UPLOAD_MAX_BYTES),
+    };
+
     let presigned_url = 
-        deps.object_storage.get_presigned_post_url(&deps.config.s3.videos_bucket, &video_id.to_string()).await?;
+        deps.object_storage.get_presigned_post_url(&deps.config.s3.videos_bucket, &video_id.to_string(), &policy).await?;
+
+    let thumbnail_policy = PostPolicy {
+        content_type: Some(ImageContentType::Jpeg.as_str().to_owned()),
+        content_length_range: (THUMBNAIL_MIN_BYTES, THUMBNAIL_MAX_BYTES),
+    };
+
+    let thumbnail_presigned_url = deps
+        .object_storage
+        .get_presigned_post_url(
+            &deps.config.s3.videos_bucket,
+            &thumbnail_key(input.user_id, video_id),
+            &thumbnail_policy,
+        )
+        .await?;
+
+    deps.repos
+        .video_uploads
+        .create(
+            &mut deps.db.write().await?,
+            NewVideoUpload {
+                id: video_id,
+                user_id: input.user_id,
+                expires_at: Utc::now() + Duration::seconds(VIDEO_UPLOAD_EXPIRES_IN_SECS),
+            },
+        )
+        .await?;
 
     Ok(StartVideoUploadOutput {
         video_id,
         presigned_url,
+        thumbnail_presigned_url,
     })
 }
 
-// TODO: check if user can upload a video. Is the user a content creator?
-// Users that just view videos from content creators should not be able to upload
-// videos.
+/// Every thumbnail uploaded by the user is stored under this prefix.
+/// Thumbnails are public since they are shown to viewers that cannot watch the video.
+pub fn thumbnails_key_prefix(user_id: Uuid) -> String {
+    format!("thumbnails/{user_id}/")
+}
+
+pub fn thumbnail_key(user_id: Uuid, video_id: Uuid) -> String {
+    format!("{}{video_id}", thumbnails_key_prefix(user_id))
+}
+
+/// Only content creators can upload videos.
+/// Users that just view videos from content creators should not be able to upload
+/// videos.
 #[tracing::instrument(name = "commands::video::is_user_allowed_to_upload_videos", skip_all, fields(
-    user_id = ?user_id,
+    user_id = ?input.user_id,
+    role = %input.role,
     allowed 
 ))]
-fn is_user_allowed_to_upload_videos(_deps: &Deps, user_id: Uuid) -> bool {
-    let allowed = true;
+fn is_user_allowed_to_upload_videos(input: &StartVideoUploadInput) -> bool {
+    let allowed = input.role == Role::Creator;
     tracing::Span::current().record("allowed", allowed);
     allowed
 }
@@ -5,3 +5,108 @@ pub const X_REQUEST_ID_HEADER_NAME: &str = "x-request-id";
 pub const AUTHORIZATION_HEADER_NAME: &str = "Authorization";
 
 pub const TIMELINE_LIMIT: i64 = 20;
+
+/// Number of pinned posts shown at the top of the first page of a creator feed.
+pub const PINNED_POSTS_LIMIT: i64 = 5;
+
+/// Number of users returned per page of the followers and following lists.
+pub const FOLLOWS_LIMIT: i64 = 50;
+
+/// Number of users returned per page of the blocked and muted lists.
+pub const RELATIONSHIPS_LIMIT: i64 = 50;
+
+/// Number of creators returned per page when discovering creators by tag.
+pub const CREATORS_LIMIT: i64 = 20;
+
+/// How long an access token is accepted for after being issued.
+pub const ACCESS_TOKEN_EXPIRES_IN_SECS: i64 = 15 * 60;
+
+/// How long a refresh token can be exchanged for a new access token.
+pub const REFRESH_TOKEN_EXPIRES_IN_SECS: i64 = 30 * 24 * 60 * 60;
+
+/// How long the token sent to verify an email address can be used for.
+pub const EMAIL_VERIFICATION_TOKEN_EXPIRES_IN_SECS: i64 = 24 * 60 * 60;
+
+/// How long the token sent to reset a password can be used for.
+pub const PASSWORD_RESET_TOKEN_EXPIRES_IN_SECS: i64 = 60 * 60;
+
+/// How long the user has to send the second factor after sending their password.
+pub const MFA_PENDING_TOKEN_EXPIRES_IN_SECS: i64 = 5 * 60;
+
+/// Number of recovery codes generated when two-factor authentication is enabled.
+pub const MFA_RECOVERY_CODES_COUNT: usize = 10;
+
+/// Shown next to the account name in authenticator apps.
+pub const TOTP_ISSUER: &str = "BetarMe";
+
+/// Failed logins allowed for an account before it is locked.
+pub const LOGIN_MAX_FAILED_ATTEMPTS_PER_ACCOUNT: i32 = 5;
+
+/// Failed logins allowed from an ip address before it is locked.
+/// Higher than the account limit because many users may share an ip.
+pub const LOGIN_MAX_FAILED_ATTEMPTS_PER_IP: i32 = 20;
+
+/// Duration of the first lockout, it doubles with every failed attempt after that.
+pub const LOGIN_LOCKOUT_BASE_SECS: i64 = 30;
+
+pub const LOGIN_LOCKOUT_MAX_SECS: i64 = 60 * 60;
+
+/// Failed attempts older than this are forgotten.
+pub const LOGIN_FAILED_ATTEMPTS_WINDOW_SECS: i64 = 24 * 60 * 60;
+
+/// Contains the address of the client when the api runs behind a proxy.
+pub const X_FORWARDED_FOR_HEADER_NAME: &str = "x-forwarded-for";
+
+/// The header should contain an api key created by the user making the request.
+pub const X_API_KEY_HEADER_NAME: &str = "x-api-key";
+
+/// Every api key starts with it, makes leaked keys easier to find.
+pub const API_KEY_PREFIX: &str = "bm_";
+
+/// How long the user has to come back from the openid connect issuer.
+pub const OIDC_AUTHORIZATION_REQUEST_EXPIRES_IN_SECS: i64 = 10 * 60;
+
+/// Size limits of the videos uploaded by creators, in bytes.
+pub const VIDEO_UPLOAD_MIN_BYTES: u64 = 1000;
+pub const VIDEO_UPLOAD_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// How long the creator has to upload a video and its thumbnail and create the post.
+pub const VIDEO_UPLOAD_EXPIRES_IN_SECS: i64 = 24 * 60 * 60;
+
+/// Size limits of the thumbnails uploaded with the videos, in bytes.
+pub const THUMBNAIL_MIN_BYTES: u64 = 100;
+pub const THUMBNAIL_MAX_BYTES: u64 = 512 * 1024;
+
+/// How long the url returned to watch the video of a post works for.
+/// Clients ask for a new one when it expires in the middle of a video.
+pub const POST_MEDIA_URL_EXPIRES_IN_SECS: i64 = 15 * 60;
+
+/// Size limits of avatars and banners, in bytes.
+pub const PROFILE_IMAGE_MIN_BYTES: u64 = 100;
+pub const PROFILE_IMAGE_MAX_BYTES: u64 = 2 * 1024 * 1024;
+
+/// How long the user has to upload and confirm a profile image.
+pub const PROFILE_IMAGE_UPLOAD_EXPIRES_IN_SECS: i64 = 60 * 60;
+
+/// How long the objects of a deleted account are kept before being purged,
+/// so accounts deleted by mistake can still be restored by support.
+pub const ACCOUNT_DELETION_PURGE_DELAY_SECS: i64 = 30 * 24 * 60 * 60;
+
+/// How long the download link of a personal data export works for.
+/// The archive is purged after that.
+pub const PERSONAL_DATA_EXPORT_EXPIRES_IN_SECS: i64 = 24 * 60 * 60;
+
+/// How long the user has to wait between personal data export requests.
+pub const PERSONAL_DATA_EXPORT_MIN_INTERVAL_SECS: i64 = 24 * 60 * 60;
+
+/// How often requested personal data exports are looked for.
+pub const PERSONAL_DATA_EXPORT_INTERVAL_SECS: u64 = 60;
+
+/// Maximum number of exports built at each interval.
+pub const PERSONAL_DATA_EXPORT_BATCH_SIZE: i64 = 5;
+
+/// How often scheduled object purges are looked for.
+pub const OBJECT_PURGE_INTERVAL_SECS: u64 = 10 * 60;
+
+/// Maximum number of purges run at each interval.
+pub const OBJECT_PURGE_BATCH_SIZE: i64 = 50;
@@ -3,6 +3,8 @@ use std::sync::Arc;
 use crate::config::Config;
 
 use super::http::Http;
+use super::identity_provider::IdentityProvider;
+use super::mailer::Mailer;
 use super::{
     object_storage::ObjectStorage,
     repository::{Database, Repository},
@@ -14,4 +16,7 @@ pub struct Deps {
     pub repos: Repository,
     pub http: Arc<dyn Http>,
     pub object_storage: Arc<dyn ObjectStorage>,
+    pub mailer: Arc<dyn Mailer>,
+    /// None when openid connect login is not configured.
+    pub identity_provider: Option<Arc<dyn IdentityProvider>>,
 }
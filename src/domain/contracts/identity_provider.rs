@@ -0,0 +1,42 @@
+use std::fmt::Debug;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Parameters sent to the issuer when the user is redirected to it.
+#[derive(Debug)]
+pub struct AuthorizationRequest {
+    pub state: String,
+    /// Echoed back in the id token so it cannot be replayed in another login.
+    pub nonce: String,
+    /// S256 challenge of the pkce code verifier.
+    pub code_challenge: String,
+}
+
+/// Claims of an id token whose signature, issuer, audience and expiration
+/// have already been verified.
+#[derive(Debug)]
+pub struct IdTokenClaims {
+    pub issuer: String,
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    pub nonce: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum IdentityProviderError {
+    #[error("the issuer rejected the authorization code")]
+    CodeRejected,
+    #[error("id token is invalid: {0}")]
+    InvalidIdToken(String),
+}
+
+#[async_trait]
+pub trait IdentityProvider: Send + Sync + Debug {
+    /// Returns the url of the issuer's authorization endpoint the user must be sent to.
+    async fn authorization_url(&self, request: &AuthorizationRequest) -> Result<String>;
+
+    /// Exchanges the authorization code for an id token and verifies it.
+    async fn exchange_code(&self, code: &str, code_verifier: &str) -> Result<IdTokenClaims>;
+}
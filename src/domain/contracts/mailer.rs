@@ -0,0 +1,26 @@
+use std::fmt::Debug;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[derive(Clone)]
+pub struct Mail {
+    pub to: String,
+    pub subject: String,
+    /// Plain text body.
+    pub body: String,
+}
+
+// The body is not printed because it may contain secrets such as verification tokens.
+impl Debug for Mail {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Mail")
+            .field("subject", &self.subject)
+            .finish()
+    }
+}
+
+#[async_trait]
+pub trait Mailer: Send + Sync + Debug {
+    async fn send(&self, mail: Mail) -> Result<()>;
+}
@@ -1,5 +1,7 @@
 pub mod context;
 pub mod deps;
 pub mod http;
+pub mod identity_provider;
+pub mod mailer;
 pub mod object_storage;
 pub mod repository;
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use std::{path::Path, time::Duration};
 
 #[derive(Debug)]
 pub struct GetPresignedPostUrlOutput {
@@ -16,6 +17,15 @@ pub struct FormDataField {
     pub value: String,
 }
 
+/// Restrictions the object uploaded through a presigned post url must follow.
+#[derive(Debug)]
+pub struct PostPolicy {
+    /// The object must be sent with exactly this content type when set.
+    pub content_type: Option<String>,
+    /// Minimum and maximum size of the object in bytes.
+    pub content_length_range: (u64, u64),
+}
+
 #[async_trait]
 pub trait ObjectStorage: Send + Sync {
     /// Generates a url that can be used by the client to
@@ -26,8 +36,41 @@ pub trait ObjectStorage: Send + Sync {
         &self,
         bucket: &str,
         key: &str,
+        policy: &PostPolicy,
     ) -> Result<GetPresignedPostUrlOutput>;
 
     /// Fetches a value associated with `key` in the `bucket`.
+    /// Returns None when there is no object with this key.
     async fn get(&self, bucket: &str, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Checks whether there is an object with this key without downloading it.
+    async fn exists(&self, bucket: &str, key: &str) -> Result<bool>;
+
+    /// Url the object can be downloaded from.
+    fn object_url(&self, bucket: &str, key: &str) -> String;
+
+    /// Stores the file at `path` under `key`, replacing the object if it exists.
+    /// The file is uploaded in parts so it is never loaded in memory at once.
+    async fn put_file(
+        &self,
+        bucket: &str,
+        key: &str,
+        path: &Path,
+        content_type: &str,
+    ) -> Result<()>;
+
+    /// Returns the keys of every object whose key starts with `prefix`.
+    async fn list_keys(&self, bucket: &str, prefix: &str) -> Result<Vec<String>>;
+
+    /// Does nothing when there is no object with this key.
+    async fn delete(&self, bucket: &str, key: &str) -> Result<()>;
+
+    /// Generates a url that can be used to download a private object
+    /// until `expires_in` has passed.
+    fn get_presigned_get_url(
+        &self,
+        bucket: &str,
+        key: &str,
+        expires_in: Duration,
+    ) -> Result<String>;
 }
@@ -6,10 +6,16 @@ use sqlx::{Executor as SqlxExecutor, Pool, Postgres, Transaction};
 use std::fmt::Debug;
 use std::sync::Arc;
 
-use crate::domain::commands;
 use crate::domain::queries::timeline::get_timeline::Post;
+use crate::domain::value_objects::creator_tag::CreatorTag;
 use crate::domain::value_objects::cursor::Cursor;
+use crate::domain::value_objects::password::Password;
+use crate::domain::value_objects::post_cursor::PostCursor;
+use crate::domain::value_objects::profile_image::ProfileImageKind;
+use crate::domain::value_objects::totp::TotpSecret;
+use crate::domain::{commands, queries};
 use crate::infra::uuid::Uuid;
+use chrono::{DateTime, Utc};
 
 pub struct Executor<'c> {
     inner: ExecutorInner<'c>,
@@ -27,7 +33,6 @@ pub enum ExecutorInner<'c> {
 }
 
 impl<'c> Executor<'c> {
-    #[allow(unused)]
     pub async fn transaction(self) -> Result<Executor<'c>> {
         match self.inner {
             ExecutorInner::Transaction(_) => {
@@ -39,6 +44,18 @@ impl<'c> Executor<'c> {
             }
         }
     }
+
+    pub async fn commit(self) -> Result<()> {
+        match self.inner {
+            ExecutorInner::Pool(_) => {
+                unreachable!("called commit() without calling transaction(), this is a bug")
+            }
+            ExecutorInner::Transaction(tx) => {
+                tx.commit().await?;
+                Ok(())
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -156,25 +173,736 @@ pub trait Database: Send + Sync + Debug {
 pub struct Repository {
     pub users: Arc<dyn UserRepository>,
     pub timeline: Arc<dyn TimelineRepository>,
+    pub refresh_tokens: Arc<dyn RefreshTokenRepository>,
+    pub email_verification_tokens: Arc<dyn EmailVerificationTokenRepository>,
+    pub password_reset_tokens: Arc<dyn PasswordResetTokenRepository>,
+    pub mfa_recovery_codes: Arc<dyn MfaRecoveryCodeRepository>,
+    pub used_mfa_tokens: Arc<dyn UsedMfaTokenRepository>,
+    pub login_attempts: Arc<dyn LoginAttemptRepository>,
+    pub api_keys: Arc<dyn ApiKeyRepository>,
+    pub oidc: Arc<dyn OidcRepository>,
+    pub profile_image_uploads: Arc<dyn ProfileImageUploadRepository>,
+    pub posts: Arc<dyn PostRepository>,
+    pub object_purges: Arc<dyn ObjectPurgeRepository>,
+    pub user_bans: Arc<dyn UserBanRepository>,
+    pub user_followers: Arc<dyn UserFollowerRepository>,
+    pub creator_tags: Arc<dyn CreatorTagRepository>,
+    pub user_blocks: Arc<dyn UserBlockRepository>,
+    pub user_mutes: Arc<dyn UserMuteRepository>,
+    pub terms: Arc<dyn TermsRepository>,
+    pub subscriptions: Arc<dyn SubscriptionRepository>,
+    pub video_uploads: Arc<dyn VideoUploadRepository>,
+    pub personal_data_exports: Arc<dyn PersonalDataExportRepository>,
 }
 
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait UserRepository: Send + Sync + Debug {
-    async fn get_by_id<'c>(&self, executor: &mut Executor<'c>, id: Uuid);
+    /// Returns None when the user does not exist or has been deleted.
+    async fn get_by_id<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+    ) -> Result<Option<queries::user::get_by_id::User>>;
+
+    async fn get_authenticated_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+    ) -> Result<Option<queries::session::get_authenticated_user::AuthenticatedUser>>;
 
+    async fn get_credentials_by_email<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        email: &str,
+    ) -> Result<Option<commands::session::UserCredentials>>;
+
+    /// Returns the id of the new user.
     async fn create<'c>(
         &self,
         executor: &mut Executor<'c>,
         input: commands::user::CreateUserInput,
+    ) -> Result<Uuid>;
+
+    async fn mark_email_as_verified<'c>(&self, executor: &mut Executor<'c>, id: Uuid)
+        -> Result<()>;
+
+    /// Locks the user row until the transaction ends.
+    /// Returns None when the user does not exist or has been deleted.
+    async fn get_email_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+    ) -> Result<Option<commands::user::UserEmail>>;
+
+    async fn update_password<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+        password: Password,
+    ) -> Result<()>;
+
+    /// Locks the user row until the transaction ends, so the same totp code
+    /// cannot be accepted twice concurrently.
+    async fn get_mfa_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+    ) -> Result<Option<commands::mfa::UserMfa>>;
+
+    async fn set_totp_secret<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+        secret: &TotpSecret,
+    ) -> Result<()>;
+
+    /// `totp_step` is the step of the code used to confirm the enrollment.
+    async fn enable_mfa<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+        totp_step: i64,
+    ) -> Result<()>;
+
+    async fn set_totp_last_used_step<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+        totp_step: i64,
+    ) -> Result<()>;
+
+    async fn update_profile<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+        changes: commands::user::ProfileChanges,
+    ) -> Result<()>;
+
+    async fn set_profile_image<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+        kind: ProfileImageKind,
+        url: &str,
+    ) -> Result<()>;
+
+    async fn soft_delete<'c>(&self, executor: &mut Executor<'c>, id: Uuid) -> Result<()>;
+
+    /// Returns None when the user does not exist or has been deleted.
+    async fn get_personal_data<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+    ) -> Result<Option<commands::user::PersonalDataProfile>>;
+
+    /// Locks the user row until the transaction ends, so concurrent bans
+    /// do not record the same change twice.
+    /// Returns None when the user does not exist or has been deleted.
+    async fn get_ban_status_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+    ) -> Result<Option<commands::moderation::UserBanStatus>>;
+
+    async fn set_banned<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+        banned: bool,
     ) -> Result<()>;
 }
 
 #[async_trait]
 pub trait TimelineRepository: Send + Sync + Debug {
+    /// Posts of banned or deleted creators and of creators that blocked
+    /// or were blocked or muted by the viewer are left out.
+    /// Returns up to `limit` posts that come after `cursor`, the most recently published first.
     async fn get_timeline<'c>(
         &self,
         executor: &mut Executor<'c>,
-        cursor: Cursor,
+        viewer_id: Option<Uuid>,
+        cursor: Option<PostCursor>,
+        limit: i64,
     ) -> Result<Vec<Post>>;
+
+    /// Same as `get_timeline` but only with posts of creators the viewer
+    /// follows or has an active subscription to.
+    async fn get_following_timeline<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        viewer_id: Uuid,
+        cursor: Option<PostCursor>,
+        limit: i64,
+    ) -> Result<Vec<Post>>;
+}
+
+#[async_trait]
+pub trait RefreshTokenRepository: Send + Sync + Debug {
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::session::NewRefreshToken,
+    ) -> Result<()>;
+
+    /// Locks the token row until the transaction ends, so the same token
+    /// cannot be exchanged twice concurrently.
+    async fn get_by_token_hash_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        token_hash: &str,
+    ) -> Result<Option<commands::session::RefreshToken>>;
+
+    async fn mark_as_used<'c>(&self, executor: &mut Executor<'c>, id: Uuid) -> Result<()>;
+
+    async fn revoke_family<'c>(&self, executor: &mut Executor<'c>, family_id: Uuid) -> Result<()>;
+
+    async fn revoke_all_for_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+    ) -> Result<()>;
+
+    async fn is_family_revoked<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        family_id: Uuid,
+    ) -> Result<bool>;
+
+    /// Returns every session the user has started, including revoked ones.
+    async fn list_sessions_by_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+    ) -> Result<Vec<commands::user::PersonalDataSession>>;
+}
+
+#[async_trait]
+pub trait EmailVerificationTokenRepository: Send + Sync + Debug {
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::user::NewEmailVerificationToken,
+    ) -> Result<()>;
+
+    async fn get_by_token_hash_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        token_hash: &str,
+    ) -> Result<Option<commands::user::EmailVerificationToken>>;
+
+    async fn mark_as_used<'c>(&self, executor: &mut Executor<'c>, id: Uuid) -> Result<()>;
+
+    async fn mark_all_as_used_for_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+    ) -> Result<()>;
+}
+
+#[async_trait]
+pub trait PasswordResetTokenRepository: Send + Sync + Debug {
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::password_reset::NewPasswordResetToken,
+    ) -> Result<()>;
+
+    async fn get_by_token_hash_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        token_hash: &str,
+    ) -> Result<Option<commands::password_reset::PasswordResetToken>>;
+
+    async fn mark_all_as_used_for_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+    ) -> Result<()>;
+}
+
+#[async_trait]
+pub trait UsedMfaTokenRepository: Send + Sync + Debug {
+    /// Records that the mfa token identified by `jti` was exchanged for a session.
+    /// Returns false when it already was.
+    async fn use_token<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        jti: Uuid,
+        user_id: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool>;
+}
+
+#[async_trait]
+pub trait MfaRecoveryCodeRepository: Send + Sync + Debug {
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::mfa::NewMfaRecoveryCode,
+    ) -> Result<()>;
+
+    async fn delete_all_for_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+    ) -> Result<()>;
+
+    /// Marks the code as used. Returns false when the user does not have
+    /// an unused code with this hash.
+    async fn use_code<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+        code_hash: &str,
+    ) -> Result<bool>;
+}
+
+#[async_trait]
+pub trait LoginAttemptRepository: Send + Sync + Debug {
+    /// Returns the furthest lockout among the keys, if any of them has one.
+    async fn get_locked_until<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        keys: &[commands::session::LoginAttemptKey],
+    ) -> Result<Option<DateTime<Utc>>>;
+
+    /// Returns the number of failed attempts including this one.
+    /// The count starts over when the last failure happened more than `window_secs` ago.
+    async fn record_failure<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        key: &commands::session::LoginAttemptKey,
+        window_secs: i64,
+    ) -> Result<i32>;
+
+    async fn lock<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        key: &commands::session::LoginAttemptKey,
+        until: DateTime<Utc>,
+    ) -> Result<()>;
+
+    async fn clear<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        key: &commands::session::LoginAttemptKey,
+    ) -> Result<()>;
+}
+
+#[async_trait]
+pub trait ApiKeyRepository: Send + Sync + Debug {
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::api_key::NewApiKey,
+    ) -> Result<()>;
+
+    /// Returns the keys of the user that have not been revoked.
+    async fn list_by_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+    ) -> Result<Vec<queries::api_key::list::ApiKey>>;
+
+    /// Returns None when the key does not exist or has been revoked.
+    async fn get_by_secret_hash<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        secret_hash: &str,
+    ) -> Result<Option<queries::api_key::authenticate::ApiKeyOwner>>;
+
+    /// Returns false when the user does not have a key with this id that is not revoked.
+    async fn revoke<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<bool>;
+
+    async fn revoke_all_for_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+    ) -> Result<()>;
+
+    async fn mark_as_used<'c>(&self, executor: &mut Executor<'c>, id: Uuid) -> Result<()>;
+}
+
+#[async_trait]
+pub trait OidcRepository: Send + Sync + Debug {
+    async fn create_authorization_request<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::session::NewOidcAuthorizationRequest,
+    ) -> Result<()>;
+
+    /// Locks the request row until the transaction ends, so the same state
+    /// cannot be used twice concurrently.
+    async fn get_authorization_request_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        state_hash: &str,
+    ) -> Result<Option<commands::session::OidcAuthorizationRequest>>;
+
+    async fn mark_authorization_request_as_used<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+    ) -> Result<()>;
+
+    /// Returns None when the identity is not linked to a user that has not been deleted.
+    async fn get_user_id_by_identity<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        issuer: &str,
+        subject: &str,
+    ) -> Result<Option<Uuid>>;
+
+    async fn create_identity<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::session::NewOidcIdentity,
+    ) -> Result<()>;
+
+    async fn list_identities_by_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+    ) -> Result<Vec<commands::user::PersonalDataIdentity>>;
+}
+
+#[async_trait]
+pub trait ProfileImageUploadRepository: Send + Sync + Debug {
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::user::NewProfileImageUpload,
+    ) -> Result<()>;
+
+    /// Locks the upload row until the transaction ends, so the same upload
+    /// cannot be confirmed twice concurrently.
+    async fn get_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<commands::user::ProfileImageUpload>>;
+
+    async fn mark_as_confirmed<'c>(&self, executor: &mut Executor<'c>, id: Uuid) -> Result<()>;
+}
+
+#[async_trait]
+pub trait VideoUploadRepository: Send + Sync + Debug {
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::video::NewVideoUpload,
+    ) -> Result<()>;
+
+    /// Returns None when the upload does not exist or belongs to another user.
+    async fn get_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<commands::video::VideoUpload>>;
+
+    async fn set_post_id<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+        post_id: Uuid,
+    ) -> Result<()>;
+}
+
+#[async_trait]
+pub trait PostRepository: Send + Sync + Debug {
+    /// Returns None when the post does not exist or its creator
+    /// has been banned or deleted.
+    async fn get_by_id<'c>(&self, executor: &mut Executor<'c>, id: Uuid) -> Result<Option<Post>>;
+
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::post::NewPost,
+    ) -> Result<()>;
+
+    async fn list_by_creator<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        creator_id: Uuid,
+    ) -> Result<Vec<commands::user::PersonalDataPost>>;
+
+    /// Returns up to `limit` pinned posts of the creator, the most recently pinned first.
+    /// Drafts and scheduled posts are left out unless `include_unpublished` is true.
+    async fn list_pinned_by_creator<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        creator_id: Uuid,
+        include_unpublished: bool,
+        limit: i64,
+    ) -> Result<Vec<Post>>;
+
+    /// Returns up to `limit` posts of the creator that come after `cursor`,
+    /// the most recently published first, like the timeline.
+    /// Drafts and scheduled posts are left out unless `include_unpublished` is true.
+    ///
+    /// The posts returned by `list_pinned_by_creator` with the same `pinned_limit`
+    /// are left out, older pinned posts are part of the feed like any other post.
+    async fn list_feed_by_creator<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        creator_id: Uuid,
+        include_unpublished: bool,
+        pinned_limit: i64,
+        cursor: Option<PostCursor>,
+        limit: i64,
+    ) -> Result<Vec<Post>>;
+}
+
+#[async_trait]
+pub trait ObjectPurgeRepository: Send + Sync + Debug {
+    async fn schedule<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::object_purge::NewObjectPurge,
+    ) -> Result<()>;
+
+    /// Returns at most `limit` purges whose time has come and that have not run yet.
+    /// Purges locked by another transaction are skipped.
+    async fn get_due_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        limit: i64,
+    ) -> Result<Vec<commands::object_purge::ObjectPurge>>;
+
+    async fn mark_as_purged<'c>(&self, executor: &mut Executor<'c>, id: Uuid) -> Result<()>;
+}
+
+#[async_trait]
+pub trait PersonalDataExportRepository: Send + Sync + Debug {
+    /// Returns false when the user already has an export waiting to be built.
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::user::NewPersonalDataExport,
+    ) -> Result<bool>;
+
+    /// Returns the export the user requested most recently.
+    async fn get_latest_by_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+    ) -> Result<Option<commands::user::PersonalDataExport>>;
+
+    /// Returns None when the export does not exist or was requested by another user.
+    async fn get_by_id<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<commands::user::PersonalDataExport>>;
+
+    /// Returns the oldest export that has not been built yet.
+    /// Exports locked by another transaction and exports of deleted users are skipped.
+    async fn get_pending_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+    ) -> Result<Option<commands::user::PendingPersonalDataExport>>;
+
+    async fn mark_as_completed<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+        object_key: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<()>;
+}
+
+#[async_trait]
+pub trait UserBanRepository: Send + Sync + Debug {
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::moderation::NewUserBan,
+    ) -> Result<()>;
+}
+
+#[async_trait]
+pub trait UserFollowerRepository: Send + Sync + Debug {
+    /// Returns false when the follower already follows the followee.
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        follower_id: Uuid,
+        followee_id: Uuid,
+    ) -> Result<bool>;
+
+    /// Returns false when the follower did not follow the followee.
+    async fn delete<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        follower_id: Uuid,
+        followee_id: Uuid,
+    ) -> Result<bool>;
+
+    /// Users that blocked or were blocked by the viewer are left out.
+    async fn list_followers<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        viewer_id: Option<Uuid>,
+        user_id: Uuid,
+        cursor: Cursor,
+    ) -> Result<Vec<queries::follow::list_followers::FollowUser>>;
+
+    /// Users that blocked or were blocked by the viewer are left out.
+    async fn list_following<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        viewer_id: Option<Uuid>,
+        user_id: Uuid,
+        cursor: Cursor,
+    ) -> Result<Vec<queries::follow::list_followers::FollowUser>>;
+}
+
+#[async_trait]
+pub trait CreatorTagRepository: Send + Sync + Debug {
+    async fn list_by_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+    ) -> Result<Vec<CreatorTag>>;
+
+    /// Returns the tags that the user did not have yet.
+    async fn add<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+        tags: &[CreatorTag],
+    ) -> Result<Vec<CreatorTag>>;
+
+    /// Returns the tags that the user had.
+    async fn remove<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+        tags: &[CreatorTag],
+    ) -> Result<Vec<CreatorTag>>;
+
+    /// Adds `delta` to the cached number of creators of each tag.
+    async fn update_counts<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        tags: &[CreatorTag],
+        delta: i32,
+    ) -> Result<()>;
+
+    /// Returns the cached number of creators of the tags that have been used.
+    async fn list_counts<'c>(&self, executor: &mut Executor<'c>) -> Result<Vec<(CreatorTag, i32)>>;
+
+    async fn list_creators<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        tag: CreatorTag,
+        cursor: Cursor,
+    ) -> Result<Vec<queries::creator::list_by_tag::Creator>>;
+}
+
+#[async_trait]
+pub trait UserBlockRepository: Send + Sync + Debug {
+    /// Returns false when the blocker already blocks the user.
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        blocker_id: Uuid,
+        blocked_id: Uuid,
+    ) -> Result<bool>;
+
+    /// Returns false when the blocker did not block the user.
+    async fn delete<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        blocker_id: Uuid,
+        blocked_id: Uuid,
+    ) -> Result<bool>;
+
+    /// Returns true when either user blocked the other one.
+    /// Every interaction between users must be refused when it does.
+    async fn is_blocked_between<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+        other_user_id: Uuid,
+    ) -> Result<bool>;
+
+    async fn list_by_blocker<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        blocker_id: Uuid,
+        cursor: Cursor,
+    ) -> Result<Vec<queries::relationship::list_blocked::RelatedUser>>;
+}
+
+#[async_trait]
+pub trait UserMuteRepository: Send + Sync + Debug {
+    /// Returns false when the muter already mutes the user.
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        muter_id: Uuid,
+        muted_id: Uuid,
+    ) -> Result<bool>;
+
+    /// Returns false when the muter did not mute the user.
+    async fn delete<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        muter_id: Uuid,
+        muted_id: Uuid,
+    ) -> Result<bool>;
+
+    async fn list_by_muter<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        muter_id: Uuid,
+        cursor: Cursor,
+    ) -> Result<Vec<queries::relationship::list_blocked::RelatedUser>>;
+}
+
+#[async_trait]
+pub trait TermsRepository: Send + Sync + Debug {
+    /// Returns the most recently published version,
+    /// None when no version has been published yet.
+    async fn get_current<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+    ) -> Result<Option<queries::terms::get_current::TermsVersion>>;
+
+    async fn create_acceptance<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::terms::NewTermsAcceptance,
+    ) -> Result<()>;
+}
+
+#[async_trait]
+pub trait SubscriptionRepository: Send + Sync + Debug {
+    /// Returns the ids of the creators, among `creator_ids`,
+    /// the subscriber has an active subscription to.
+    async fn list_active_creator_ids<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        subscriber_id: Uuid,
+        creator_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>>;
+
+    /// Ends the active subscriptions either user has to the other one.
+    /// Returns the number of subscriptions that were ended.
+    async fn end_between<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+        other_user_id: Uuid,
+    ) -> Result<u64>;
 }
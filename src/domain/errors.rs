@@ -1,4 +1,10 @@
-use super::value_objects::{password::PasswordError, email::EmailError};
+use super::commands::terms::AcceptTermsError;
+use super::commands::user::CreateUserError;
+use super::value_objects::{
+    api_key_scope::ApiKeyScopeError, creator_tag::CreatorTagError, email::EmailError,
+    password::PasswordError, post_cursor::PostCursorError, profile_image::ProfileImageError,
+    username::UsernameError,
+};
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct ValidationError {
@@ -17,13 +23,86 @@ impl From<EmailError> for ValidationError {
     }
 }
 
+impl From<UsernameError> for ValidationError {
+    fn from(input: UsernameError) -> Self {
+        Self {
+            name: "username".to_owned(),
+            message: input.to_string()
+        }
+    }
+}
+
+impl From<CreateUserError> for ValidationError {
+    fn from(input: CreateUserError) -> Self {
+        match input {
+            CreateUserError::UsernameTaken => Self {
+                name: "username".to_owned(),
+                message: input.to_string()
+            },
+            CreateUserError::EmailTaken => Self {
+                name: "email".to_owned(),
+                message: input.to_string()
+            }
+        }
+    }
+}
+
+impl From<AcceptTermsError> for ValidationError {
+    fn from(input: AcceptTermsError) -> Self {
+        Self {
+            name: "accepted_terms_version".to_owned(),
+            message: input.to_string()
+        }
+    }
+}
+
 impl From<PasswordError> for ValidationError {
     fn from(input: PasswordError) -> Self {
+        Self {
+            name: "password".to_owned(),
+            message: input.to_string()
+        }
+    }
+}
+
+impl From<ApiKeyScopeError> for ValidationError {
+    fn from(input: ApiKeyScopeError) -> Self {
+        Self {
+            name: "scopes".to_owned(),
+            message: input.to_string()
+        }
+    }
+}
+
+impl From<CreatorTagError> for ValidationError {
+    fn from(input: CreatorTagError) -> Self {
+        Self {
+            name: "tags".to_owned(),
+            message: input.to_string()
+        }
+    }
+}
+
+impl From<PostCursorError> for ValidationError {
+    fn from(input: PostCursorError) -> Self {
+        Self {
+            name: "cursor".to_owned(),
+            message: input.to_string()
+        }
+    }
+}
+
+impl From<ProfileImageError> for ValidationError {
+    fn from(input: ProfileImageError) -> Self {
         match input {
-            PasswordError::InvalidPassword => Self {
-                name: "password".to_owned(),
-                message: "invalid password".to_owned()
-            }
+            ProfileImageError::UnknownKind(_) => Self {
+                name: "kind".to_owned(),
+                message: input.to_string()
+            },
+            ProfileImageError::UnsupportedContentType(_) => Self {
+                name: "content_type".to_owned(),
+                message: input.to_string()
+            },
         }
     }
 }
@@ -0,0 +1,31 @@
+use crate::{
+    domain::{
+        constants::API_KEY_PREFIX,
+        contracts::deps::Deps,
+        value_objects::{api_key_scope::ApiKeyScope, opaque_token::OpaqueToken},
+    },
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+
+/// The api key used in a request and who it belongs to.
+#[derive(Debug)]
+pub struct ApiKeyOwner {
+    pub api_key_id: Uuid,
+    pub user_id: Uuid,
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+/// Returns None when the key does not exist or has been revoked.
+#[tracing::instrument(name = "queries::api_key::authenticate", skip_all)]
+pub async fn handle(deps: &Deps, key: &str) -> Result<Option<ApiKeyOwner>> {
+    let secret = match key.strip_prefix(API_KEY_PREFIX) {
+        None => return Ok(None),
+        Some(secret) => OpaqueToken::from(secret.to_owned()),
+    };
+
+    deps.repos
+        .api_keys
+        .get_by_secret_hash(&mut deps.db.read().await?, &secret.hash())
+        .await
+}
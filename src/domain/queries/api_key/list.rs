@@ -0,0 +1,31 @@
+use crate::{
+    domain::{
+        contracts::{context::Context, deps::Deps},
+        value_objects::api_key_scope::ApiKeyScope,
+    },
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+/// An api key that has not been revoked. The key itself cannot be recovered.
+#[derive(Debug)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub name: String,
+    pub prefix: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[tracing::instrument(name = "queries::api_key::list", skip_all, fields(
+    ctx = ?ctx,
+    user_id = %user_id
+))]
+pub async fn handle(deps: &Deps, ctx: &Context, user_id: Uuid) -> Result<Vec<ApiKey>> {
+    deps.repos
+        .api_keys
+        .list_by_user(&mut deps.db.read().await?, user_id)
+        .await
+}
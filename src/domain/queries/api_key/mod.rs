@@ -0,0 +1,2 @@
+pub mod authenticate;
+pub mod list;
@@ -0,0 +1,38 @@
+use crate::{
+    domain::{
+        contracts::{context::Context, deps::Deps},
+        value_objects::{creator_tag::CreatorTag, cursor::Cursor},
+    },
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+
+/// A creator shown when discovering creators.
+#[derive(Debug)]
+pub struct Creator {
+    pub id: Uuid,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub followers_count: i64,
+    pub tags: Vec<CreatorTag>,
+}
+
+/// Creators with the most followers first.
+/// Deleted and banned creators are left out.
+#[tracing::instrument(name = "queries::creator::list_by_tag", skip_all, fields(
+    ctx = ?ctx,
+    tag = %tag,
+    cursor = ?cursor
+))]
+pub async fn handle(
+    deps: &Deps,
+    ctx: &Context,
+    tag: CreatorTag,
+    cursor: Cursor,
+) -> Result<Vec<Creator>> {
+    deps.repos
+        .creator_tags
+        .list_creators(&mut deps.db.read().await?, tag, cursor)
+        .await
+}
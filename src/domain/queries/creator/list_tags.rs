@@ -0,0 +1,33 @@
+use crate::domain::{
+    contracts::{context::Context, deps::Deps},
+    value_objects::creator_tag::CreatorTag,
+};
+use anyhow::Result;
+
+#[derive(Debug)]
+pub struct TagCount {
+    pub tag: CreatorTag,
+    pub creators_count: i32,
+}
+
+/// Every tag of the curated list, including the ones no creator uses yet.
+#[tracing::instrument(name = "queries::creator::list_tags", skip_all, fields(ctx = ?ctx))]
+pub async fn handle(deps: &Deps, ctx: &Context) -> Result<Vec<TagCount>> {
+    let counts = deps
+        .repos
+        .creator_tags
+        .list_counts(&mut deps.db.read().await?)
+        .await?;
+
+    Ok(CreatorTag::ALL
+        .into_iter()
+        .map(|tag| TagCount {
+            tag,
+            creators_count: counts
+                .iter()
+                .find(|(counted, _)| *counted == tag)
+                .map(|(_, count)| *count)
+                .unwrap_or(0),
+        })
+        .collect())
+}
@@ -0,0 +1,2 @@
+pub mod list_by_tag;
+pub mod list_tags;
@@ -0,0 +1,37 @@
+use crate::{
+    domain::{
+        contracts::{context::Context, deps::Deps},
+        queries::user::get_by_id,
+        value_objects::cursor::Cursor,
+    },
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+
+use super::list_followers::FollowUser;
+
+/// Most recently followed users first. Deleted and banned users are left out,
+/// and so are users that blocked or were blocked by the viewer.
+#[tracing::instrument(name = "queries::follow::list_following", skip_all, fields(
+    ctx = ?ctx,
+    viewer_id = ?viewer_id,
+    user_id = %user_id,
+    cursor = ?cursor
+))]
+pub async fn handle(
+    deps: &Deps,
+    ctx: &Context,
+    viewer_id: Option<Uuid>,
+    user_id: Uuid,
+    cursor: Cursor,
+) -> Result<Vec<FollowUser>> {
+    // The list is not found when the user is not found by the viewer.
+    get_by_id::handle(deps, ctx, viewer_id, user_id).await?;
+
+    let mut executor = deps.db.read().await?;
+
+    deps.repos
+        .user_followers
+        .list_following(&mut executor, viewer_id, user_id, cursor)
+        .await
+}
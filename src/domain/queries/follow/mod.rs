@@ -0,0 +1,2 @@
+pub mod list_followers;
+pub mod list_following;
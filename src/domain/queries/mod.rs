@@ -1,2 +1,9 @@
+pub mod api_key;
+pub mod creator;
+pub mod follow;
+pub mod post;
+pub mod relationship;
+pub mod session;
+pub mod terms;
 pub mod user;
 pub mod timeline;
@@ -0,0 +1,100 @@
+use crate::{
+    domain::{
+        constants::POST_MEDIA_URL_EXPIRES_IN_SECS,
+        contracts::{context::Context, deps::Deps},
+        queries::timeline::paywall,
+    },
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use tracing::info;
+
+#[derive(Debug)]
+pub struct PostMedia {
+    /// Where the video can be downloaded from until `expires_at`.
+    pub video_url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetPostMediaError {
+    #[error("post not found")]
+    NotFound,
+    #[error("subscribe to the creator to watch this post")]
+    Locked,
+}
+
+/// The videos bucket is private, the media of a post can only be downloaded
+/// through the short lived url returned here, after going through the paywall.
+///
+/// Drafts and scheduled posts are only found by their creator, and posts are not
+/// found when the viewer and the creator blocked each other.
+#[tracing::instrument(name = "queries::post::get_media", skip_all, fields(
+    ctx = ?ctx,
+    viewer_id = ?viewer_id,
+    post_id = %post_id
+))]
+pub async fn handle(
+    deps: &Deps,
+    ctx: &Context,
+    viewer_id: Option<Uuid>,
+    post_id: Uuid,
+) -> Result<PostMedia> {
+    let mut executor = deps.db.read().await?;
+
+    let post = match deps.repos.posts.get_by_id(&mut executor, post_id).await? {
+        None => return Err(GetPostMediaError::NotFound.into()),
+        Some(post) => post,
+    };
+
+    let is_creator = viewer_id == Some(post.creator_id);
+
+    let is_published = post
+        .published_at
+        .map(|published_at| published_at <= Utc::now())
+        .unwrap_or(false);
+
+    if !is_creator && !is_published {
+        info!("post is not published yet");
+        return Err(GetPostMediaError::NotFound.into());
+    }
+
+    let mut subscribed_to = vec![];
+
+    if let Some(viewer_id) = viewer_id {
+        if !is_creator
+            && deps
+                .repos
+                .user_blocks
+                .is_blocked_between(&mut executor, viewer_id, post.creator_id)
+                .await?
+        {
+            return Err(GetPostMediaError::NotFound.into());
+        }
+
+        if post.paid && !is_creator {
+            subscribed_to = deps
+                .repos
+                .subscriptions
+                .list_active_creator_ids(&mut executor, viewer_id, &[post.creator_id])
+                .await?;
+        }
+    }
+
+    if !paywall::can_access_media(&post, viewer_id, &subscribed_to) {
+        info!("viewer is not subscribed to the creator");
+        return Err(GetPostMediaError::Locked.into());
+    }
+
+    let video_url = deps.object_storage.get_presigned_get_url(
+        &deps.config.s3.videos_bucket,
+        &post.video_key,
+        std::time::Duration::from_secs(POST_MEDIA_URL_EXPIRES_IN_SECS as u64),
+    )?;
+
+    Ok(PostMedia {
+        video_url,
+        expires_at: Utc::now() + Duration::seconds(POST_MEDIA_URL_EXPIRES_IN_SECS),
+    })
+}
@@ -0,0 +1,88 @@
+use crate::{
+    domain::{
+        constants::{PINNED_POSTS_LIMIT, TIMELINE_LIMIT},
+        contracts::{context::Context, deps::Deps},
+        queries::{
+            timeline::{get_timeline::PostPage, paywall},
+            user::get_by_id::GetUserError,
+        },
+        value_objects::post_cursor::PostCursor,
+    },
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+
+/// The feed of a creator. The first page starts with the most recently pinned posts,
+/// the other pinned posts are in the feed like any other post.
+/// The creator also sees their drafts and scheduled posts.
+#[tracing::instrument(name = "queries::post::list_by_creator", skip_all, fields(
+    ctx = ?ctx,
+    viewer_id = ?viewer_id,
+    creator_id = %creator_id
+))]
+pub async fn handle(
+    deps: &Deps,
+    ctx: &Context,
+    viewer_id: Option<Uuid>,
+    creator_id: Uuid,
+    cursor: Option<PostCursor>,
+) -> Result<PostPage> {
+    let mut executor = deps.db.read().await?;
+
+    let is_creator = viewer_id == Some(creator_id);
+
+    if let Some(viewer_id) = viewer_id {
+        if !is_creator
+            && deps
+                .repos
+                .user_blocks
+                .is_blocked_between(&mut executor, viewer_id, creator_id)
+                .await?
+        {
+            return Err(GetUserError::NotFound.into());
+        }
+    }
+
+    if deps
+        .repos
+        .users
+        .get_by_id(&mut executor, creator_id)
+        .await?
+        .is_none()
+    {
+        return Err(GetUserError::NotFound.into());
+    }
+
+    let pinned = match &cursor {
+        Some(_) => vec![],
+        None => {
+            deps.repos
+                .posts
+                .list_pinned_by_creator(&mut executor, creator_id, is_creator, PINNED_POSTS_LIMIT)
+                .await?
+        }
+    };
+
+    let posts = deps
+        .repos
+        .posts
+        .list_feed_by_creator(
+            &mut executor,
+            creator_id,
+            is_creator,
+            PINNED_POSTS_LIMIT,
+            cursor,
+            TIMELINE_LIMIT + 1,
+        )
+        .await?;
+
+    let mut page = PostPage::new(posts, TIMELINE_LIMIT);
+
+    // The cursor points at the last post of the feed,
+    // so prepending the pinned posts does not change it.
+    page.posts = pinned.into_iter().chain(page.posts).collect();
+
+    paywall::lock_posts(deps, &mut executor, viewer_id, &mut page.posts).await?;
+
+    Ok(page)
+}
@@ -0,0 +1,2 @@
+pub mod get_media;
+pub mod list_by_creator;
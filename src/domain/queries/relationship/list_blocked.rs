@@ -0,0 +1,38 @@
+use crate::{
+    domain::{
+        contracts::{context::Context, deps::Deps},
+        value_objects::cursor::Cursor,
+    },
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+/// A user the owner of the list has blocked or muted.
+#[derive(Debug)]
+pub struct RelatedUser {
+    pub id: Uuid,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    /// When the user was blocked or muted.
+    pub since: DateTime<Utc>,
+}
+
+/// Most recently blocked users first. Deleted users are left out.
+#[tracing::instrument(name = "queries::relationship::list_blocked", skip_all, fields(
+    ctx = ?ctx,
+    user_id = %user_id,
+    cursor = ?cursor
+))]
+pub async fn handle(
+    deps: &Deps,
+    ctx: &Context,
+    user_id: Uuid,
+    cursor: Cursor,
+) -> Result<Vec<RelatedUser>> {
+    deps.repos
+        .user_blocks
+        .list_by_blocker(&mut deps.db.read().await?, user_id, cursor)
+        .await
+}
@@ -0,0 +1,28 @@
+use crate::{
+    domain::{
+        contracts::{context::Context, deps::Deps},
+        value_objects::cursor::Cursor,
+    },
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+
+use super::list_blocked::RelatedUser;
+
+/// Most recently muted users first. Deleted users are left out.
+#[tracing::instrument(name = "queries::relationship::list_muted", skip_all, fields(
+    ctx = ?ctx,
+    user_id = %user_id,
+    cursor = ?cursor
+))]
+pub async fn handle(
+    deps: &Deps,
+    ctx: &Context,
+    user_id: Uuid,
+    cursor: Cursor,
+) -> Result<Vec<RelatedUser>> {
+    deps.repos
+        .user_mutes
+        .list_by_muter(&mut deps.db.read().await?, user_id, cursor)
+        .await
+}
@@ -0,0 +1,2 @@
+pub mod list_blocked;
+pub mod list_muted;
@@ -0,0 +1,32 @@
+use crate::{
+    domain::{contracts::deps::Deps, value_objects::role::Role},
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+
+/// What we need to know about the user making a request
+/// before letting the request through.
+#[derive(Debug)]
+pub struct AuthenticatedUser {
+    pub id: Uuid,
+    pub role: Role,
+    pub email_verified: bool,
+    /// True when the user enabled two-factor authentication.
+    pub mfa_enabled: bool,
+    /// Banned users cannot use the app until an admin lifts the ban.
+    pub banned: bool,
+    /// The current version of the terms when the user has not accepted it yet.
+    /// The user cannot use the app until they accept it.
+    pub pending_terms_version: Option<String>,
+}
+
+/// Returns None when the user does not exist anymore.
+#[tracing::instrument(name = "queries::session::get_authenticated_user", skip_all, fields(
+    user_id = %user_id
+))]
+pub async fn handle(deps: &Deps, user_id: Uuid) -> Result<Option<AuthenticatedUser>> {
+    deps.repos
+        .users
+        .get_authenticated_user(&mut deps.db.read().await?, user_id)
+        .await
+}
@@ -0,0 +1,20 @@
+use crate::{domain::contracts::deps::Deps, infra::uuid::Uuid};
+use anyhow::Result;
+
+/// Returns true when the session has been revoked by logging out,
+/// logging out everywhere or by reusing a refresh token.
+#[tracing::instrument(name = "queries::session::is_revoked", skip_all, fields(
+    session_id = %session_id,
+    revoked
+))]
+pub async fn handle(deps: &Deps, session_id: Uuid) -> Result<bool> {
+    let revoked = deps
+        .repos
+        .refresh_tokens
+        .is_family_revoked(&mut deps.db.read().await?, session_id)
+        .await?;
+
+    tracing::Span::current().record("revoked", revoked);
+
+    Ok(revoked)
+}
@@ -0,0 +1,2 @@
+pub mod get_authenticated_user;
+pub mod is_revoked;
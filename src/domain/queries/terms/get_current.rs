@@ -0,0 +1,25 @@
+use crate::domain::contracts::{context::Context, deps::Deps};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug)]
+pub struct TermsVersion {
+    pub version: String,
+    pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetCurrentTermsError {
+    #[error("no terms have been published")]
+    NotFound,
+}
+
+/// The version users have to accept to use the app.
+#[tracing::instrument(name = "queries::terms::get_current", skip_all, fields(ctx = ?ctx))]
+pub async fn handle(deps: &Deps, ctx: &Context) -> Result<TermsVersion> {
+    deps.repos
+        .terms
+        .get_current(&mut deps.db.read().await?)
+        .await?
+        .ok_or_else(|| GetCurrentTermsError::NotFound.into())
+}
@@ -0,0 +1,35 @@
+use crate::{
+    domain::{
+        constants::TIMELINE_LIMIT,
+        contracts::{context::Context, deps::Deps},
+        queries::timeline::{get_timeline::PostPage, paywall},
+        value_objects::post_cursor::PostCursor,
+    },
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+
+/// Posts of the creators the viewer follows or subscribes to.
+/// `cursor` is None to get the first page.
+#[tracing::instrument(name = "queries::timeline::get_following_timeline", skip_all, fields(
+    ctx = ?ctx,
+    viewer_id = %viewer_id
+))]
+pub async fn handle(
+    deps: &Deps,
+    ctx: &Context,
+    viewer_id: Uuid,
+    cursor: Option<PostCursor>,
+) -> Result<PostPage> {
+    let mut executor = deps.db.read().await?;
+
+    let mut posts = deps
+        .repos
+        .timeline
+        .get_following_timeline(&mut executor, viewer_id, cursor, TIMELINE_LIMIT + 1)
+        .await?;
+
+    paywall::lock_posts(deps, &mut executor, Some(viewer_id), &mut posts).await?;
+
+    Ok(PostPage::new(posts, TIMELINE_LIMIT))
+}
@@ -1,7 +1,9 @@
 use crate::{
     domain::{
+        constants::TIMELINE_LIMIT,
         contracts::{context::Context, deps::Deps},
-        value_objects::cursor::Cursor,
+        queries::timeline::paywall,
+        value_objects::post_cursor::PostCursor,
     },
     infra::uuid::Uuid,
 };
@@ -12,21 +14,77 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, PartialOrd)]
 pub struct Post {
     pub id: Uuid,
+    pub creator_id: Uuid,
     pub creator_username: String,
     pub description: String,
-    pub video_url: String,
+    /// Key of the video in the videos bucket, which is private.
+    /// Viewers that can watch the post get a presigned url to it.
+    pub video_key: String,
     pub likes: i32,
     pub paid: bool,
+    pub preview: PostPreview,
+    /// Only the creator sees posts that are not published yet. None for drafts,
+    /// in the future for scheduled posts.
+    pub published_at: Option<DateTime<Utc>>,
+    pub pinned: bool,
+    /// True when the post is paid and the viewer cannot watch it.
+    pub locked: bool,
     pub created_at: DateTime<Utc>,
 }
 
-#[tracing::instrument(name = "commands::user::create::run", skip_all, fields(ctx = ?ctx))]
-pub async fn handle(deps: &Deps, ctx: &Context, cursor: Cursor) -> Result<Vec<Post>> {
-    let posts = deps
+/// What every viewer can see about a post, even when they cannot watch it.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, PartialOrd)]
+pub struct PostPreview {
+    pub thumbnail_url: Option<String>,
+    pub duration_secs: Option<i32>,
+}
+
+/// A page of posts, newest first.
+#[derive(Debug)]
+pub struct PostPage {
+    pub posts: Vec<Post>,
+    /// Where the next page starts, None when this is the last page.
+    pub next_cursor: Option<PostCursor>,
+}
+
+impl PostPage {
+    /// `posts` must have been fetched with one more post than `limit`,
+    /// the extra post only tells us there is a next page.
+    pub fn new(mut posts: Vec<Post>, limit: i64) -> Self {
+        let next_cursor = if posts.len() as i64 > limit {
+            posts.truncate(limit as usize);
+
+            posts.last().map(|post| PostCursor {
+                // Drafts are ordered by the time they were created.
+                published_at: post.published_at.unwrap_or(post.created_at),
+                id: post.id,
+            })
+        } else {
+            None
+        };
+
+        Self { posts, next_cursor }
+    }
+}
+
+/// `viewer_id` is None when the timeline is requested without logging in.
+/// `cursor` is None to get the first page.
+#[tracing::instrument(name = "queries::timeline::get_timeline", skip_all, fields(ctx = ?ctx))]
+pub async fn handle(
+    deps: &Deps,
+    ctx: &Context,
+    viewer_id: Option<Uuid>,
+    cursor: Option<PostCursor>,
+) -> Result<PostPage> {
+    let mut executor = deps.db.read().await?;
+
+    let mut posts = deps
         .repos
         .timeline
-        .get_timeline(&mut deps.db.read().await?, cursor)
+        .get_timeline(&mut executor, viewer_id, cursor, TIMELINE_LIMIT + 1)
         .await?;
 
-    Ok(posts)
+    paywall::lock_posts(deps, &mut executor, viewer_id, &mut posts).await?;
+
+    Ok(PostPage::new(posts, TIMELINE_LIMIT))
 }
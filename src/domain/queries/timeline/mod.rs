@@ -1 +1,3 @@
+pub mod get_following_timeline;
 pub mod get_timeline;
+pub mod paywall;
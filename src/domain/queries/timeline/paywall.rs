@@ -0,0 +1,63 @@
+use crate::{
+    domain::{
+        contracts::{deps::Deps, repository::Executor},
+        queries::timeline::get_timeline::Post,
+    },
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+
+/// The media of paid posts can only be accessed by the creator
+/// and by viewers with an active subscription to the creator.
+pub fn can_access_media(post: &Post, viewer_id: Option<Uuid>, subscribed_to: &[Uuid]) -> bool {
+    if !post.paid {
+        return true;
+    }
+
+    match viewer_id {
+        None => false,
+        Some(viewer_id) => viewer_id == post.creator_id || subscribed_to.contains(&post.creator_id),
+    }
+}
+
+/// Marks the posts the viewer cannot access as locked,
+/// they only get the preview of those.
+#[tracing::instrument(name = "queries::timeline::paywall::lock_posts", skip_all, fields(
+    viewer_id = ?viewer_id
+))]
+pub async fn lock_posts<'c>(
+    deps: &Deps,
+    executor: &mut Executor<'c>,
+    viewer_id: Option<Uuid>,
+    posts: &mut [Post],
+) -> Result<()> {
+    let subscribed_to = match viewer_id {
+        None => vec![],
+        Some(viewer_id) => {
+            let mut creator_ids: Vec<Uuid> = posts
+                .iter()
+                .filter(|post| post.paid)
+                .map(|post| post.creator_id)
+                .collect();
+            creator_ids.sort();
+            creator_ids.dedup();
+
+            if creator_ids.is_empty() {
+                vec![]
+            } else {
+                deps.repos
+                    .subscriptions
+                    .list_active_creator_ids(executor, viewer_id, &creator_ids)
+                    .await?
+            }
+        }
+    };
+
+    for post in posts.iter_mut() {
+        if !can_access_media(post, viewer_id, &subscribed_to) {
+            post.locked = true;
+        }
+    }
+
+    Ok(())
+}
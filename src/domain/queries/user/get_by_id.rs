@@ -1 +1,65 @@
+use crate::{
+    domain::{
+        contracts::{context::Context, deps::Deps},
+        value_objects::{creator_tag::CreatorTag, role::Role},
+    },
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
 
+/// Public information about a user.
+#[derive(Debug)]
+pub struct User {
+    pub id: Uuid,
+    pub username: String,
+    pub role: Role,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub links: Vec<String>,
+    pub avatar_url: Option<String>,
+    pub banner_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Deleted and banned users are not counted.
+    pub followers_count: i64,
+    pub following_count: i64,
+    pub tags: Vec<CreatorTag>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetUserError {
+    #[error("user not found")]
+    NotFound,
+}
+
+/// Users that have been deleted are not found, and neither are users
+/// that blocked or were blocked by the viewer.
+#[tracing::instrument(name = "queries::user::get_by_id", skip_all, fields(
+    ctx = ?ctx,
+    viewer_id = ?viewer_id,
+    user_id = %user_id
+))]
+pub async fn handle(
+    deps: &Deps,
+    ctx: &Context,
+    viewer_id: Option<Uuid>,
+    user_id: Uuid,
+) -> Result<User> {
+    let mut executor = deps.db.read().await?;
+
+    if let Some(viewer_id) = viewer_id {
+        if viewer_id != user_id
+            && deps
+                .repos
+                .user_blocks
+                .is_blocked_between(&mut executor, viewer_id, user_id)
+                .await?
+        {
+            return Err(GetUserError::NotFound.into());
+        }
+    }
+
+    let user = deps.repos.users.get_by_id(&mut executor, user_id).await?;
+
+    Ok(user.ok_or(GetUserError::NotFound)?)
+}
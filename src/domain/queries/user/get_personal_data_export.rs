@@ -0,0 +1,83 @@
+use crate::{
+    domain::contracts::{context::Context, deps::Deps},
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use tracing::info;
+
+#[derive(Debug)]
+pub enum PersonalDataExportStatus {
+    /// The archive has not been built yet.
+    Pending,
+    /// The archive can be downloaded from `download_url` until `expires_at`.
+    Ready {
+        download_url: String,
+        expires_at: DateTime<Utc>,
+        completed_at: DateTime<Utc>,
+    },
+}
+
+#[derive(Debug)]
+pub struct PersonalDataExport {
+    pub id: Uuid,
+    pub status: PersonalDataExportStatus,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetPersonalDataExportError {
+    #[error("personal data export not found")]
+    NotFound,
+}
+
+/// Archives are purged when they expire, so expired exports are not found.
+#[tracing::instrument(name = "queries::user::get_personal_data_export", skip_all, fields(
+    ctx = ?ctx,
+    user_id = %user_id,
+    export_id = %export_id
+))]
+pub async fn handle(
+    deps: &Deps,
+    ctx: &Context,
+    user_id: Uuid,
+    export_id: Uuid,
+) -> Result<PersonalDataExport> {
+    let export = match deps
+        .repos
+        .personal_data_exports
+        .get_by_id(&mut deps.db.read().await?, user_id, export_id)
+        .await?
+    {
+        None => return Err(GetPersonalDataExportError::NotFound.into()),
+        Some(export) => export,
+    };
+
+    let status = match (export.object_key, export.expires_at, export.completed_at) {
+        (Some(key), Some(expires_at), Some(completed_at)) => {
+            if expires_at <= Utc::now() {
+                info!("personal data export expired");
+                return Err(GetPersonalDataExportError::NotFound.into());
+            }
+
+            let download_url = deps.object_storage.get_presigned_get_url(
+                &deps.config.s3.videos_bucket,
+                &key,
+                (expires_at - Utc::now()).to_std()?,
+            )?;
+
+            PersonalDataExportStatus::Ready {
+                download_url,
+                expires_at,
+                completed_at,
+            }
+        }
+        _ => PersonalDataExportStatus::Pending,
+    };
+
+    Ok(PersonalDataExport {
+        id: export.id,
+        status,
+        created_at: export.created_at,
+    })
+}
@@ -0,0 +1,2 @@
+pub mod get_by_id;
+pub mod get_personal_data_export;
@@ -0,0 +1,45 @@
+use std::{fmt::Display, str::FromStr};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ApiKeyScopeError {
+    #[error("unknown api key scope: {0:?}")]
+    UnknownScope(String),
+}
+
+/// Limits what an api key can be used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiKeyScope {
+    /// Allows the key to be used to upload videos.
+    VideosUpload,
+}
+
+impl ApiKeyScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiKeyScope::VideosUpload => "videos:upload",
+        }
+    }
+
+    /// Keys created without scopes can be used for every endpoint that accepts api keys.
+    pub fn is_allowed_by(&self, scopes: &[ApiKeyScope]) -> bool {
+        scopes.is_empty() || scopes.contains(self)
+    }
+}
+
+impl Display for ApiKeyScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for ApiKeyScope {
+    type Err = ApiKeyScopeError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "videos:upload" => Ok(ApiKeyScope::VideosUpload),
+            _ => Err(ApiKeyScopeError::UnknownScope(input.to_owned())),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use std::{fmt::Display, str::FromStr};
+use thiserror::Error;
+use unicode_normalization::UnicodeNormalization;
+
+#[derive(Debug, Clone, Error)]
+pub enum CreatorTagError {
+    #[error("unknown tag: {0:?}, tags must be one of: fitness, cooking, music")]
+    Unknown(String),
+}
+
+/// Categories creators can tag themselves with so viewers can discover them.
+/// The list is curated, new tags are added here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CreatorTag {
+    Fitness,
+    Cooking,
+    Music,
+}
+
+impl CreatorTag {
+    pub const ALL: [CreatorTag; 3] = [CreatorTag::Fitness, CreatorTag::Cooking, CreatorTag::Music];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CreatorTag::Fitness => "fitness",
+            CreatorTag::Cooking => "cooking",
+            CreatorTag::Music => "music",
+        }
+    }
+}
+
+impl Display for CreatorTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Accepts the tag in any case, with surrounding spaces and with
+/// full width characters, so "  Fitness" and "ＦＩＴＮＥＳＳ" are the same tag.
+impl FromStr for CreatorTag {
+    type Err = CreatorTagError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let normalized = input.nfkc().collect::<String>().trim().to_lowercase();
+
+        CreatorTag::ALL
+            .into_iter()
+            .find(|tag| tag.as_str() == normalized)
+            .ok_or_else(|| CreatorTagError::Unknown(input.to_owned()))
+    }
+}
@@ -1,3 +1,12 @@
+pub mod api_key_scope;
+pub mod creator_tag;
 pub mod email;
 pub mod password;
 pub mod cursor;
+pub mod opaque_token;
+pub mod post_cursor;
+pub mod profile_image;
+pub mod role;
+pub mod recovery_code;
+pub mod totp;
+pub mod username;
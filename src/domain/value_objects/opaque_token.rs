@@ -0,0 +1,38 @@
+use std::fmt::Debug;
+
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+/// Random secret handed to the client. Only its hash should be stored
+/// so a leaked database does not leak usable tokens.
+pub struct OpaqueToken(String);
+
+impl OpaqueToken {
+    /// Generates a token with 256 bits of randomness.
+    pub fn generate() -> Self {
+        let mut bytes = [0_u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        Self(hex::encode(bytes))
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the value that should be stored and used to look the token up.
+    pub fn hash(&self) -> String {
+        hex::encode(Sha256::digest(self.0.as_bytes()))
+    }
+}
+
+impl From<String> for OpaqueToken {
+    fn from(input: String) -> Self {
+        Self(input)
+    }
+}
+
+impl Debug for OpaqueToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "OpaqueToken(<redacted>)")
+    }
+}
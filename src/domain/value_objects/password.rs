@@ -1,28 +1,165 @@
 use thiserror::Error;
 
-#[derive(Debug, Error)]
+/// bcrypt ignores everything after the first 72 bytes.
+pub const MAX_PASSWORD_BYTES: usize = 72;
+
+pub const MIN_PASSWORD_CHARS: usize = 8;
+
+/// Usernames and emails shorter than this are not checked against the password
+/// because they would match too many passwords by accident.
+const MIN_PERSONAL_INFO_CHARS: usize = 3;
+
+const COMMON_PASSWORDS: &str = include_str!("common_passwords.txt");
+
+#[derive(Debug, Clone, Error)]
 pub enum PasswordError {
+    #[error("the password must have at least {MIN_PASSWORD_CHARS} characters")]
+    TooShort,
+    #[error("the password must have at most {MAX_PASSWORD_BYTES} bytes")]
+    TooLong,
+    #[error("the password must not contain the username")]
+    ContainsUsername,
+    #[error("the password must not contain the email")]
+    ContainsEmail,
+    #[error("the password is too common")]
+    TooCommon,
     #[error("the password is invalid")]
-    InvalidPassword
+    InvalidPassword,
 }
 
 pub struct Password(String);
 
 impl Password {
+    /// Checks that `plain` follows the password policy and hashes it.
+    /// `username` and `email` belong to the user that's choosing the password.
+    pub fn new(plain: &str, username: &str, email: &str) -> Result<Self, PasswordError> {
+        if plain.chars().count() < MIN_PASSWORD_CHARS {
+            return Err(PasswordError::TooShort);
+        }
+
+        if plain.len() > MAX_PASSWORD_BYTES {
+            return Err(PasswordError::TooLong);
+        }
+
+        let lowercase = plain.to_lowercase();
+
+        if contains(&lowercase, username) {
+            return Err(PasswordError::ContainsUsername);
+        }
+
+        let email_local_part = email.split('@').next().unwrap_or_default();
+
+        if contains(&lowercase, email) || contains(&lowercase, email_local_part) {
+            return Err(PasswordError::ContainsEmail);
+        }
+
+        if COMMON_PASSWORDS.lines().any(|common| common == lowercase) {
+            return Err(PasswordError::TooCommon);
+        }
+
+        let hash = match bcrypt::hash(plain, bcrypt::DEFAULT_COST) {
+            Ok(hash) => hash,
+            Err(_) => return Err(PasswordError::InvalidPassword),
+        };
+
+        Ok(Self(hash))
+    }
+
     pub fn expose(&self) -> String {
         self.0.to_owned()
     }
+
+    /// Wraps a bcrypt hash that has already been stored.
+    pub fn from_hash(hash: String) -> Self {
+        Self(hash)
+    }
+
+    /// Returns true when `candidate` is the plain text password that generated the hash.
+    pub fn verify(&self, candidate: &str) -> bool {
+        bcrypt::verify(candidate, &self.0).unwrap_or(false)
+    }
 }
 
-impl<'a> TryFrom<&'a str> for Password {
-    type Error = PasswordError;
+/// `lowercase_password` must already be in lowercase.
+fn contains(lowercase_password: &str, personal_info: &str) -> bool {
+    let personal_info = personal_info.trim().to_lowercase();
 
-    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-        let hash = match bcrypt::hash(value, bcrypt::DEFAULT_COST) {
-            Ok(hash) => hash,
-            Err(_) => return Err(PasswordError::InvalidPassword)
-        };
+    personal_info.chars().count() >= MIN_PERSONAL_INFO_CHARS
+        && lowercase_password.contains(&personal_info)
+}
 
-        Ok(Self(hash))
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USERNAME: &str = "john_doe";
+
+    const EMAIL: &str = "jdoe@example.com";
+
+    #[test]
+    fn rejects_passwords_that_are_too_short() {
+        // Characters are counted, not bytes.
+        for plain in ["Tr0ub4d", "ŧŗøűƀąđ"] {
+            assert!(
+                matches!(
+                    Password::new(plain, USERNAME, EMAIL),
+                    Err(PasswordError::TooShort)
+                ),
+                "{plain}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_passwords_longer_than_bcrypt_accepts() {
+        // The second one has fewer than 72 characters but more than 72 bytes.
+        for plain in ["x".repeat(MAX_PASSWORD_BYTES + 1), "ŧ".repeat(37)] {
+            assert!(
+                matches!(
+                    Password::new(&plain, USERNAME, EMAIL),
+                    Err(PasswordError::TooLong)
+                ),
+                "{plain}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_passwords_that_contain_the_username() {
+        assert!(matches!(
+            Password::new("correct-JOHN_DOE-horse", USERNAME, EMAIL),
+            Err(PasswordError::ContainsUsername)
+        ));
+    }
+
+    #[test]
+    fn rejects_passwords_that_contain_the_email() {
+        // The local part of the email is checked on its own as well.
+        for plain in ["my jdoe@example.com horse", "correct-JDoe-horse"] {
+            assert!(
+                matches!(
+                    Password::new(plain, USERNAME, EMAIL),
+                    Err(PasswordError::ContainsEmail)
+                ),
+                "{plain}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_common_passwords() {
+        assert!(matches!(
+            Password::new("Password123", USERNAME, EMAIL),
+            Err(PasswordError::TooCommon)
+        ));
+    }
+
+    #[test]
+    fn hashes_passwords_that_follow_the_policy() {
+        // Usernames too short to be checked do not count.
+        let password = Password::new("correct-jo-horse", "jo", EMAIL).unwrap();
+
+        assert!(password.verify("correct-jo-horse"));
+        assert!(!password.verify("correct-horse"));
     }
 }
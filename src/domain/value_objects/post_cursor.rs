@@ -0,0 +1,182 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, TimeZone, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use thiserror::Error;
+
+use crate::infra::uuid::Uuid;
+
+/// Bytes of the timestamp followed by the bytes of the id.
+const PAYLOAD_LEN: usize = 8 + 16;
+
+const SIGNATURE_LEN: usize = 32;
+
+#[derive(Debug, Clone, Error)]
+pub enum PostCursorError {
+    #[error("the cursor is invalid")]
+    Invalid,
+}
+
+/// Position of the last post of a page. Posts are ordered by `published_at`
+/// and then by `id`, newest first, so the next page starts right after it
+/// even when new posts are published in the meantime. Drafts, which only their
+/// creator sees, are ordered by the time they were created.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostCursor {
+    pub published_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl PostCursor {
+    /// Returns the base64 token handed to clients. It is signed with `secret`
+    /// so clients cannot craft cursors, they can only pass back the ones we issued.
+    pub fn encode(&self, secret: &str) -> String {
+        let mut bytes = self.payload().to_vec();
+        bytes.extend_from_slice(&mac(secret, &bytes).finalize().into_bytes());
+
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    /// Decodes a token returned by `encode`. Tokens that were modified
+    /// or signed with another secret are rejected.
+    pub fn decode(token: &str, secret: &str) -> Result<Self, PostCursorError> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(token)
+            .map_err(|_| PostCursorError::Invalid)?;
+
+        if bytes.len() != PAYLOAD_LEN + SIGNATURE_LEN {
+            return Err(PostCursorError::Invalid);
+        }
+
+        let (payload, signature) = bytes.split_at(PAYLOAD_LEN);
+
+        mac(secret, payload)
+            .verify_slice(signature)
+            .map_err(|_| PostCursorError::Invalid)?;
+
+        let (micros, id) = payload.split_at(8);
+        let micros = i64::from_be_bytes(micros.try_into().map_err(|_| PostCursorError::Invalid)?);
+
+        let published_at = Utc
+            .timestamp_opt(
+                micros.div_euclid(1_000_000),
+                (micros.rem_euclid(1_000_000) * 1_000) as u32,
+            )
+            .single()
+            .ok_or(PostCursorError::Invalid)?;
+
+        Ok(Self {
+            published_at,
+            id: Uuid::from_slice(id).map_err(|_| PostCursorError::Invalid)?,
+        })
+    }
+
+    /// Postgres stores timestamps with microsecond precision,
+    /// so nothing is lost by dropping the nanoseconds.
+    fn payload(&self) -> [u8; PAYLOAD_LEN] {
+        let mut payload = [0_u8; PAYLOAD_LEN];
+        payload[..8].copy_from_slice(&self.published_at.timestamp_micros().to_be_bytes());
+        payload[8..].copy_from_slice(self.id.as_bytes());
+        payload
+    }
+}
+
+fn mac(secret: &str, payload: &[u8]) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("hmac accepts keys of any size");
+    mac.update(payload);
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "cursor-secret";
+
+    fn cursor(secs: i64, micros: u32) -> PostCursor {
+        PostCursor {
+            published_at: Utc.timestamp_opt(secs, micros * 1_000).unwrap(),
+            id: Uuid::new_v4(),
+        }
+    }
+
+    fn is_invalid(result: Result<PostCursor, PostCursorError>) -> bool {
+        matches!(result, Err(PostCursorError::Invalid))
+    }
+
+    #[test]
+    fn decodes_what_it_encodes() {
+        // Timestamps before the epoch have negative micros.
+        for cursor in [cursor(1_680_000_000, 123_456), cursor(-1, 999_999)] {
+            let token = cursor.encode(SECRET);
+
+            assert_eq!(PostCursor::decode(&token, SECRET).unwrap(), cursor);
+        }
+    }
+
+    #[test]
+    fn rejects_tampered_tokens() {
+        let token = cursor(1_680_000_000, 0).encode(SECRET);
+
+        let bytes = URL_SAFE_NO_PAD.decode(&token).unwrap();
+
+        // Flipping a byte of the payload or of the signature breaks the signature.
+        for index in [0, PAYLOAD_LEN - 1, PAYLOAD_LEN, bytes.len() - 1] {
+            let mut tampered = bytes.clone();
+            tampered[index] ^= 1;
+
+            assert!(
+                is_invalid(PostCursor::decode(
+                    &URL_SAFE_NO_PAD.encode(tampered),
+                    SECRET
+                )),
+                "{index}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_tokens_signed_with_another_secret() {
+        let token = cursor(1_680_000_000, 0).encode("another-secret");
+
+        assert!(is_invalid(PostCursor::decode(&token, SECRET)));
+    }
+
+    #[test]
+    fn rejects_tokens_with_the_wrong_length() {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(cursor(1_680_000_000, 0).encode(SECRET))
+            .unwrap();
+
+        let mut longer = bytes.clone();
+        longer.push(0);
+
+        for bytes in [
+            Vec::new(),
+            bytes[..PAYLOAD_LEN].to_vec(),
+            bytes[..bytes.len() - 1].to_vec(),
+            longer,
+        ] {
+            assert!(
+                is_invalid(PostCursor::decode(&URL_SAFE_NO_PAD.encode(&bytes), SECRET)),
+                "{}",
+                bytes.len()
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_tokens_that_are_not_base64() {
+        let token = cursor(1_680_000_000, 0).encode(SECRET);
+
+        // The engine is url safe and does not accept padding.
+        for token in [
+            format!("{token}="),
+            format!("+{}", &token[1..]),
+            "not a cursor!".to_owned(),
+        ] {
+            assert!(is_invalid(PostCursor::decode(&token, SECRET)), "{token}");
+        }
+    }
+}
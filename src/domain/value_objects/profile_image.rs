@@ -0,0 +1,94 @@
+use std::{fmt::Display, str::FromStr};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ProfileImageError {
+    #[error("unknown profile image kind: {0:?}")]
+    UnknownKind(String),
+    #[error("images must be png, jpeg or webp")]
+    UnsupportedContentType(String),
+}
+
+/// Where the image is shown in the profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileImageKind {
+    Avatar,
+    Banner,
+}
+
+impl ProfileImageKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProfileImageKind::Avatar => "avatar",
+            ProfileImageKind::Banner => "banner",
+        }
+    }
+}
+
+impl Display for ProfileImageKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for ProfileImageKind {
+    type Err = ProfileImageError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "avatar" => Ok(ProfileImageKind::Avatar),
+            "banner" => Ok(ProfileImageKind::Banner),
+            _ => Err(ProfileImageError::UnknownKind(input.to_owned())),
+        }
+    }
+}
+
+/// Image formats accepted for profile images.
+/// Formats that can contain scripts, such as svg, are not accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageContentType {
+    Png,
+    Jpeg,
+    Webp,
+}
+
+impl ImageContentType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImageContentType::Png => "image/png",
+            ImageContentType::Jpeg => "image/jpeg",
+            ImageContentType::Webp => "image/webp",
+        }
+    }
+
+    /// Checks the first bytes of the file, the content type sent
+    /// by the client cannot be trusted.
+    pub fn matches(&self, bytes: &[u8]) -> bool {
+        match self {
+            ImageContentType::Png => bytes.starts_with(b"\x89PNG\r\n\x1a\n"),
+            ImageContentType::Jpeg => bytes.starts_with(&[0xff, 0xd8, 0xff]),
+            ImageContentType::Webp => {
+                bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP"
+            }
+        }
+    }
+}
+
+impl Display for ImageContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for ImageContentType {
+    type Err = ProfileImageError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.to_ascii_lowercase().as_str() {
+            "image/png" => Ok(ImageContentType::Png),
+            "image/jpeg" | "image/jpg" => Ok(ImageContentType::Jpeg),
+            "image/webp" => Ok(ImageContentType::Webp),
+            _ => Err(ProfileImageError::UnsupportedContentType(input.to_owned())),
+        }
+    }
+}
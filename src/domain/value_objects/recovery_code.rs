@@ -0,0 +1,59 @@
+use std::fmt::Debug;
+
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+/// Characters that are hard to mix up when the code is typed by hand.
+const ALPHABET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+
+/// Number of characters in each half of the code.
+const GROUP_LEN: usize = 5;
+
+/// Single use code that can be used instead of a totp code when
+/// the user loses access to their authenticator app.
+/// Only its hash should be stored.
+pub struct RecoveryCode(String);
+
+impl RecoveryCode {
+    /// Generates a code in the format `xxxxx-xxxxx`.
+    pub fn generate() -> Self {
+        let mut rng = rand::thread_rng();
+
+        let mut group = || -> String {
+            (0..GROUP_LEN)
+                .map(|_| ALPHABET[rng.gen_range(0..ALPHABET.len())] as char)
+                .collect()
+        };
+
+        Self(format!("{}-{}", group(), group()))
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the value that should be stored and used to look the code up.
+    /// Casing, spaces and dashes do not matter.
+    pub fn hash(&self) -> String {
+        let normalized: String = self
+            .0
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+
+        hex::encode(Sha256::digest(normalized.as_bytes()))
+    }
+}
+
+impl From<String> for RecoveryCode {
+    fn from(input: String) -> Self {
+        Self(input)
+    }
+}
+
+impl Debug for RecoveryCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RecoveryCode(<redacted>)")
+    }
+}
@@ -0,0 +1,48 @@
+use std::{fmt::Display, str::FromStr};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RoleError {
+    #[error("unknown role: {0:?}")]
+    UnknownRole(String),
+}
+
+/// What a user is allowed to do in the platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Watches content from creators.
+    Viewer,
+    /// Publishes content.
+    Creator,
+    /// Manages the platform.
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Viewer => "viewer",
+            Role::Creator => "creator",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Role {
+    type Err = RoleError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "viewer" => Ok(Role::Viewer),
+            "creator" => Ok(Role::Creator),
+            "admin" => Ok(Role::Admin),
+            _ => Err(RoleError::UnknownRole(input.to_owned())),
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt::Debug;
+
+use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha1::Sha1;
+use thiserror::Error;
+
+/// Number of seconds each code is valid for. Authenticator apps use 30 by default.
+pub const TOTP_STEP_SECS: i64 = 30;
+
+const TOTP_DIGITS: usize = 6;
+
+/// Codes from the steps right before and after the current one are accepted
+/// to tolerate clocks that are a little out of sync.
+const TOTP_ALLOWED_DRIFT_STEPS: i64 = 1;
+
+/// RFC 4226 recommends secrets with at least 160 bits.
+const SECRET_BYTES: usize = 20;
+
+#[derive(Debug, Error)]
+pub enum TotpError {
+    #[error("totp secret is not valid base32")]
+    InvalidSecret,
+}
+
+/// Secret shared with the authenticator app of the user.
+/// Used to generate time based one time passwords as described in RFC 6238.
+pub struct TotpSecret(Vec<u8>);
+
+impl TotpSecret {
+    pub fn generate() -> Self {
+        let mut bytes = vec![0_u8; SECRET_BYTES];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        Self(bytes)
+    }
+
+    pub fn from_base32(value: &str) -> Result<Self, TotpError> {
+        BASE32_NOPAD
+            .decode(value.as_bytes())
+            .map(Self)
+            .map_err(|_| TotpError::InvalidSecret)
+    }
+
+    /// The format authenticator apps expect the secret to be in.
+    pub fn to_base32(&self) -> String {
+        BASE32_NOPAD.encode(&self.0)
+    }
+
+    /// Returns the uri that authenticator apps use to import the secret,
+    /// usually by scanning a qr code that contains it.
+    pub fn otpauth_uri(&self, issuer: &str, account: &str) -> String {
+        format!(
+            "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={TOTP_DIGITS}&period={TOTP_STEP_SECS}",
+            percent_encode(issuer),
+            percent_encode(account),
+            self.to_base32(),
+            percent_encode(issuer),
+        )
+    }
+
+    /// Returns the code that is valid at `unix_timestamp`.
+    #[cfg(test)]
+    pub fn code_at(&self, unix_timestamp: i64) -> String {
+        self.code_for_step(unix_timestamp.div_euclid(TOTP_STEP_SECS))
+    }
+
+    /// Returns the step the code belongs to when `code` is valid at `unix_timestamp`.
+    /// The step can be stored to stop the same code from being used twice.
+    pub fn verify(&self, code: &str, unix_timestamp: i64) -> Option<i64> {
+        let code = code.trim();
+
+        if code.len() != TOTP_DIGITS {
+            return None;
+        }
+
+        let current_step = unix_timestamp.div_euclid(TOTP_STEP_SECS);
+
+        (current_step - TOTP_ALLOWED_DRIFT_STEPS..=current_step + TOTP_ALLOWED_DRIFT_STEPS)
+            .find(|step| self.code_for_step(*step) == code)
+    }
+
+    fn code_for_step(&self, step: i64) -> String {
+        let mut mac = Hmac::<Sha1>::new_from_slice(&self.0).expect("hmac accepts keys of any size");
+        mac.update(&(step as u64).to_be_bytes());
+        let hash = mac.finalize().into_bytes();
+
+        // Dynamic truncation as described in RFC 4226.
+        let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+        let binary = u32::from_be_bytes([
+            hash[offset],
+            hash[offset + 1],
+            hash[offset + 2],
+            hash[offset + 3],
+        ]) & 0x7fff_ffff;
+
+        format!(
+            "{:0width$}",
+            binary % 10_u32.pow(TOTP_DIGITS as u32),
+            width = TOTP_DIGITS
+        )
+    }
+}
+
+impl Debug for TotpSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TotpSecret(<redacted>)")
+    }
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| {
+            if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+                (byte as char).to_string()
+            } else {
+                format!("%{byte:02X}")
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,202 @@
+use std::{
+    fmt::{Debug, Display},
+    ops::Deref,
+};
+use thiserror::Error;
+use unicode_normalization::UnicodeNormalization;
+
+pub const MIN_USERNAME_CHARS: usize = 3;
+
+pub const MAX_USERNAME_CHARS: usize = 30;
+
+/// Usernames that could be mistaken for the staff or for a page of the app.
+/// Compared against the normalized username.
+const RESERVED_USERNAMES: [&str; 22] = [
+    "admin",
+    "administrator",
+    "api",
+    "creators",
+    "explore",
+    "help",
+    "images",
+    "login",
+    "logout",
+    "me",
+    "moderator",
+    "payments",
+    "posts",
+    "root",
+    "sessions",
+    "settings",
+    "staff",
+    "support",
+    "system",
+    "timeline",
+    "users",
+    "videos",
+];
+
+#[derive(Debug, Clone, Error)]
+pub enum UsernameError {
+    #[error(
+        "the username must have between {MIN_USERNAME_CHARS} and {MAX_USERNAME_CHARS} characters"
+    )]
+    InvalidLength,
+    #[error("the username can only contain letters, numbers, dots and underscores, and must start with a letter or a number")]
+    InvalidCharacters,
+    #[error("the username is reserved")]
+    Reserved,
+}
+
+/// A username as the user typed it, with full width and other
+/// compatibility characters replaced by their ascii equivalents.
+///
+/// Two usernames are the same user when their `normalized` forms are equal,
+/// so "Admin", "admin " and "ＡＤＭＩＮ" cannot be registered by different users.
+#[derive(Clone)]
+pub struct Username {
+    display: String,
+    normalized: String,
+}
+
+impl Username {
+    /// The username shown in the app.
+    pub fn expose(&self) -> &str {
+        &self.display
+    }
+
+    /// The NFKC case folded username, used to check for uniqueness.
+    pub fn normalized(&self) -> &str {
+        &self.normalized
+    }
+}
+
+impl Deref for Username {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.display
+    }
+}
+
+impl Debug for Username {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display)
+    }
+}
+
+impl Display for Username {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display)
+    }
+}
+
+impl PartialEq for Username {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized == other.normalized
+    }
+}
+
+impl Eq for Username {}
+
+impl TryFrom<&str> for Username {
+    type Error = UsernameError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let display = input.nfkc().collect::<String>().trim().to_owned();
+
+        let length = display.chars().count();
+
+        if !(MIN_USERNAME_CHARS..=MAX_USERNAME_CHARS).contains(&length) {
+            return Err(UsernameError::InvalidLength);
+        }
+
+        // Only ascii is accepted after the normalization so usernames cannot
+        // impersonate others with characters that look the same, such as the
+        // cyrillic "а". Case folding ascii is the same as lowercasing it.
+        let valid_char = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '.';
+
+        if !display.chars().all(valid_char)
+            || !display.starts_with(|c: char| c.is_ascii_alphanumeric())
+        {
+            return Err(UsernameError::InvalidCharacters);
+        }
+
+        let normalized = display.to_ascii_lowercase();
+
+        if RESERVED_USERNAMES.contains(&normalized.as_str()) {
+            return Err(UsernameError::Reserved);
+        }
+
+        Ok(Username {
+            display,
+            normalized,
+        })
+    }
+}
+
+impl TryFrom<String> for Username {
+    type Error = UsernameError;
+
+    fn try_from(input: String) -> Result<Self, Self::Error> {
+        Username::try_from(input.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folds_compatibility_characters_and_case() {
+        let username = Username::try_from(" Ｊｏｈｎ_Doe ").unwrap();
+
+        assert_eq!(username.expose(), "John_Doe");
+        assert_eq!(username.normalized(), "john_doe");
+        assert_eq!(username, Username::try_from("JOHN_DOE").unwrap());
+    }
+
+    #[test]
+    fn rejects_reserved_usernames() {
+        for input in ["admin", "Admin", "ＡＤＭＩＮ", "Root", "support"] {
+            assert!(
+                matches!(Username::try_from(input), Err(UsernameError::Reserved)),
+                "{input}"
+            );
+        }
+
+        assert!(Username::try_from("admin_john").is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_characters() {
+        // The first one has a cyrillic "а".
+        for input in ["jаne", "john doe", "john-doe", "_john", ".john", "joão"] {
+            assert!(
+                matches!(
+                    Username::try_from(input),
+                    Err(UsernameError::InvalidCharacters)
+                ),
+                "{input}"
+            );
+        }
+
+        assert!(Username::try_from("john.doe_2").is_ok());
+    }
+
+    #[test]
+    fn rejects_usernames_that_are_too_short_or_too_long() {
+        let too_short = "a".repeat(MIN_USERNAME_CHARS - 1);
+        let too_long = "a".repeat(MAX_USERNAME_CHARS + 1);
+
+        for input in [too_short.as_str(), too_long.as_str(), "  ab  "] {
+            assert!(
+                matches!(Username::try_from(input), Err(UsernameError::InvalidLength)),
+                "{input}"
+            );
+        }
+
+        assert!(Username::try_from("a".repeat(MIN_USERNAME_CHARS)).is_ok());
+        assert!(Username::try_from("a".repeat(MAX_USERNAME_CHARS)).is_ok());
+    }
+}
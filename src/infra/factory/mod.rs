@@ -1,2 +1,4 @@
 pub mod post;
+pub mod subscription;
+pub mod terms;
 pub mod user;
@@ -1,43 +1,96 @@
 use crate::domain::contracts::repository::{Executor, SqlxExt};
-use crate::domain::queries::timeline::get_timeline::Post;
+use crate::domain::queries::timeline::get_timeline::{Post, PostPreview};
 use crate::infra::uuid::Uuid;
 use anyhow::Result;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use fake::{Dummy, Fake, Faker};
 
 impl Dummy<Faker> for Post {
     fn dummy_with_rng<R: rand::Rng + ?Sized>(_config: &Faker, _rng: &mut R) -> Self {
         Self {
             id: Uuid::new_v4(),
+            creator_id: Uuid::new_v4(),
             description: Faker.fake(),
             likes: Faker.fake(),
             creator_username: Faker.fake(),
-            video_url: Faker.fake(),
+            video_key: Uuid::new_v4().to_string(),
             paid: Faker.fake(),
+            preview: PostPreview {
+                thumbnail_url: Some(Faker.fake()),
+                duration_secs: Some((1..600).fake()),
+            },
+            published_at: Some(Utc::now()),
+            pinned: false,
+            locked: false,
             created_at: Utc::now(),
         }
     }
 }
 
 #[allow(dead_code)]
-pub async fn create_for_user<'c>(user_id: Uuid, executor: &mut Executor<'c>) -> Result<()> {
+pub async fn create_for_user<'c>(user_id: Uuid, executor: &mut Executor<'c>) -> Result<Uuid> {
+    create_with_paid(user_id, Faker.fake(), executor).await
+}
+
+#[allow(dead_code)]
+pub async fn create_with_paid<'c>(
+    user_id: Uuid,
+    paid: bool,
+    executor: &mut Executor<'c>,
+) -> Result<Uuid> {
+    let id = Uuid::new_v4();
+
     sqlx::query!(
         "INSERT INTO posts (
             id,
             creator_id,
             description,
-            video_url,
+            video_key,
             likes,
-            paid
+            paid,
+            thumbnail_url,
+            duration_secs
         ) VALUES (
-            $1, $2, $3, $4, $5, $6
+            $1, $2, $3, $4, $5, $6, $7, $8
         )",
-        &Uuid::new_v4(),
+        &id,
         &user_id,
         &Faker.fake::<String>(),
-        &Faker.fake::<String>(),
+        &Uuid::new_v4().to_string(),
         &Faker.fake::<i32>(),
-        &Faker.fake::<bool>(),
+        paid,
+        &Faker.fake::<String>(),
+        (1..600).fake::<i32>(),
+    )
+    .execute_ex(executor)
+    .await?;
+
+    Ok(id)
+}
+
+#[allow(dead_code)]
+pub async fn pin<'c>(post_id: Uuid, executor: &mut Executor<'c>) -> Result<()> {
+    sqlx::query!(
+        "UPDATE posts SET pinned_at = CURRENT_TIMESTAMP WHERE id = $1",
+        &post_id
+    )
+    .execute_ex(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// None turns the post into a draft, a date in the future schedules it.
+#[allow(dead_code)]
+pub async fn set_published_at<'c>(
+    post_id: Uuid,
+    published_at: Option<DateTime<Utc>>,
+    executor: &mut Executor<'c>,
+) -> Result<()> {
+    sqlx::query!(
+        "UPDATE posts SET published_at = $2 WHERE id = $1",
+        &post_id,
+        published_at
     )
     .execute_ex(executor)
     .await?;
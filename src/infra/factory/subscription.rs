@@ -0,0 +1,31 @@
+use crate::domain::contracts::repository::{Executor, SqlxExt};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+
+/// Creates a subscription that is active for a month.
+#[allow(dead_code)]
+pub async fn create<'c>(
+    executor: &mut Executor<'c>,
+    subscriber_id: Uuid,
+    creator_id: Uuid,
+) -> Result<Uuid> {
+    let id = Uuid::new_v4();
+
+    sqlx::query!(
+        "INSERT INTO subscriptions (
+            id,
+            subscriber_id,
+            creator_id,
+            expires_at
+        ) VALUES (
+            $1, $2, $3, CURRENT_TIMESTAMP + INTERVAL '30 days'
+        )",
+        &id,
+        &subscriber_id,
+        &creator_id,
+    )
+    .execute_ex(executor)
+    .await?;
+
+    Ok(id)
+}
@@ -0,0 +1,17 @@
+use crate::domain::contracts::repository::{Executor, SqlxExt};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+
+/// The version published by the migrations. Tests do not publish other versions.
+#[allow(dead_code)]
+pub const CURRENT_VERSION: &str = "2023-01-11";
+
+/// Forgets every acceptance of the user, as if a new version had been published.
+#[allow(dead_code)]
+pub async fn remove_acceptances<'c>(executor: &mut Executor<'c>, user_id: Uuid) -> Result<()> {
+    sqlx::query!("DELETE FROM terms_acceptances WHERE user_id = $1", &user_id)
+        .execute_ex(executor)
+        .await?;
+
+    Ok(())
+}
@@ -1,28 +1,124 @@
 use crate::domain::contracts::repository::{Executor, SqlxExt};
+use crate::domain::value_objects::role::Role;
 use crate::infra::uuid::Uuid;
 use anyhow::Result;
 use fake::faker::internet::en::FreeEmail;
 use fake::{Fake, Faker};
+use sqlx::Row;
 
 #[allow(dead_code)]
 pub async fn create<'c>(executor: &mut Executor<'c>) -> Result<Uuid> {
+    create_with_role(executor, Role::Viewer).await
+}
+
+#[allow(dead_code)]
+pub async fn create_with_role<'c>(executor: &mut Executor<'c>, role: Role) -> Result<Uuid> {
+    let id = insert(executor, role).await?;
+
+    sqlx::query!(
+        "UPDATE users SET email_verified_at = CURRENT_TIMESTAMP WHERE id = $1",
+        &id
+    )
+    .execute_ex(executor)
+    .await?;
+
+    Ok(id)
+}
+
+/// Creates a verified user that can log in with `password`.
+/// Returns the id and the email of the user.
+#[allow(dead_code)]
+pub async fn create_with_password<'c>(
+    executor: &mut Executor<'c>,
+    role: Role,
+    password: &str,
+) -> Result<(Uuid, String)> {
+    let id = create_with_role(executor, role).await?;
+
+    let row = sqlx::query!(
+        "UPDATE users SET password = $2 WHERE id = $1 RETURNING email",
+        &id,
+        bcrypt::hash(password, bcrypt::DEFAULT_COST)?
+    )
+    .fetch_one_ex(executor)
+    .await?;
+
+    Ok((id, row.try_get("email")?))
+}
+
+/// Creates a user that has not verified their email yet.
+#[allow(dead_code)]
+pub async fn create_unverified<'c>(executor: &mut Executor<'c>) -> Result<Uuid> {
+    insert(executor, Role::Viewer).await
+}
+
+#[allow(dead_code)]
+pub async fn soft_delete<'c>(executor: &mut Executor<'c>, id: Uuid) -> Result<()> {
+    sqlx::query!(
+        "UPDATE users SET deleted_at = CURRENT_TIMESTAMP WHERE id = $1",
+        &id
+    )
+    .execute_ex(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Turns on two-factor authentication without a totp secret,
+/// for tests that only care about whether it is enabled.
+#[allow(dead_code)]
+pub async fn enable_mfa<'c>(executor: &mut Executor<'c>, id: Uuid) -> Result<()> {
+    sqlx::query!(
+        "UPDATE users SET mfa_enabled_at = CURRENT_TIMESTAMP WHERE id = $1",
+        &id
+    )
+    .execute_ex(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// A random username that follows the username rules.
+#[allow(dead_code)]
+pub fn username() -> String {
+    format!("user_{:08x}", rand::random::<u32>())
+}
+
+async fn insert<'c>(executor: &mut Executor<'c>, role: Role) -> Result<Uuid> {
     let id = Uuid::new_v4();
 
+    let username = username();
+
     sqlx::query!(
         "INSERT INTO users (
             id,
             username,
+            username_normalized,
             email,
             password,
-            accepted_terms_at
+            accepted_terms_at,
+            role
         ) VALUES (
-            $1, $2, $3, $4, CURRENT_TIMESTAMP
+            $1, $2, $2, $3, $4, CURRENT_TIMESTAMP, $5
         )
         ",
         &id,
-        &Faker.fake::<String>(),
+        &username,
         &FreeEmail().fake::<String>(),
         &Faker.fake::<String>(),
+        role.as_str(),
+    )
+    .execute_ex(executor)
+    .await?;
+
+    sqlx::query!(
+        "INSERT INTO terms_acceptances (id, user_id, terms_version)
+        SELECT uuid_generate_v4(), $1, version
+        FROM terms_versions
+        WHERE published_at <= CURRENT_TIMESTAMP
+        ORDER BY published_at DESC
+        LIMIT 1",
+        &id
     )
     .execute_ex(executor)
     .await?;
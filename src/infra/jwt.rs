@@ -0,0 +1,122 @@
+//! Hides the jwt library behind a small api used to issue
+//! and verify access tokens and mfa pending tokens.
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use jwt::{SignWithStore, VerifyWithStore};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::Sha256;
+use thiserror::Error;
+
+use crate::{config::JwtConfig, infra::uuid::Uuid};
+
+/// Claims contained in an access token.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Claims {
+    /// Id of the user the token was issued to.
+    pub sub: Uuid,
+    /// Id of the session the token belongs to.
+    pub sid: Uuid,
+    /// Unix timestamp of when the token was issued.
+    pub iat: i64,
+    /// Unix timestamp after which the token must be rejected.
+    pub exp: i64,
+    /// True when the user used a second factor to log in.
+    #[serde(default)]
+    pub mfa: bool,
+}
+
+/// Claims contained in the token returned after the first login step
+/// of users that have two-factor authentication enabled.
+/// It can only be exchanged for a session by sending the second factor.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MfaPendingClaims {
+    /// Id of the user that sent the correct password.
+    pub sub: Uuid,
+    /// Unique id of the token, it can only be exchanged for a session once.
+    pub jti: Uuid,
+    /// Unix timestamp of when the token was issued.
+    pub iat: i64,
+    /// Unix timestamp after which the token must be rejected.
+    pub exp: i64,
+    /// Always true. Access tokens do not have this claim and mfa pending tokens
+    /// do not have a `sid`, so one kind of token cannot be used as the other.
+    pub mfa_pending: bool,
+}
+
+/// Implemented by every kind of claims we put in a token.
+pub trait TokenClaims: Serialize + DeserializeOwned {
+    /// Id of the user the token was issued to.
+    fn sub(&self) -> Uuid;
+    /// Unix timestamp after which the token must be rejected.
+    fn exp(&self) -> i64;
+}
+
+impl TokenClaims for Claims {
+    fn sub(&self) -> Uuid {
+        self.sub
+    }
+
+    fn exp(&self) -> i64 {
+        self.exp
+    }
+}
+
+impl TokenClaims for MfaPendingClaims {
+    fn sub(&self) -> Uuid {
+        self.sub
+    }
+
+    fn exp(&self) -> i64 {
+        self.exp
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum TokenError {
+    #[error("jwt token is invalid")]
+    Invalid,
+    #[error("jwt token has expired")]
+    Expired,
+}
+
+/// Returns every configured key indexed by its `kid`.
+fn keys(config: &JwtConfig) -> BTreeMap<&str, Hmac<Sha256>> {
+    config
+        .signing_keys
+        .iter()
+        .map(|key| {
+            (
+                key.kid.as_str(),
+                Hmac::new_from_slice(key.secret.as_bytes()).expect("hmac accepts keys of any size"),
+            )
+        })
+        .collect()
+}
+
+/// Signs the claims with the active key. The key id goes in the token header.
+#[tracing::instrument(name = "jwt::sign", skip_all, fields(
+    sub = %claims.sub(),
+    kid = %config.active_key_id
+))]
+pub fn sign<T: TokenClaims>(config: &JwtConfig, claims: &T) -> Result<String> {
+    Ok((config.active_key_id.as_str(), claims).sign_with_store(&keys(config))?)
+}
+
+/// Verifies the token with the key identified by the `kid` in the token header,
+/// tokens signed by keys that are not configured anymore are rejected.
+#[tracing::instrument(name = "jwt::verify", skip_all)]
+pub fn verify<T: TokenClaims>(config: &JwtConfig, token: &str) -> Result<T, TokenError> {
+    let claims: T = token
+        .verify_with_store(&keys(config))
+        .map_err(|_| TokenError::Invalid)?;
+
+    if claims.exp() <= Utc::now().timestamp() {
+        return Err(TokenError::Expired);
+    }
+
+    Ok(claims)
+}
@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::info;
+
+use crate::domain;
+
+/// Writes emails to files instead of sending them.
+/// Meant to be used in the local env.
+#[derive(Debug)]
+pub struct FileDrop {
+    from: String,
+    dir: PathBuf,
+}
+
+impl FileDrop {
+    pub async fn new(from: String, dir: PathBuf) -> Result<Self> {
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .with_context(|| format!("creating mail drop directory. dir={dir:?}"))?;
+
+        Ok(Self { from, dir })
+    }
+}
+
+#[async_trait]
+impl domain::contracts::mailer::Mailer for FileDrop {
+    #[tracing::instrument(name = "FileDrop::send", skip_all, fields(
+        mail = ?mail
+    ))]
+    async fn send(&self, mail: domain::contracts::mailer::Mail) -> Result<()> {
+        let path = self.dir.join(format!(
+            "{}-{}.eml",
+            Utc::now().format("%Y%m%dT%H%M%S%.6f"),
+            mail.to
+        ));
+
+        let contents = format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\n\r\n{}\r\n",
+            self.from, mail.to, mail.subject, mail.body
+        );
+
+        tokio::fs::write(&path, contents)
+            .await
+            .with_context(|| format!("writing email to file. path={path:?}"))?;
+
+        info!(?path, "email written to file");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use std::{sync::Mutex, time::Duration};
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::domain::contracts::mailer::{Mail, Mailer};
+
+/// Keeps sent emails in memory so tests can read them.
+#[derive(Debug, Default)]
+pub struct InMemory {
+    mails: Mutex<Vec<Mail>>,
+}
+
+impl InMemory {
+    /// Returns the emails sent to `to` in the order they were sent.
+    pub fn sent_to(&self, to: &str) -> Vec<Mail> {
+        self.mails
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|mail| mail.to == to)
+            .cloned()
+            .collect()
+    }
+
+    /// Same as `sent_to` but waits up to 5 seconds for `count` emails,
+    /// for emails that are sent in the background.
+    pub async fn wait_for(&self, to: &str, count: usize) -> Vec<Mail> {
+        for _ in 0..100 {
+            let mails = self.sent_to(to);
+
+            if mails.len() >= count {
+                return mails;
+            }
+
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+
+        self.sent_to(to)
+    }
+}
+
+#[async_trait]
+impl Mailer for InMemory {
+    async fn send(&self, mail: Mail) -> Result<()> {
+        self.mails.lock().unwrap().push(mail);
+        Ok(())
+    }
+}
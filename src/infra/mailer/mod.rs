@@ -0,0 +1,4 @@
+pub mod file_drop;
+#[cfg(test)]
+pub mod memory;
+pub mod smtp;
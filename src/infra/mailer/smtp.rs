@@ -0,0 +1,54 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use lettre::{
+    transport::smtp::authentication::Credentials, AsyncSmtpTransport, AsyncTransport, Message,
+    Tokio1Executor,
+};
+
+use crate::{config::SmtpConfig, domain};
+
+#[derive(Debug)]
+pub struct Smtp {
+    from: String,
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl Smtp {
+    #[tracing::instrument(name = "Smtp::new", skip_all, fields(
+        config = ?config
+    ))]
+    pub fn new(from: String, config: &SmtpConfig) -> Result<Self> {
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)
+            .context("creating smtp transport")?
+            .port(config.port)
+            .credentials(Credentials::new(
+                config.username.clone(),
+                config.password.clone(),
+            ))
+            .build();
+
+        Ok(Self { from, transport })
+    }
+}
+
+#[async_trait]
+impl domain::contracts::mailer::Mailer for Smtp {
+    #[tracing::instrument(name = "Smtp::send", skip_all, fields(
+        mail = ?mail
+    ))]
+    async fn send(&self, mail: domain::contracts::mailer::Mail) -> Result<()> {
+        let message = Message::builder()
+            .from(self.from.parse().context("parsing from address")?)
+            .to(mail.to.parse().context("parsing to address")?)
+            .subject(mail.subject)
+            .body(mail.body)
+            .context("building email")?;
+
+        self.transport
+            .send(message)
+            .await
+            .context("sending email")?;
+
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 #[cfg(test)]
 pub mod factory;
 pub mod http;
+pub mod jwt;
+pub mod mailer;
 pub mod object_storage;
+pub mod oidc;
 pub mod repository;
 pub mod uuid;
@@ -1,7 +1,7 @@
-use std::{str::FromStr, sync::Arc};
+use std::{path::Path, str::FromStr, sync::Arc, time::Duration};
 
 use crate::{config::Config, domain};
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
 
 use aws_credential_types::provider::ProvideCredentials;
@@ -9,14 +9,27 @@ use aws_sdk_s3::Credentials;
 use base64::{engine::general_purpose, Engine};
 use chrono::{SecondsFormat, Utc};
 use hmac::{Hmac, Mac};
-use rusoto_s3::{GetObjectRequest, S3Client, S3 as rusotoS3};
+use rusoto_core::RusotoError;
+use rusoto_core::{credential::AwsCredentials, Region};
+use rusoto_s3::util::{PreSignedRequest, PreSignedRequestOption};
+use rusoto_s3::{
+    AbortMultipartUploadRequest, CompleteMultipartUploadRequest, CompletedMultipartUpload,
+    CompletedPart, CreateMultipartUploadRequest, DeleteObjectRequest, GetObjectError,
+    GetObjectRequest, HeadObjectError, HeadObjectRequest, ListObjectsV2Request, S3Client,
+    UploadPartRequest, S3 as rusotoS3,
+};
 use sha2::Sha256;
 use tokio::io::AsyncReadExt;
 use tracing::info;
 
+/// Size of the parts of multipart uploads, S3 requires at least 5MB
+/// for every part but the last one.
+const MULTIPART_UPLOAD_PART_BYTES: usize = 8 * 1024 * 1024;
+
 pub struct S3 {
     config: Arc<Config>,
     credentials: Credentials,
+    region: Region,
     rusoto_client: S3Client,
 }
 
@@ -30,7 +43,7 @@ impl S3 {
 
         let credentials = credentials_provider.provide_credentials().await?;
 
-        let rusoto_client = S3Client::new(if config.is_local_env() {
+        let region = if config.is_local_env() {
             let endpoint = config.aws.local_endpoint.clone().unwrap();
             info!(?endpoint, "setting rusoto S3 endpoint to local endpoint");
             rusoto_core::Region::Custom {
@@ -39,14 +52,88 @@ impl S3 {
             }
         } else {
             rusoto_core::Region::from_str(&config.aws.region)?
-        });
+        };
+
+        let rusoto_client = S3Client::new(region.clone());
 
         Ok(Self {
             credentials,
             config,
+            region,
             rusoto_client,
         })
     }
+
+    /// Uploads every part of the file, returns the parts in the order they were uploaded.
+    async fn upload_parts(
+        &self,
+        bucket: &str,
+        key: &str,
+        upload_id: &str,
+        path: &Path,
+    ) -> Result<Vec<CompletedPart>> {
+        let mut file = tokio::fs::File::open(path).await?;
+
+        let mut parts = vec![];
+
+        loop {
+            let mut buffer = Vec::with_capacity(MULTIPART_UPLOAD_PART_BYTES);
+
+            while buffer.len() < MULTIPART_UPLOAD_PART_BYTES {
+                let read = (&mut file)
+                    .take((MULTIPART_UPLOAD_PART_BYTES - buffer.len()) as u64)
+                    .read_to_end(&mut buffer)
+                    .await?;
+
+                if read == 0 {
+                    break;
+                }
+            }
+
+            // The previous part ended right at the end of the file.
+            // Empty files are still uploaded as a single empty part.
+            if buffer.is_empty() && !parts.is_empty() {
+                return Ok(parts);
+            }
+
+            let part_number = parts.len() as i64 + 1;
+            let is_last_part = buffer.len() < MULTIPART_UPLOAD_PART_BYTES;
+
+            let response = self
+                .rusoto_client
+                .upload_part(UploadPartRequest {
+                    bucket: bucket.to_owned(),
+                    key: key.to_owned(),
+                    upload_id: upload_id.to_owned(),
+                    part_number,
+                    body: Some(buffer.into()),
+                    ..UploadPartRequest::default()
+                })
+                .await?;
+
+            parts.push(CompletedPart {
+                e_tag: response.e_tag,
+                part_number: Some(part_number),
+            });
+
+            if is_last_part {
+                return Ok(parts);
+            }
+        }
+    }
+
+    /// Objects are uploaded to this url and downloaded from `{bucket_url}/{key}`.
+    fn bucket_url(&self, bucket: &str) -> String {
+        if self.config.is_local_env() {
+            format!(
+                "{}/{}",
+                self.config.aws.local_endpoint.clone().unwrap(),
+                bucket
+            )
+        } else {
+            format!("http://{bucket}.s3.amazonaws.com")
+        }
+    }
 }
 
 #[async_trait]
@@ -60,6 +147,7 @@ impl domain::contracts::object_storage::ObjectStorage for S3 {
         &self,
         bucket: &str,
         key: &str,
+        policy: &domain::contracts::object_storage::PostPolicy,
     ) -> Result<domain::contracts::object_storage::GetPresignedPostUrlOutput> {
         let expiration = Utc::now()
             + chrono::Duration::seconds(
@@ -79,22 +167,31 @@ impl domain::contracts::object_storage::ObjectStorage for S3 {
             self.config.aws.region
         );
 
-        let conditions =
-            serde_json::to_string(&serde_json::json!({ "expiration": expiration_rfc3339,
-              "conditions": [
-                {"bucket": bucket},
-                ["starts-with", "$key", key],
-                // {"success_action_redirect": "http://sigv4examplebucket.s3.amazonaws.com/successful_upload.html"},
-                // ["starts-with", "$Content-Type", "image/"],
-                // {"x-amz-meta-uuid": "14365123651274"},
-                // {"x-amz-server-side-encryption": "AES256"},
-                // ["starts-with", "$x-amz-meta-tag", ""],
-                {"x-amz-credential": x_amz_credential},
-                {"x-amz-algorithm": "AWS4-HMAC-SHA256"},
-                {"x-amz-date": date_iso_8601_basic_format},
-                ["content-length-range", 1000, 10_485_760]
-              ]
-            }))?;
+        let mut policy_conditions = vec![
+            serde_json::json!({"bucket": bucket}),
+            serde_json::json!(["starts-with", "$key", key]),
+            // {"success_action_redirect": "http://sigv4examplebucket.s3.amazonaws.com/successful_upload.html"},
+            // {"x-amz-meta-uuid": "14365123651274"},
+            // {"x-amz-server-side-encryption": "AES256"},
+            // ["starts-with", "$x-amz-meta-tag", ""],
+            serde_json::json!({"x-amz-credential": x_amz_credential}),
+            serde_json::json!({"x-amz-algorithm": "AWS4-HMAC-SHA256"}),
+            serde_json::json!({"x-amz-date": date_iso_8601_basic_format}),
+            serde_json::json!([
+                "content-length-range",
+                policy.content_length_range.0,
+                policy.content_length_range.1
+            ]),
+        ];
+
+        if let Some(content_type) = &policy.content_type {
+            policy_conditions.push(serde_json::json!(["eq", "$Content-Type", content_type]));
+        }
+
+        let conditions = serde_json::to_string(&serde_json::json!({
+            "expiration": expiration_rfc3339,
+            "conditions": policy_conditions,
+        }))?;
 
         let base64_conditions = general_purpose::STANDARD.encode(&conditions);
 
@@ -128,19 +225,8 @@ impl domain::contracts::object_storage::ObjectStorage for S3 {
             hex::encode(signature.finalize().into_bytes())
         };
 
-        let output = domain::contracts::object_storage::GetPresignedPostUrlOutput {
-            endpoint: if self.config.is_local_env() {
-                format!(
-                    "{}/{}",
-                    self.config.aws.local_endpoint.clone().unwrap(),
-                    self.config.s3.videos_bucket
-                )
-            } else {
-                format!(
-                    "http://{bucket}.s3.amazonaws.com",
-                    bucket = self.config.s3.videos_bucket,
-                )
-            },
+        let mut output = domain::contracts::object_storage::GetPresignedPostUrlOutput {
+            endpoint: self.bucket_url(bucket),
             form_data_fields: vec![
                 domain::contracts::object_storage::FormDataField {
                     name: "key".to_owned(),
@@ -169,6 +255,15 @@ impl domain::contracts::object_storage::ObjectStorage for S3 {
             ],
         };
 
+        if let Some(content_type) = &policy.content_type {
+            output
+                .form_data_fields
+                .push(domain::contracts::object_storage::FormDataField {
+                    name: "Content-Type".to_owned(),
+                    value: content_type.clone(),
+                });
+        }
+
         Ok(output)
     }
 
@@ -177,14 +272,18 @@ impl domain::contracts::object_storage::ObjectStorage for S3 {
         key = ?key
     ))]
     async fn get(&self, bucket: &str, key: &str) -> Result<Option<Vec<u8>>> {
-        let response = self
+        let response = match self
             .rusoto_client
             .get_object(GetObjectRequest {
                 bucket: bucket.to_owned(),
                 key: key.to_owned(),
                 ..GetObjectRequest::default()
             })
-            .await?;
+            .await
+        {
+            Err(RusotoError::Service(GetObjectError::NoSuchKey(_))) => return Ok(None),
+            response => response?,
+        };
 
         match response.body {
             None => Ok(None),
@@ -195,4 +294,166 @@ impl domain::contracts::object_storage::ObjectStorage for S3 {
             }
         }
     }
+
+    #[tracing::instrument(name = "S3::exists", skip_all, fields(
+        bucket = ?bucket,
+        key = ?key
+    ))]
+    async fn exists(&self, bucket: &str, key: &str) -> Result<bool> {
+        match self
+            .rusoto_client
+            .head_object(HeadObjectRequest {
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
+                ..HeadObjectRequest::default()
+            })
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(RusotoError::Service(HeadObjectError::NoSuchKey(_))) => Ok(false),
+            // Responses to HEAD requests have no body, so S3 cannot say which error happened.
+            Err(RusotoError::Unknown(response)) if response.status.as_u16() == 404 => Ok(false),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    fn object_url(&self, bucket: &str, key: &str) -> String {
+        format!("{}/{key}", self.bucket_url(bucket))
+    }
+
+    #[tracing::instrument(name = "S3::put_file", skip_all, fields(
+        bucket = ?bucket,
+        key = ?key,
+        path = ?path
+    ))]
+    async fn put_file(
+        &self,
+        bucket: &str,
+        key: &str,
+        path: &Path,
+        content_type: &str,
+    ) -> Result<()> {
+        let upload_id = self
+            .rusoto_client
+            .create_multipart_upload(CreateMultipartUploadRequest {
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
+                content_type: Some(content_type.to_owned()),
+                ..CreateMultipartUploadRequest::default()
+            })
+            .await?
+            .upload_id
+            .context("multipart upload was created without an upload id")?;
+
+        let parts = match self.upload_parts(bucket, key, &upload_id, path).await {
+            Ok(parts) => parts,
+            Err(error) => {
+                // The parts that were uploaded are stored, and billed, until the upload is aborted.
+                self.rusoto_client
+                    .abort_multipart_upload(AbortMultipartUploadRequest {
+                        bucket: bucket.to_owned(),
+                        key: key.to_owned(),
+                        upload_id,
+                        ..AbortMultipartUploadRequest::default()
+                    })
+                    .await?;
+
+                return Err(error);
+            }
+        };
+
+        self.rusoto_client
+            .complete_multipart_upload(CompleteMultipartUploadRequest {
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
+                upload_id,
+                multipart_upload: Some(CompletedMultipartUpload { parts: Some(parts) }),
+                ..CompleteMultipartUploadRequest::default()
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "S3::list_keys", skip_all, fields(
+        bucket = ?bucket,
+        prefix = ?prefix
+    ))]
+    async fn list_keys(&self, bucket: &str, prefix: &str) -> Result<Vec<String>> {
+        let mut keys = vec![];
+        let mut continuation_token = None;
+
+        loop {
+            let response = self
+                .rusoto_client
+                .list_objects_v2(ListObjectsV2Request {
+                    bucket: bucket.to_owned(),
+                    prefix: Some(prefix.to_owned()),
+                    continuation_token,
+                    ..ListObjectsV2Request::default()
+                })
+                .await?;
+
+            keys.extend(
+                response
+                    .contents
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|object| object.key),
+            );
+
+            match response.next_continuation_token {
+                Some(token) if response.is_truncated == Some(true) => {
+                    continuation_token = Some(token)
+                }
+                _ => return Ok(keys),
+            }
+        }
+    }
+
+    #[tracing::instrument(name = "S3::delete", skip_all, fields(
+        bucket = ?bucket,
+        key = ?key
+    ))]
+    async fn delete(&self, bucket: &str, key: &str) -> Result<()> {
+        self.rusoto_client
+            .delete_object(DeleteObjectRequest {
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
+                ..DeleteObjectRequest::default()
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "S3::get_presigned_get_url", skip_all, fields(
+        bucket = ?bucket,
+        key = ?key
+    ))]
+    fn get_presigned_get_url(
+        &self,
+        bucket: &str,
+        key: &str,
+        expires_in: Duration,
+    ) -> Result<String> {
+        let credentials = AwsCredentials::new(
+            self.credentials.access_key_id(),
+            self.credentials.secret_access_key(),
+            self.credentials.session_token().map(str::to_owned),
+            None,
+        );
+
+        let request = GetObjectRequest {
+            bucket: bucket.to_owned(),
+            key: key.to_owned(),
+            ..GetObjectRequest::default()
+        };
+
+        Ok(request.get_presigned_url(
+            &self.region,
+            &credentials,
+            &PreSignedRequestOption { expires_in },
+        ))
+    }
 }
@@ -0,0 +1,248 @@
+//! OpenID Connect client that talks to the issuer through the `Http` contract.
+
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use axum::http::{header, HeaderMap, HeaderValue};
+use jsonwebtoken::{jwk::JwkSet, Algorithm, DecodingKey, Validation};
+use serde::{de::DeserializeOwned, Deserialize};
+use tokio::sync::RwLock;
+
+use crate::{
+    config::OidcConfig,
+    domain::contracts::{
+        http::Http,
+        identity_provider::{self, AuthorizationRequest, IdTokenClaims, IdentityProviderError},
+    },
+};
+
+/// Id tokens signed with symmetric algorithms or `none` are always rejected.
+const ALLOWED_ALGORITHMS: [Algorithm; 3] = [Algorithm::RS256, Algorithm::PS256, Algorithm::ES256];
+
+/// The parts of the issuer's discovery document we use.
+#[derive(Debug, Deserialize)]
+struct ProviderMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    id_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawIdTokenClaims {
+    iss: String,
+    sub: String,
+    email: Option<String>,
+    #[serde(default)]
+    email_verified: EmailVerified,
+    nonce: Option<String>,
+}
+
+/// Some issuers send `email_verified` as the string "true".
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum EmailVerified {
+    Bool(bool),
+    String(String),
+}
+
+impl Default for EmailVerified {
+    fn default() -> Self {
+        Self::Bool(false)
+    }
+}
+
+impl EmailVerified {
+    fn is_true(&self) -> bool {
+        match self {
+            Self::Bool(value) => *value,
+            Self::String(value) => value.eq_ignore_ascii_case("true"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct OidcIdentityProvider {
+    http: Arc<dyn Http>,
+    config: OidcConfig,
+    /// The discovery document is fetched once, the keys are fetched on every
+    /// login so key rotations at the issuer are picked up right away.
+    metadata: RwLock<Option<Arc<ProviderMetadata>>>,
+}
+
+impl OidcIdentityProvider {
+    pub fn new(http: Arc<dyn Http>, config: OidcConfig) -> Self {
+        Self {
+            http,
+            config,
+            metadata: RwLock::new(None),
+        }
+    }
+
+    #[tracing::instrument(name = "OidcIdentityProvider.metadata", skip_all)]
+    async fn metadata(&self) -> Result<Arc<ProviderMetadata>> {
+        if let Some(metadata) = &*self.metadata.read().await {
+            return Ok(Arc::clone(metadata));
+        }
+
+        let issuer = self.config.issuer_url.trim_end_matches('/');
+
+        let metadata: ProviderMetadata = self
+            .get_json(&format!("{issuer}/.well-known/openid-configuration"))
+            .await
+            .context("fetching oidc discovery document")?;
+
+        // The issuer in the document must be the one we trust,
+        // otherwise it could make us accept tokens from another issuer.
+        if metadata.issuer.trim_end_matches('/') != issuer {
+            anyhow::bail!(
+                "oidc discovery document belongs to another issuer. issuer={}",
+                metadata.issuer
+            );
+        }
+
+        let metadata = Arc::new(metadata);
+
+        *self.metadata.write().await = Some(Arc::clone(&metadata));
+
+        Ok(metadata)
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T> {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, HeaderValue::from_static("application/json"));
+
+        let response = self
+            .http
+            .get(url, None, Some(headers))
+            .await?
+            .error_for_status()?;
+
+        Ok(serde_json::from_slice(&response.bytes().await?)?)
+    }
+
+    #[tracing::instrument(name = "OidcIdentityProvider.verify_id_token", skip_all)]
+    async fn verify_id_token(
+        &self,
+        metadata: &ProviderMetadata,
+        id_token: &str,
+    ) -> Result<IdTokenClaims> {
+        let invalid = |reason: &str| IdentityProviderError::InvalidIdToken(reason.to_owned());
+
+        let header =
+            jsonwebtoken::decode_header(id_token).map_err(|_| invalid("malformed header"))?;
+
+        if !ALLOWED_ALGORITHMS.contains(&header.alg) {
+            return Err(invalid("algorithm is not allowed").into());
+        }
+
+        let jwks: JwkSet = self
+            .get_json(&metadata.jwks_uri)
+            .await
+            .context("fetching oidc jwks")?;
+
+        let jwk = match &header.kid {
+            Some(kid) => jwks.find(kid),
+            None if jwks.keys.len() == 1 => jwks.keys.first(),
+            None => None,
+        }
+        .ok_or_else(|| invalid("signing key not found"))?;
+
+        let key = DecodingKey::from_jwk(jwk).map_err(|_| invalid("unsupported signing key"))?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&metadata.issuer]);
+        validation.set_audience(&[&self.config.client_id]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+        let claims = jsonwebtoken::decode::<RawIdTokenClaims>(id_token, &key, &validation)
+            .map_err(|error| invalid(&error.to_string()))?
+            .claims;
+
+        Ok(IdTokenClaims {
+            issuer: claims.iss,
+            subject: claims.sub,
+            email: claims.email,
+            email_verified: claims.email_verified.is_true(),
+            nonce: claims.nonce,
+        })
+    }
+}
+
+#[async_trait]
+impl identity_provider::IdentityProvider for OidcIdentityProvider {
+    #[tracing::instrument(name = "OidcIdentityProvider.authorization_url", skip_all)]
+    async fn authorization_url(&self, request: &AuthorizationRequest) -> Result<String> {
+        let metadata = self.metadata().await?;
+
+        let query = serde_urlencoded::to_string([
+            ("response_type", "code"),
+            ("scope", "openid email"),
+            ("client_id", self.config.client_id.as_str()),
+            ("redirect_uri", self.config.redirect_uri.as_str()),
+            ("state", request.state.as_str()),
+            ("nonce", request.nonce.as_str()),
+            ("code_challenge", request.code_challenge.as_str()),
+            ("code_challenge_method", "S256"),
+        ])?;
+
+        let separator = if metadata.authorization_endpoint.contains('?') {
+            '&'
+        } else {
+            '?'
+        };
+
+        Ok(format!(
+            "{}{separator}{query}",
+            metadata.authorization_endpoint
+        ))
+    }
+
+    #[tracing::instrument(name = "OidcIdentityProvider.exchange_code", skip_all)]
+    async fn exchange_code(&self, code: &str, code_verifier: &str) -> Result<IdTokenClaims> {
+        let metadata = self.metadata().await?;
+
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", self.config.redirect_uri.as_str()),
+            ("client_id", self.config.client_id.as_str()),
+            ("code_verifier", code_verifier),
+        ];
+
+        if let Some(client_secret) = &self.config.client_secret {
+            form.push(("client_secret", client_secret.as_str()));
+        }
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/x-www-form-urlencoded"),
+        );
+        headers.insert(header::ACCEPT, HeaderValue::from_static("application/json"));
+
+        let response = self
+            .http
+            .post(
+                &metadata.token_endpoint,
+                Some(serde_urlencoded::to_string(form)?.into()),
+                Some(headers),
+            )
+            .await?;
+
+        if response.status().is_client_error() {
+            return Err(IdentityProviderError::CodeRejected.into());
+        }
+
+        let response: TokenResponse =
+            serde_json::from_slice(&response.error_for_status()?.bytes().await?)?;
+
+        self.verify_id_token(&metadata, &response.id_token).await
+    }
+}
@@ -0,0 +1,184 @@
+use crate::domain::{
+    commands,
+    contracts::{
+        self,
+        repository::{Executor, SqlxExt},
+    },
+    queries::api_key::{authenticate::ApiKeyOwner, list::ApiKey},
+    value_objects::api_key_scope::ApiKeyScope,
+};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, Row};
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub struct ApiKeyRepository;
+
+#[async_trait]
+impl contracts::repository::ApiKeyRepository for ApiKeyRepository {
+    #[tracing::instrument(name = "ApiKeyRepository.create", skip_all, fields(
+        id = %input.id,
+        user_id = %input.user_id
+    ))]
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::api_key::NewApiKey,
+    ) -> Result<()> {
+        let scopes: Vec<&str> = input.scopes.iter().map(ApiKeyScope::as_str).collect();
+
+        sqlx::query!(
+            "INSERT INTO api_keys (
+                id,
+                user_id,
+                name,
+                prefix,
+                secret_hash,
+                scopes
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6
+            )",
+            &input.id,
+            &input.user_id,
+            &input.name,
+            &input.prefix,
+            &input.secret_hash,
+            &scopes as &[&str],
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "ApiKeyRepository.list_by_user", skip_all, fields(
+        user_id = %user_id
+    ))]
+    async fn list_by_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+    ) -> Result<Vec<ApiKey>> {
+        let rows = sqlx::query!(
+            "SELECT
+                id,
+                name,
+                prefix,
+                scopes,
+                last_used_at,
+                created_at
+            FROM api_keys
+            WHERE user_id = $1 AND revoked_at IS NULL
+            ORDER BY created_at DESC",
+            &user_id
+        )
+        .fetch_all_ex(executor)
+        .await?;
+
+        rows.into_iter().map(ApiKey::try_from).collect()
+    }
+
+    #[tracing::instrument(name = "ApiKeyRepository.get_by_secret_hash", skip_all)]
+    async fn get_by_secret_hash<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        secret_hash: &str,
+    ) -> Result<Option<ApiKeyOwner>> {
+        let row = sqlx::query!(
+            "SELECT id, user_id, scopes
+            FROM api_keys
+            WHERE secret_hash = $1 AND revoked_at IS NULL",
+            secret_hash
+        )
+        .fetch_optional_ex(executor)
+        .await?;
+
+        match row {
+            None => Ok(None),
+            Some(row) => Ok(Some(ApiKeyOwner {
+                api_key_id: row.try_get("id")?,
+                user_id: row.try_get("user_id")?,
+                scopes: parse_scopes(row.try_get("scopes")?)?,
+            })),
+        }
+    }
+
+    #[tracing::instrument(name = "ApiKeyRepository.revoke", skip_all, fields(
+        user_id = %user_id,
+        id = %id
+    ))]
+    async fn revoke<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            "UPDATE api_keys
+            SET revoked_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL",
+            &id,
+            &user_id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[tracing::instrument(name = "ApiKeyRepository.revoke_all_for_user", skip_all, fields(
+        user_id = %user_id
+    ))]
+    async fn revoke_all_for_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+    ) -> Result<()> {
+        sqlx::query!(
+            "UPDATE api_keys
+            SET revoked_at = CURRENT_TIMESTAMP
+            WHERE user_id = $1 AND revoked_at IS NULL",
+            &user_id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "ApiKeyRepository.mark_as_used", skip_all, fields(id = %id))]
+    async fn mark_as_used<'c>(&self, executor: &mut Executor<'c>, id: Uuid) -> Result<()> {
+        sqlx::query!(
+            "UPDATE api_keys SET last_used_at = CURRENT_TIMESTAMP WHERE id = $1",
+            &id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+}
+
+impl TryFrom<PgRow> for ApiKey {
+    type Error = anyhow::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            prefix: row.try_get("prefix")?,
+            scopes: parse_scopes(row.try_get("scopes")?)?,
+            last_used_at: row.try_get("last_used_at")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+fn parse_scopes(scopes: Vec<String>) -> Result<Vec<ApiKeyScope>> {
+    Ok(scopes
+        .iter()
+        .map(|scope| ApiKeyScope::from_str(scope))
+        .collect::<Result<_, _>>()?)
+}
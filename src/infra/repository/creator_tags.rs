@@ -0,0 +1,214 @@
+use crate::domain::{
+    contracts::{
+        self,
+        repository::{Executor, SqlxExt},
+    },
+    queries::creator::list_by_tag::Creator,
+    value_objects::{creator_tag::CreatorTag, cursor::Cursor},
+};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, Row};
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub struct CreatorTagRepository;
+
+#[async_trait]
+impl contracts::repository::CreatorTagRepository for CreatorTagRepository {
+    #[tracing::instrument(name = "CreatorTagRepository.list_by_user", skip_all, fields(
+        user_id = %user_id
+    ))]
+    async fn list_by_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+    ) -> Result<Vec<CreatorTag>> {
+        let rows = sqlx::query!(
+            "SELECT name FROM user_tags WHERE user_id = $1 ORDER BY name",
+            &user_id
+        )
+        .fetch_all_ex(executor)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Ok(CreatorTag::from_str(row.try_get("name")?)?))
+            .collect()
+    }
+
+    #[tracing::instrument(name = "CreatorTagRepository.add", skip_all, fields(
+        user_id = %user_id,
+        tags = ?tags
+    ))]
+    async fn add<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+        tags: &[CreatorTag],
+    ) -> Result<Vec<CreatorTag>> {
+        let names: Vec<&str> = tags.iter().map(CreatorTag::as_str).collect();
+
+        let rows = sqlx::query!(
+            "INSERT INTO user_tags (id, user_id, name)
+            SELECT uuid_generate_v4(), $1, name FROM UNNEST($2::VARCHAR[]) as name
+            ON CONFLICT (user_id, name) DO NOTHING
+            RETURNING name",
+            &user_id,
+            &names as &[&str],
+        )
+        .fetch_all_ex(executor)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Ok(CreatorTag::from_str(row.try_get("name")?)?))
+            .collect()
+    }
+
+    #[tracing::instrument(name = "CreatorTagRepository.remove", skip_all, fields(
+        user_id = %user_id,
+        tags = ?tags
+    ))]
+    async fn remove<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+        tags: &[CreatorTag],
+    ) -> Result<Vec<CreatorTag>> {
+        let names: Vec<&str> = tags.iter().map(CreatorTag::as_str).collect();
+
+        let rows = sqlx::query!(
+            "DELETE FROM user_tags
+            WHERE user_id = $1 AND name = ANY($2)
+            RETURNING name",
+            &user_id,
+            &names as &[&str],
+        )
+        .fetch_all_ex(executor)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Ok(CreatorTag::from_str(row.try_get("name")?)?))
+            .collect()
+    }
+
+    #[tracing::instrument(name = "CreatorTagRepository.update_counts", skip_all, fields(
+        tags = ?tags,
+        delta = %delta
+    ))]
+    async fn update_counts<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        tags: &[CreatorTag],
+        delta: i32,
+    ) -> Result<()> {
+        if tags.is_empty() {
+            return Ok(());
+        }
+
+        let names: Vec<&str> = tags.iter().map(CreatorTag::as_str).collect();
+
+        sqlx::query!(
+            "INSERT INTO tag_counts (name, creators_count)
+            SELECT name, GREATEST($2, 0) FROM UNNEST($1::VARCHAR[]) as name
+            ON CONFLICT (name) DO UPDATE
+            SET
+                creators_count = GREATEST(tag_counts.creators_count + $2, 0),
+                updated_at = CURRENT_TIMESTAMP",
+            &names as &[&str],
+            delta
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "CreatorTagRepository.list_counts", skip_all)]
+    async fn list_counts<'c>(&self, executor: &mut Executor<'c>) -> Result<Vec<(CreatorTag, i32)>> {
+        let names: Vec<&str> = CreatorTag::ALL.iter().map(CreatorTag::as_str).collect();
+
+        let rows = sqlx::query!(
+            "SELECT name, creators_count FROM tag_counts WHERE name = ANY($1)",
+            &names as &[&str],
+        )
+        .fetch_all_ex(executor)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok((
+                    CreatorTag::from_str(row.try_get("name")?)?,
+                    row.try_get("creators_count")?,
+                ))
+            })
+            .collect()
+    }
+
+    #[tracing::instrument(name = "CreatorTagRepository.list_creators", skip_all, fields(
+        tag = %tag,
+        cursor = ?cursor
+    ))]
+    async fn list_creators<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        tag: CreatorTag,
+        cursor: Cursor,
+    ) -> Result<Vec<Creator>> {
+        let rows = sqlx::query!(
+            "SELECT
+                users.id,
+                users.username,
+                users.display_name,
+                users.avatar_url,
+                (
+                    SELECT COUNT(*)
+                    FROM user_followers
+                    INNER JOIN users followers ON followers.id = user_followers.follower_id
+                    WHERE user_followers.followee_id = users.id
+                    AND followers.deleted_at IS NULL
+                    AND followers.banned_at IS NULL
+                ) as followers_count,
+                ARRAY(
+                    SELECT name FROM user_tags tags
+                    WHERE tags.user_id = users.id
+                    ORDER BY name
+                ) as tags
+            FROM user_tags
+            INNER JOIN users ON users.id = user_tags.user_id
+            WHERE user_tags.name = $1
+            AND users.role = 'creator'
+            AND users.deleted_at IS NULL
+            AND users.banned_at IS NULL
+            ORDER BY followers_count DESC, users.id
+            OFFSET $2 LIMIT $3",
+            tag.as_str(),
+            cursor.offset,
+            cursor.limit
+        )
+        .fetch_all_ex(executor)
+        .await?;
+
+        rows.into_iter().map(Creator::try_from).collect()
+    }
+}
+
+impl TryFrom<PgRow> for Creator {
+    type Error = anyhow::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        let tags: Vec<String> = row.try_get("tags")?;
+
+        Ok(Self {
+            id: row.try_get("id")?,
+            username: row.try_get("username")?,
+            display_name: row.try_get("display_name")?,
+            avatar_url: row.try_get("avatar_url")?,
+            followers_count: row.try_get("followers_count")?,
+            tags: tags
+                .iter()
+                .map(|tag| CreatorTag::from_str(tag))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
@@ -0,0 +1,111 @@
+use crate::domain::{
+    commands,
+    contracts::{
+        self,
+        repository::{Executor, SqlxExt},
+    },
+};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::Row;
+
+#[derive(Debug)]
+pub struct EmailVerificationTokenRepository;
+
+#[async_trait]
+impl contracts::repository::EmailVerificationTokenRepository for EmailVerificationTokenRepository {
+    #[tracing::instrument(name = "EmailVerificationTokenRepository.create", skip_all, fields(
+        user_id = %input.user_id
+    ))]
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::user::NewEmailVerificationToken,
+    ) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO email_verification_tokens (
+                id,
+                user_id,
+                token_hash,
+                expires_at
+            ) VALUES (
+                $1, $2, $3, $4
+            )",
+            &input.id,
+            &input.user_id,
+            &input.token_hash,
+            &input.expires_at,
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "EmailVerificationTokenRepository.get_by_token_hash_for_update",
+        skip_all
+    )]
+    async fn get_by_token_hash_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        token_hash: &str,
+    ) -> Result<Option<commands::user::EmailVerificationToken>> {
+        let row = sqlx::query!(
+            "SELECT id, user_id, expires_at, used_at
+            FROM email_verification_tokens
+            WHERE token_hash = $1
+            FOR UPDATE",
+            token_hash
+        )
+        .fetch_optional_ex(executor)
+        .await?;
+
+        match row {
+            None => Ok(None),
+            Some(row) => Ok(Some(commands::user::EmailVerificationToken {
+                id: row.try_get("id")?,
+                user_id: row.try_get("user_id")?,
+                expires_at: row.try_get("expires_at")?,
+                used_at: row.try_get("used_at")?,
+            })),
+        }
+    }
+
+    #[tracing::instrument(name = "EmailVerificationTokenRepository.mark_as_used", skip_all, fields(
+        id = %id
+    ))]
+    async fn mark_as_used<'c>(&self, executor: &mut Executor<'c>, id: Uuid) -> Result<()> {
+        sqlx::query!(
+            "UPDATE email_verification_tokens SET used_at = CURRENT_TIMESTAMP WHERE id = $1",
+            &id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "EmailVerificationTokenRepository.mark_all_as_used_for_user",
+        skip_all,
+        fields(user_id = %user_id)
+    )]
+    async fn mark_all_as_used_for_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+    ) -> Result<()> {
+        sqlx::query!(
+            "UPDATE email_verification_tokens
+            SET used_at = CURRENT_TIMESTAMP
+            WHERE user_id = $1 AND used_at IS NULL",
+            &user_id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+}
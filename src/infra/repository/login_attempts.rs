@@ -0,0 +1,107 @@
+use crate::domain::{
+    commands::session::LoginAttemptKey,
+    contracts::{
+        self,
+        repository::{Executor, SqlxExt},
+    },
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+
+#[derive(Debug)]
+pub struct LoginAttemptRepository;
+
+#[async_trait]
+impl contracts::repository::LoginAttemptRepository for LoginAttemptRepository {
+    #[tracing::instrument(name = "LoginAttemptRepository.get_locked_until", skip_all)]
+    async fn get_locked_until<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        keys: &[LoginAttemptKey],
+    ) -> Result<Option<DateTime<Utc>>> {
+        let scopes: Vec<&str> = keys.iter().map(|key| key.scope.as_str()).collect();
+        let values: Vec<&str> = keys.iter().map(|key| key.key.as_str()).collect();
+
+        let row = sqlx::query!(
+            "SELECT MAX(login_attempts.locked_until) as locked_until
+            FROM login_attempts
+            INNER JOIN UNNEST($1::VARCHAR[], $2::VARCHAR[]) AS keys(scope, key)
+            ON keys.scope = login_attempts.scope AND keys.key = login_attempts.key",
+            &scopes as &[&str],
+            &values as &[&str],
+        )
+        .fetch_one_ex(executor)
+        .await?;
+
+        Ok(row.try_get("locked_until")?)
+    }
+
+    #[tracing::instrument(name = "LoginAttemptRepository.record_failure", skip_all, fields(
+        scope = key.scope.as_str()
+    ))]
+    async fn record_failure<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        key: &LoginAttemptKey,
+        window_secs: i64,
+    ) -> Result<i32> {
+        let row = sqlx::query!(
+            "INSERT INTO login_attempts (scope, key, failed_count, last_failed_at)
+            VALUES ($1, $2, 1, CURRENT_TIMESTAMP)
+            ON CONFLICT (scope, key) DO UPDATE SET
+                failed_count = CASE
+                    WHEN login_attempts.last_failed_at < CURRENT_TIMESTAMP - make_interval(secs => $3)
+                    THEN 1
+                    ELSE login_attempts.failed_count + 1
+                END,
+                last_failed_at = CURRENT_TIMESTAMP
+            RETURNING failed_count",
+            key.scope.as_str(),
+            &key.key,
+            window_secs as f64,
+        )
+        .fetch_one_ex(executor)
+        .await?;
+
+        Ok(row.try_get("failed_count")?)
+    }
+
+    #[tracing::instrument(name = "LoginAttemptRepository.lock", skip_all, fields(
+        scope = key.scope.as_str(),
+        until = %until
+    ))]
+    async fn lock<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        key: &LoginAttemptKey,
+        until: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query!(
+            "UPDATE login_attempts SET locked_until = $3 WHERE scope = $1 AND key = $2",
+            key.scope.as_str(),
+            &key.key,
+            until,
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "LoginAttemptRepository.clear", skip_all, fields(
+        scope = key.scope.as_str()
+    ))]
+    async fn clear<'c>(&self, executor: &mut Executor<'c>, key: &LoginAttemptKey) -> Result<()> {
+        sqlx::query!(
+            "DELETE FROM login_attempts WHERE scope = $1 AND key = $2",
+            key.scope.as_str(),
+            &key.key,
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use crate::domain::{
+    commands,
+    contracts::{
+        self,
+        repository::{Executor, SqlxExt},
+    },
+};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[derive(Debug)]
+pub struct MfaRecoveryCodeRepository;
+
+#[async_trait]
+impl contracts::repository::MfaRecoveryCodeRepository for MfaRecoveryCodeRepository {
+    #[tracing::instrument(name = "MfaRecoveryCodeRepository.create", skip_all, fields(
+        user_id = %input.user_id
+    ))]
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::mfa::NewMfaRecoveryCode,
+    ) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO mfa_recovery_codes (
+                id,
+                user_id,
+                code_hash
+            ) VALUES (
+                $1, $2, $3
+            )",
+            &input.id,
+            &input.user_id,
+            &input.code_hash,
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "MfaRecoveryCodeRepository.delete_all_for_user", skip_all, fields(
+        user_id = %user_id
+    ))]
+    async fn delete_all_for_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+    ) -> Result<()> {
+        sqlx::query!(
+            "DELETE FROM mfa_recovery_codes WHERE user_id = $1",
+            &user_id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "MfaRecoveryCodeRepository.use_code", skip_all, fields(
+        user_id = %user_id
+    ))]
+    async fn use_code<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+        code_hash: &str,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            "UPDATE mfa_recovery_codes
+            SET used_at = CURRENT_TIMESTAMP
+            WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL",
+            &user_id,
+            code_hash
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
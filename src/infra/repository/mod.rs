@@ -1,5 +1,25 @@
+pub mod api_keys;
+pub mod creator_tags;
+pub mod email_verification_tokens;
+pub mod login_attempts;
+pub mod mfa_recovery_codes;
+pub mod object_purges;
+pub mod oidc;
+pub mod password_reset_tokens;
+pub mod personal_data_exports;
+pub mod posts;
+pub mod profile_image_uploads;
+pub mod refresh_tokens;
+pub mod subscriptions;
+pub mod terms;
 pub mod timeline;
+pub mod used_mfa_tokens;
+pub mod user_bans;
+pub mod user_blocks;
+pub mod user_followers;
+pub mod user_mutes;
 pub mod users;
+pub mod video_uploads;
 
 use std::sync::Arc;
 
@@ -15,7 +35,19 @@ use async_trait::async_trait;
 use sqlx::{postgres::PgPoolOptions, Pool, Postgres};
 use tokio::sync::RwLock;
 
-use self::{timeline::TimelineRepository, users::UserRepository};
+use self::{
+    api_keys::ApiKeyRepository, creator_tags::CreatorTagRepository,
+    email_verification_tokens::EmailVerificationTokenRepository,
+    login_attempts::LoginAttemptRepository, mfa_recovery_codes::MfaRecoveryCodeRepository,
+    object_purges::ObjectPurgeRepository, oidc::OidcRepository,
+    password_reset_tokens::PasswordResetTokenRepository,
+    personal_data_exports::PersonalDataExportRepository, posts::PostRepository,
+    profile_image_uploads::ProfileImageUploadRepository, refresh_tokens::RefreshTokenRepository,
+    subscriptions::SubscriptionRepository, terms::TermsRepository, timeline::TimelineRepository,
+    used_mfa_tokens::UsedMfaTokenRepository, user_bans::UserBanRepository,
+    user_blocks::UserBlockRepository, user_followers::UserFollowerRepository,
+    user_mutes::UserMuteRepository, users::UserRepository, video_uploads::VideoUploadRepository,
+};
 
 #[derive(Debug)]
 pub struct Config {
@@ -112,5 +144,25 @@ pub fn new() -> contracts::repository::Repository {
     contracts::repository::Repository {
         users: Arc::new(UserRepository),
         timeline: Arc::new(TimelineRepository),
+        refresh_tokens: Arc::new(RefreshTokenRepository),
+        email_verification_tokens: Arc::new(EmailVerificationTokenRepository),
+        password_reset_tokens: Arc::new(PasswordResetTokenRepository),
+        mfa_recovery_codes: Arc::new(MfaRecoveryCodeRepository),
+        used_mfa_tokens: Arc::new(UsedMfaTokenRepository),
+        login_attempts: Arc::new(LoginAttemptRepository),
+        api_keys: Arc::new(ApiKeyRepository),
+        oidc: Arc::new(OidcRepository),
+        profile_image_uploads: Arc::new(ProfileImageUploadRepository),
+        posts: Arc::new(PostRepository),
+        object_purges: Arc::new(ObjectPurgeRepository),
+        user_bans: Arc::new(UserBanRepository),
+        user_followers: Arc::new(UserFollowerRepository),
+        creator_tags: Arc::new(CreatorTagRepository),
+        user_blocks: Arc::new(UserBlockRepository),
+        user_mutes: Arc::new(UserMuteRepository),
+        terms: Arc::new(TermsRepository),
+        subscriptions: Arc::new(SubscriptionRepository),
+        video_uploads: Arc::new(VideoUploadRepository),
+        personal_data_exports: Arc::new(PersonalDataExportRepository),
     }
 }
@@ -0,0 +1,94 @@
+use crate::domain::{
+    commands,
+    contracts::{
+        self,
+        repository::{Executor, SqlxExt},
+    },
+};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::Row;
+
+#[derive(Debug)]
+pub struct ObjectPurgeRepository;
+
+#[async_trait]
+impl contracts::repository::ObjectPurgeRepository for ObjectPurgeRepository {
+    #[tracing::instrument(name = "ObjectPurgeRepository.schedule", skip_all, fields(
+        id = %input.id,
+        user_id = %input.user_id,
+        purge_after = %input.purge_after
+    ))]
+    async fn schedule<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::object_purge::NewObjectPurge,
+    ) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO object_purges (
+                id,
+                user_id,
+                bucket,
+                key_prefix,
+                purge_after
+            ) VALUES (
+                $1, $2, $3, $4, $5
+            )",
+            &input.id,
+            &input.user_id,
+            &input.bucket,
+            &input.key_prefix,
+            &input.purge_after,
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "ObjectPurgeRepository.get_due_for_update", skip_all, fields(
+        limit = %limit
+    ))]
+    async fn get_due_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        limit: i64,
+    ) -> Result<Vec<commands::object_purge::ObjectPurge>> {
+        let rows = sqlx::query!(
+            "SELECT id, bucket, key_prefix
+            FROM object_purges
+            WHERE purged_at IS NULL AND purge_after <= CURRENT_TIMESTAMP
+            ORDER BY purge_after
+            LIMIT $1
+            FOR UPDATE SKIP LOCKED",
+            limit
+        )
+        .fetch_all_ex(executor)
+        .await?;
+
+        let mut purges = Vec::with_capacity(rows.len());
+
+        for row in rows {
+            purges.push(commands::object_purge::ObjectPurge {
+                id: row.try_get("id")?,
+                bucket: row.try_get("bucket")?,
+                key_prefix: row.try_get("key_prefix")?,
+            });
+        }
+
+        Ok(purges)
+    }
+
+    #[tracing::instrument(name = "ObjectPurgeRepository.mark_as_purged", skip_all, fields(id = %id))]
+    async fn mark_as_purged<'c>(&self, executor: &mut Executor<'c>, id: Uuid) -> Result<()> {
+        sqlx::query!(
+            "UPDATE object_purges SET purged_at = CURRENT_TIMESTAMP WHERE id = $1",
+            &id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+}
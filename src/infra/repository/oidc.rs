@@ -0,0 +1,194 @@
+use crate::domain::{
+    commands,
+    contracts::{
+        self,
+        repository::{Executor, SqlxExt},
+    },
+};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::Row;
+
+#[derive(Debug)]
+pub struct OidcRepository;
+
+#[async_trait]
+impl contracts::repository::OidcRepository for OidcRepository {
+    #[tracing::instrument(name = "OidcRepository.create_authorization_request", skip_all, fields(
+        id = %input.id
+    ))]
+    async fn create_authorization_request<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::session::NewOidcAuthorizationRequest,
+    ) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO oidc_authorization_requests (
+                id,
+                state_hash,
+                verifier_hash,
+                nonce,
+                code_verifier,
+                link_user_id,
+                expires_at
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6, $7
+            )",
+            &input.id,
+            &input.state_hash,
+            &input.verifier_hash,
+            &input.nonce,
+            &input.code_verifier,
+            input.link_user_id.as_ref(),
+            &input.expires_at,
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "OidcRepository.get_authorization_request_for_update", skip_all)]
+    async fn get_authorization_request_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        state_hash: &str,
+    ) -> Result<Option<commands::session::OidcAuthorizationRequest>> {
+        let row = sqlx::query!(
+            "SELECT
+                id,
+                verifier_hash,
+                nonce,
+                code_verifier,
+                link_user_id,
+                expires_at,
+                used_at IS NOT NULL as used
+            FROM oidc_authorization_requests
+            WHERE state_hash = $1
+            FOR UPDATE",
+            state_hash
+        )
+        .fetch_optional_ex(executor)
+        .await?;
+
+        match row {
+            None => Ok(None),
+            Some(row) => Ok(Some(commands::session::OidcAuthorizationRequest {
+                id: row.try_get("id")?,
+                verifier_hash: row.try_get("verifier_hash")?,
+                nonce: row.try_get("nonce")?,
+                code_verifier: row.try_get("code_verifier")?,
+                link_user_id: row.try_get("link_user_id")?,
+                expires_at: row.try_get("expires_at")?,
+                used: row.try_get("used")?,
+            })),
+        }
+    }
+
+    #[tracing::instrument(name = "OidcRepository.mark_authorization_request_as_used", skip_all, fields(
+        id = %id
+    ))]
+    async fn mark_authorization_request_as_used<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+    ) -> Result<()> {
+        sqlx::query!(
+            "UPDATE oidc_authorization_requests SET used_at = NOW() WHERE id = $1",
+            &id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "OidcRepository.get_user_id_by_identity", skip_all, fields(
+        issuer = %issuer
+    ))]
+    async fn get_user_id_by_identity<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        issuer: &str,
+        subject: &str,
+    ) -> Result<Option<Uuid>> {
+        let row = sqlx::query!(
+            "SELECT users.id
+            FROM oidc_identities
+            INNER JOIN users ON users.id = oidc_identities.user_id
+            WHERE oidc_identities.issuer = $1
+                AND oidc_identities.subject = $2
+                AND users.deleted_at IS NULL",
+            issuer,
+            subject
+        )
+        .fetch_optional_ex(executor)
+        .await?;
+
+        match row {
+            None => Ok(None),
+            Some(row) => Ok(Some(row.try_get("id")?)),
+        }
+    }
+
+    #[tracing::instrument(name = "OidcRepository.create_identity", skip_all, fields(
+        id = %input.id,
+        user_id = %input.user_id
+    ))]
+    async fn create_identity<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::session::NewOidcIdentity,
+    ) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO oidc_identities (
+                id,
+                user_id,
+                issuer,
+                subject
+            ) VALUES (
+                $1, $2, $3, $4
+            )",
+            &input.id,
+            &input.user_id,
+            &input.issuer,
+            &input.subject,
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "OidcRepository.list_identities_by_user", skip_all, fields(
+        user_id = %user_id
+    ))]
+    async fn list_identities_by_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+    ) -> Result<Vec<commands::user::PersonalDataIdentity>> {
+        let rows = sqlx::query!(
+            "SELECT issuer, subject, created_at
+            FROM oidc_identities
+            WHERE user_id = $1
+            ORDER BY created_at",
+            &user_id
+        )
+        .fetch_all_ex(executor)
+        .await?;
+
+        let mut identities = Vec::with_capacity(rows.len());
+
+        for row in rows {
+            identities.push(commands::user::PersonalDataIdentity {
+                issuer: row.try_get("issuer")?,
+                subject: row.try_get("subject")?,
+                created_at: row.try_get("created_at")?,
+            });
+        }
+
+        Ok(identities)
+    }
+}
@@ -0,0 +1,104 @@
+use crate::domain::{
+    commands,
+    contracts::{
+        self,
+        repository::{Executor, SqlxExt},
+    },
+};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::Row;
+
+#[derive(Debug)]
+pub struct PasswordResetTokenRepository;
+
+#[async_trait]
+impl contracts::repository::PasswordResetTokenRepository for PasswordResetTokenRepository {
+    #[tracing::instrument(name = "PasswordResetTokenRepository.create", skip_all, fields(
+        user_id = %input.user_id
+    ))]
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::password_reset::NewPasswordResetToken,
+    ) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO password_reset_tokens (
+                id,
+                user_id,
+                token_hash,
+                expires_at
+            ) VALUES (
+                $1, $2, $3, $4
+            )",
+            &input.id,
+            &input.user_id,
+            &input.token_hash,
+            &input.expires_at,
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "PasswordResetTokenRepository.get_by_token_hash_for_update",
+        skip_all
+    )]
+    async fn get_by_token_hash_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        token_hash: &str,
+    ) -> Result<Option<commands::password_reset::PasswordResetToken>> {
+        let row = sqlx::query!(
+            "SELECT
+                password_reset_tokens.user_id,
+                users.username,
+                users.email,
+                password_reset_tokens.expires_at,
+                password_reset_tokens.used_at
+            FROM password_reset_tokens
+            INNER JOIN users ON users.id = password_reset_tokens.user_id
+            WHERE password_reset_tokens.token_hash = $1
+            FOR UPDATE OF password_reset_tokens",
+            token_hash
+        )
+        .fetch_optional_ex(executor)
+        .await?;
+
+        match row {
+            None => Ok(None),
+            Some(row) => Ok(Some(commands::password_reset::PasswordResetToken {
+                user_id: row.try_get("user_id")?,
+                username: row.try_get("username")?,
+                email: row.try_get("email")?,
+                expires_at: row.try_get("expires_at")?,
+                used_at: row.try_get("used_at")?,
+            })),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "PasswordResetTokenRepository.mark_all_as_used_for_user",
+        skip_all,
+        fields(user_id = %user_id)
+    )]
+    async fn mark_all_as_used_for_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+    ) -> Result<()> {
+        sqlx::query!(
+            "UPDATE password_reset_tokens
+            SET used_at = CURRENT_TIMESTAMP
+            WHERE user_id = $1 AND used_at IS NULL",
+            &user_id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+}
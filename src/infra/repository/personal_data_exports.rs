@@ -0,0 +1,155 @@
+use crate::domain::{
+    commands,
+    contracts::{
+        self,
+        repository::{Executor, SqlxExt},
+    },
+};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+
+#[derive(Debug)]
+pub struct PersonalDataExportRepository;
+
+#[async_trait]
+impl contracts::repository::PersonalDataExportRepository for PersonalDataExportRepository {
+    #[tracing::instrument(name = "PersonalDataExportRepository.create", skip_all, fields(
+        id = %input.id,
+        user_id = %input.user_id
+    ))]
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::user::NewPersonalDataExport,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            "INSERT INTO personal_data_exports (id, user_id)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id) WHERE completed_at IS NULL DO NOTHING",
+            &input.id,
+            &input.user_id,
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[tracing::instrument(name = "PersonalDataExportRepository.get_latest_by_user", skip_all, fields(
+        user_id = %user_id
+    ))]
+    async fn get_latest_by_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+    ) -> Result<Option<commands::user::PersonalDataExport>> {
+        let row = sqlx::query!(
+            "SELECT id, object_key, expires_at, completed_at, created_at
+            FROM personal_data_exports
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            LIMIT 1",
+            &user_id
+        )
+        .fetch_optional_ex(executor)
+        .await?;
+
+        match row {
+            None => Ok(None),
+            Some(row) => Ok(Some(commands::user::PersonalDataExport {
+                id: row.try_get("id")?,
+                object_key: row.try_get("object_key")?,
+                expires_at: row.try_get("expires_at")?,
+                completed_at: row.try_get("completed_at")?,
+                created_at: row.try_get("created_at")?,
+            })),
+        }
+    }
+
+    #[tracing::instrument(name = "PersonalDataExportRepository.get_by_id", skip_all, fields(
+        user_id = %user_id,
+        id = %id
+    ))]
+    async fn get_by_id<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<commands::user::PersonalDataExport>> {
+        let row = sqlx::query!(
+            "SELECT id, object_key, expires_at, completed_at, created_at
+            FROM personal_data_exports
+            WHERE id = $1 AND user_id = $2",
+            &id,
+            &user_id
+        )
+        .fetch_optional_ex(executor)
+        .await?;
+
+        match row {
+            None => Ok(None),
+            Some(row) => Ok(Some(commands::user::PersonalDataExport {
+                id: row.try_get("id")?,
+                object_key: row.try_get("object_key")?,
+                expires_at: row.try_get("expires_at")?,
+                completed_at: row.try_get("completed_at")?,
+                created_at: row.try_get("created_at")?,
+            })),
+        }
+    }
+
+    #[tracing::instrument(name = "PersonalDataExportRepository.get_pending_for_update", skip_all)]
+    async fn get_pending_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+    ) -> Result<Option<commands::user::PendingPersonalDataExport>> {
+        let row = sqlx::query!(
+            "SELECT personal_data_exports.id, personal_data_exports.user_id
+            FROM personal_data_exports
+            INNER JOIN users ON users.id = personal_data_exports.user_id
+            WHERE personal_data_exports.completed_at IS NULL
+            AND users.deleted_at IS NULL
+            ORDER BY personal_data_exports.created_at
+            LIMIT 1
+            FOR UPDATE OF personal_data_exports SKIP LOCKED"
+        )
+        .fetch_optional_ex(executor)
+        .await?;
+
+        match row {
+            None => Ok(None),
+            Some(row) => Ok(Some(commands::user::PendingPersonalDataExport {
+                id: row.try_get("id")?,
+                user_id: row.try_get("user_id")?,
+            })),
+        }
+    }
+
+    #[tracing::instrument(name = "PersonalDataExportRepository.mark_as_completed", skip_all, fields(
+        id = %id,
+        expires_at = %expires_at
+    ))]
+    async fn mark_as_completed<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+        object_key: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query!(
+            "UPDATE personal_data_exports
+            SET object_key = $2, expires_at = $3, completed_at = CURRENT_TIMESTAMP
+            WHERE id = $1",
+            &id,
+            object_key,
+            &expires_at
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+}
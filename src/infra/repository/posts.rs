@@ -0,0 +1,253 @@
+use crate::domain::{
+    commands,
+    contracts::{
+        self,
+        repository::{Executor, SqlxExt},
+    },
+    queries::timeline::get_timeline::Post,
+    value_objects::post_cursor::PostCursor,
+};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::Row;
+
+#[derive(Debug)]
+pub struct PostRepository;
+
+#[async_trait]
+impl contracts::repository::PostRepository for PostRepository {
+    #[tracing::instrument(name = "PostRepository.get_by_id", skip_all, fields(
+        id = %id
+    ))]
+    async fn get_by_id<'c>(&self, executor: &mut Executor<'c>, id: Uuid) -> Result<Option<Post>> {
+        let row = sqlx::query!(
+            "SELECT
+                users.username as user_username,
+                posts.id as post_id,
+                posts.creator_id as post_creator_id,
+                posts.description as post_description,
+                posts.video_key as post_video_key,
+                posts.likes as post_likes,
+                posts.paid as post_paid,
+                posts.thumbnail_url as post_thumbnail_url,
+                posts.duration_secs as post_duration_secs,
+                posts.published_at as post_published_at,
+                posts.pinned_at IS NOT NULL as post_pinned,
+                posts.created_at as post_created_at
+            FROM posts
+            INNER JOIN users
+            ON users.id = posts.creator_id
+            WHERE posts.id = $1
+            AND users.banned_at IS NULL
+            AND users.deleted_at IS NULL",
+            &id
+        )
+        .fetch_optional_ex(executor)
+        .await?;
+
+        match row {
+            None => Ok(None),
+            Some(row) => Ok(Some(Post::try_from(row)?)),
+        }
+    }
+
+    #[tracing::instrument(name = "PostRepository.create", skip_all, fields(
+        id = %input.id,
+        creator_id = %input.creator_id
+    ))]
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::post::NewPost,
+    ) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO posts (
+                id,
+                creator_id,
+                description,
+                video_key,
+                likes,
+                paid,
+                thumbnail_url,
+                duration_secs
+            ) VALUES (
+                $1, $2, $3, $4, 0, $5, $6, $7
+            )",
+            &input.id,
+            &input.creator_id,
+            &input.description,
+            &input.video_key,
+            input.paid,
+            &input.thumbnail_url,
+            input.duration_secs,
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "PostRepository.list_by_creator", skip_all, fields(
+        creator_id = %creator_id
+    ))]
+    async fn list_by_creator<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        creator_id: Uuid,
+    ) -> Result<Vec<commands::user::PersonalDataPost>> {
+        let rows = sqlx::query!(
+            "SELECT
+                id,
+                description,
+                video_key,
+                likes,
+                paid,
+                created_at
+            FROM posts
+            WHERE creator_id = $1
+            ORDER BY created_at",
+            &creator_id
+        )
+        .fetch_all_ex(executor)
+        .await?;
+
+        let mut posts = Vec::with_capacity(rows.len());
+
+        for row in rows {
+            posts.push(commands::user::PersonalDataPost {
+                id: row.try_get("id")?,
+                description: row.try_get("description")?,
+                video_key: row.try_get("video_key")?,
+                likes: row.try_get("likes")?,
+                paid: row.try_get("paid")?,
+                created_at: row.try_get("created_at")?,
+            });
+        }
+
+        Ok(posts)
+    }
+
+    #[tracing::instrument(name = "PostRepository.list_pinned_by_creator", skip_all, fields(
+        creator_id = %creator_id,
+        include_unpublished = include_unpublished,
+        limit = limit
+    ))]
+    async fn list_pinned_by_creator<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        creator_id: Uuid,
+        include_unpublished: bool,
+        limit: i64,
+    ) -> Result<Vec<Post>> {
+        let rows = sqlx::query!(
+            "SELECT
+                users.username as user_username,
+                posts.id as post_id,
+                posts.creator_id as post_creator_id,
+                posts.description as post_description,
+                posts.video_key as post_video_key,
+                posts.likes as post_likes,
+                posts.paid as post_paid,
+                posts.thumbnail_url as post_thumbnail_url,
+                posts.duration_secs as post_duration_secs,
+                posts.published_at as post_published_at,
+                posts.pinned_at IS NOT NULL as post_pinned,
+                posts.created_at as post_created_at
+            FROM posts
+            INNER JOIN users
+            ON users.id = posts.creator_id
+            WHERE posts.creator_id = $1
+            AND posts.pinned_at IS NOT NULL
+            AND users.banned_at IS NULL
+            AND users.deleted_at IS NULL
+            AND ($2 OR posts.published_at <= CURRENT_TIMESTAMP)
+            ORDER BY posts.pinned_at DESC, posts.id DESC
+            LIMIT $3",
+            &creator_id,
+            include_unpublished,
+            limit
+        )
+        .fetch_all_ex(executor)
+        .await?;
+
+        let mut posts: Vec<Post> = Vec::with_capacity(rows.len());
+
+        for row in rows {
+            posts.push(Post::try_from(row)?);
+        }
+
+        Ok(posts)
+    }
+
+    #[tracing::instrument(name = "PostRepository.list_feed_by_creator", skip_all, fields(
+        creator_id = %creator_id,
+        include_unpublished = include_unpublished,
+        pinned_limit = pinned_limit,
+        cursor = ?cursor,
+        limit = limit
+    ))]
+    async fn list_feed_by_creator<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        creator_id: Uuid,
+        include_unpublished: bool,
+        pinned_limit: i64,
+        cursor: Option<PostCursor>,
+        limit: i64,
+    ) -> Result<Vec<Post>> {
+        let rows = sqlx::query!(
+            "SELECT
+                users.username as user_username,
+                posts.id as post_id,
+                posts.creator_id as post_creator_id,
+                posts.description as post_description,
+                posts.video_key as post_video_key,
+                posts.likes as post_likes,
+                posts.paid as post_paid,
+                posts.thumbnail_url as post_thumbnail_url,
+                posts.duration_secs as post_duration_secs,
+                posts.published_at as post_published_at,
+                posts.pinned_at IS NOT NULL as post_pinned,
+                posts.created_at as post_created_at
+            FROM posts
+            INNER JOIN users
+            ON users.id = posts.creator_id
+            WHERE posts.creator_id = $1
+            AND users.banned_at IS NULL
+            AND users.deleted_at IS NULL
+            AND ($2 OR posts.published_at <= CURRENT_TIMESTAMP)
+            AND posts.id NOT IN (
+                SELECT pinned.id
+                FROM posts pinned
+                WHERE pinned.creator_id = $1
+                AND pinned.pinned_at IS NOT NULL
+                AND ($2 OR pinned.published_at <= CURRENT_TIMESTAMP)
+                ORDER BY pinned.pinned_at DESC, pinned.id DESC
+                LIMIT $3
+            )
+            AND (COALESCE(posts.published_at, posts.created_at), posts.id) < (
+                COALESCE($4, 'infinity'::TIMESTAMP WITH TIME ZONE),
+                COALESCE($5, 'ffffffff-ffff-ffff-ffff-ffffffffffff'::uuid)
+            )
+            ORDER BY COALESCE(posts.published_at, posts.created_at) DESC, posts.id DESC
+            LIMIT $6",
+            &creator_id,
+            include_unpublished,
+            pinned_limit,
+            cursor.as_ref().map(|cursor| cursor.published_at),
+            cursor.as_ref().map(|cursor| cursor.id),
+            limit
+        )
+        .fetch_all_ex(executor)
+        .await?;
+
+        let mut posts: Vec<Post> = Vec::with_capacity(rows.len());
+
+        for row in rows {
+            posts.push(Post::try_from(row)?);
+        }
+
+        Ok(posts)
+    }
+}
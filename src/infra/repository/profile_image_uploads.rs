@@ -0,0 +1,104 @@
+use crate::domain::{
+    commands,
+    contracts::{
+        self,
+        repository::{Executor, SqlxExt},
+    },
+};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::Row;
+
+#[derive(Debug)]
+pub struct ProfileImageUploadRepository;
+
+#[async_trait]
+impl contracts::repository::ProfileImageUploadRepository for ProfileImageUploadRepository {
+    #[tracing::instrument(name = "ProfileImageUploadRepository.create", skip_all, fields(
+        id = %input.id,
+        user_id = %input.user_id
+    ))]
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::user::NewProfileImageUpload,
+    ) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO profile_image_uploads (
+                id,
+                user_id,
+                kind,
+                object_key,
+                content_type,
+                expires_at
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6
+            )",
+            &input.id,
+            &input.user_id,
+            input.kind.as_str(),
+            &input.object_key,
+            input.content_type.as_str(),
+            &input.expires_at,
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "ProfileImageUploadRepository.get_for_update", skip_all, fields(
+        user_id = %user_id,
+        id = %id
+    ))]
+    async fn get_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<commands::user::ProfileImageUpload>> {
+        let row = sqlx::query!(
+            "SELECT
+                id,
+                kind,
+                object_key,
+                content_type,
+                expires_at,
+                confirmed_at IS NOT NULL as confirmed
+            FROM profile_image_uploads
+            WHERE id = $1 AND user_id = $2
+            FOR UPDATE",
+            &id,
+            &user_id
+        )
+        .fetch_optional_ex(executor)
+        .await?;
+
+        match row {
+            None => Ok(None),
+            Some(row) => Ok(Some(commands::user::ProfileImageUpload {
+                id: row.try_get("id")?,
+                kind: row.try_get::<&str, _>("kind")?.parse()?,
+                object_key: row.try_get("object_key")?,
+                content_type: row.try_get::<&str, _>("content_type")?.parse()?,
+                expires_at: row.try_get("expires_at")?,
+                confirmed: row.try_get("confirmed")?,
+            })),
+        }
+    }
+
+    #[tracing::instrument(name = "ProfileImageUploadRepository.mark_as_confirmed", skip_all, fields(
+        id = %id
+    ))]
+    async fn mark_as_confirmed<'c>(&self, executor: &mut Executor<'c>, id: Uuid) -> Result<()> {
+        sqlx::query!(
+            "UPDATE profile_image_uploads SET confirmed_at = CURRENT_TIMESTAMP WHERE id = $1",
+            &id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+}
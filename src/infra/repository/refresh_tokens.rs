@@ -0,0 +1,203 @@
+use crate::domain::{
+    commands,
+    contracts::{
+        self,
+        repository::{Executor, SqlxExt},
+    },
+};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, Row};
+
+#[derive(Debug)]
+pub struct RefreshTokenRepository;
+
+#[async_trait]
+impl contracts::repository::RefreshTokenRepository for RefreshTokenRepository {
+    #[tracing::instrument(name = "RefreshTokenRepository.create", skip_all, fields(
+        user_id = %input.user_id,
+        family_id = %input.family_id
+    ))]
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::session::NewRefreshToken,
+    ) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO refresh_tokens (
+                id,
+                user_id,
+                family_id,
+                token_hash,
+                expires_at,
+                mfa_authenticated
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6
+            )",
+            &input.id,
+            &input.user_id,
+            &input.family_id,
+            &input.token_hash,
+            &input.expires_at,
+            &input.mfa_authenticated,
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "RefreshTokenRepository.get_by_token_hash_for_update", skip_all)]
+    async fn get_by_token_hash_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        token_hash: &str,
+    ) -> Result<Option<commands::session::RefreshToken>> {
+        let row = sqlx::query!(
+            "SELECT 
+                id,
+                user_id,
+                family_id,
+                expires_at,
+                used_at,
+                revoked_at,
+                mfa_authenticated
+            FROM refresh_tokens
+            WHERE token_hash = $1
+            FOR UPDATE",
+            token_hash
+        )
+        .fetch_optional_ex(executor)
+        .await?;
+
+        match row {
+            None => Ok(None),
+            Some(row) => Ok(Some(commands::session::RefreshToken::try_from(row)?)),
+        }
+    }
+
+    #[tracing::instrument(name = "RefreshTokenRepository.mark_as_used", skip_all, fields(id = %id))]
+    async fn mark_as_used<'c>(&self, executor: &mut Executor<'c>, id: Uuid) -> Result<()> {
+        sqlx::query!(
+            "UPDATE refresh_tokens SET used_at = CURRENT_TIMESTAMP WHERE id = $1",
+            &id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "RefreshTokenRepository.revoke_family", skip_all, fields(
+        family_id = %family_id
+    ))]
+    async fn revoke_family<'c>(&self, executor: &mut Executor<'c>, family_id: Uuid) -> Result<()> {
+        sqlx::query!(
+            "UPDATE refresh_tokens
+            SET revoked_at = CURRENT_TIMESTAMP
+            WHERE family_id = $1 AND revoked_at IS NULL",
+            &family_id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "RefreshTokenRepository.revoke_all_for_user", skip_all, fields(
+        user_id = %user_id
+    ))]
+    async fn revoke_all_for_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+    ) -> Result<()> {
+        sqlx::query!(
+            "UPDATE refresh_tokens
+            SET revoked_at = CURRENT_TIMESTAMP
+            WHERE user_id = $1 AND revoked_at IS NULL",
+            &user_id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "RefreshTokenRepository.is_family_revoked", skip_all, fields(
+        family_id = %family_id
+    ))]
+    async fn is_family_revoked<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        family_id: Uuid,
+    ) -> Result<bool> {
+        let row = sqlx::query!(
+            "SELECT EXISTS (
+                SELECT 1 FROM refresh_tokens
+                WHERE family_id = $1 AND revoked_at IS NOT NULL
+            ) as revoked",
+            &family_id
+        )
+        .fetch_one_ex(executor)
+        .await?;
+
+        let revoked: Option<bool> = row.try_get("revoked")?;
+
+        Ok(revoked.unwrap_or(false))
+    }
+
+    #[tracing::instrument(name = "RefreshTokenRepository.list_sessions_by_user", skip_all, fields(
+        user_id = %user_id
+    ))]
+    async fn list_sessions_by_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+    ) -> Result<Vec<commands::user::PersonalDataSession>> {
+        let rows = sqlx::query!(
+            "SELECT
+                family_id,
+                MIN(created_at) as started_at,
+                MAX(created_at) as last_refreshed_at,
+                BOOL_OR(revoked_at IS NOT NULL) as revoked
+            FROM refresh_tokens
+            WHERE user_id = $1
+            GROUP BY family_id
+            ORDER BY started_at",
+            &user_id
+        )
+        .fetch_all_ex(executor)
+        .await?;
+
+        let mut sessions = Vec::with_capacity(rows.len());
+
+        for row in rows {
+            sessions.push(commands::user::PersonalDataSession {
+                id: row.try_get("family_id")?,
+                started_at: row.try_get("started_at")?,
+                last_refreshed_at: row.try_get("last_refreshed_at")?,
+                revoked: row.try_get("revoked")?,
+            });
+        }
+
+        Ok(sessions)
+    }
+}
+
+impl TryFrom<PgRow> for commands::session::RefreshToken {
+    type Error = anyhow::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            user_id: row.try_get("user_id")?,
+            family_id: row.try_get("family_id")?,
+            expires_at: row.try_get("expires_at")?,
+            used_at: row.try_get("used_at")?,
+            revoked_at: row.try_get("revoked_at")?,
+            mfa_authenticated: row.try_get("mfa_authenticated")?,
+        })
+    }
+}
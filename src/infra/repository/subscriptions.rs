@@ -0,0 +1,68 @@
+use crate::{
+    domain::contracts::{
+        self,
+        repository::{Executor, SqlxExt},
+    },
+    infra::uuid::Uuid,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::Row;
+
+#[derive(Debug)]
+pub struct SubscriptionRepository;
+
+#[async_trait]
+impl contracts::repository::SubscriptionRepository for SubscriptionRepository {
+    #[tracing::instrument(name = "SubscriptionRepository.list_active_creator_ids", skip_all, fields(
+        subscriber_id = %subscriber_id,
+        creator_ids = ?creator_ids
+    ))]
+    async fn list_active_creator_ids<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        subscriber_id: Uuid,
+        creator_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>> {
+        let rows = sqlx::query!(
+            "SELECT DISTINCT creator_id
+            FROM subscriptions
+            WHERE subscriber_id = $1
+            AND creator_id = ANY($2)
+            AND expires_at > CURRENT_TIMESTAMP",
+            &subscriber_id,
+            creator_ids,
+        )
+        .fetch_all_ex(executor)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Ok(row.try_get("creator_id")?))
+            .collect()
+    }
+
+    #[tracing::instrument(name = "SubscriptionRepository.end_between", skip_all, fields(
+        user_id = %user_id,
+        other_user_id = %other_user_id
+    ))]
+    async fn end_between<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+        other_user_id: Uuid,
+    ) -> Result<u64> {
+        let result = sqlx::query!(
+            "UPDATE subscriptions
+            SET expires_at = CURRENT_TIMESTAMP
+            WHERE ((subscriber_id = $1 AND creator_id = $2)
+            OR (subscriber_id = $2 AND creator_id = $1))
+            AND expires_at > CURRENT_TIMESTAMP",
+            &user_id,
+            &other_user_id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
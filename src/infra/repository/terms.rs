@@ -0,0 +1,66 @@
+use crate::domain::{
+    commands,
+    contracts::{
+        self,
+        repository::{Executor, SqlxExt},
+    },
+    queries::terms::get_current::TermsVersion,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::Row;
+
+#[derive(Debug)]
+pub struct TermsRepository;
+
+#[async_trait]
+impl contracts::repository::TermsRepository for TermsRepository {
+    #[tracing::instrument(name = "TermsRepository.get_current", skip_all)]
+    async fn get_current<'c>(&self, executor: &mut Executor<'c>) -> Result<Option<TermsVersion>> {
+        let row = sqlx::query!(
+            "SELECT version, published_at
+            FROM terms_versions
+            WHERE published_at <= CURRENT_TIMESTAMP
+            ORDER BY published_at DESC
+            LIMIT 1"
+        )
+        .fetch_optional_ex(executor)
+        .await?;
+
+        match row {
+            None => Ok(None),
+            Some(row) => Ok(Some(TermsVersion {
+                version: row.try_get("version")?,
+                published_at: row.try_get("published_at")?,
+            })),
+        }
+    }
+
+    #[tracing::instrument(name = "TermsRepository.create_acceptance", skip_all, fields(
+        id = %input.id,
+        user_id = %input.user_id,
+        terms_version = %input.terms_version
+    ))]
+    async fn create_acceptance<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::terms::NewTermsAcceptance,
+    ) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO terms_acceptances (
+                id,
+                user_id,
+                terms_version
+            ) VALUES (
+                $1, $2, $3
+            )",
+            &input.id,
+            &input.user_id,
+            &input.terms_version,
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+}
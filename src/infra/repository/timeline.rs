@@ -8,8 +8,8 @@ use crate::{
             self,
             repository::{Executor, SqlxExt},
         },
-        queries::timeline::get_timeline::Post,
-        value_objects::cursor::Cursor,
+        queries::timeline::get_timeline::{Post, PostPreview},
+        value_objects::post_cursor::PostCursor,
     },
     infra::uuid::Uuid,
 };
@@ -20,30 +20,131 @@ pub struct TimelineRepository;
 #[async_trait]
 impl contracts::repository::TimelineRepository for TimelineRepository {
     #[tracing::instrument(name = "TimelineRepository::get_timeline", skip_all, fields(
-        cursor = ?cursor
+        viewer_id = ?viewer_id,
+        cursor = ?cursor,
+        limit = limit
     ))]
     async fn get_timeline<'c>(
         &self,
         executor: &mut Executor<'c>,
-        cursor: Cursor,
+        viewer_id: Option<Uuid>,
+        cursor: Option<PostCursor>,
+        limit: i64,
     ) -> Result<Vec<Post>> {
         let rows = sqlx::query!(
             "SELECT 
                 users.username as user_username,
                 posts.id as post_id,
+                posts.creator_id as post_creator_id,
                 posts.description as post_description,
-                posts.video_url as post_video_url,
+                posts.video_key as post_video_key,
                 posts.likes as post_likes,
                 posts.paid as post_paid,
+                posts.thumbnail_url as post_thumbnail_url,
+                posts.duration_secs as post_duration_secs,
+                posts.published_at as post_published_at,
+                posts.pinned_at IS NOT NULL as post_pinned,
                 posts.created_at as post_created_at
             FROM posts 
             INNER JOIN users
             ON users.id = posts.creator_id
-            ORDER BY posts.created_at DESC
-            OFFSET $1 LIMIT $2;
+            WHERE users.banned_at IS NULL
+            AND users.deleted_at IS NULL
+            AND posts.published_at <= CURRENT_TIMESTAMP
+            AND NOT EXISTS (
+                SELECT 1 FROM user_blocks
+                WHERE (user_blocks.blocker_id = $3 AND user_blocks.blocked_id = posts.creator_id)
+                OR (user_blocks.blocker_id = posts.creator_id AND user_blocks.blocked_id = $3)
+            )
+            AND NOT EXISTS (
+                SELECT 1 FROM user_mutes
+                WHERE user_mutes.muter_id = $3 AND user_mutes.muted_id = posts.creator_id
+            )
+            -- Without a cursor every post comes after the position.
+            AND (posts.published_at, posts.id) < (
+                COALESCE($1, 'infinity'::TIMESTAMP WITH TIME ZONE),
+                COALESCE($2, 'ffffffff-ffff-ffff-ffff-ffffffffffff'::uuid)
+            )
+            ORDER BY posts.published_at DESC, posts.id DESC
+            LIMIT $4;
             ",
-            cursor.offset,
-            cursor.limit
+            cursor.as_ref().map(|cursor| cursor.published_at),
+            cursor.as_ref().map(|cursor| cursor.id),
+            viewer_id.as_ref(),
+            limit
+        )
+        .fetch_all_ex(executor)
+        .await?;
+
+        let mut posts: Vec<Post> = Vec::with_capacity(rows.len());
+
+        for row in rows {
+            posts.push(Post::try_from(row)?);
+        }
+
+        Ok(posts)
+    }
+
+    #[tracing::instrument(name = "TimelineRepository::get_following_timeline", skip_all, fields(
+        viewer_id = %viewer_id,
+        cursor = ?cursor,
+        limit = limit
+    ))]
+    async fn get_following_timeline<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        viewer_id: Uuid,
+        cursor: Option<PostCursor>,
+        limit: i64,
+    ) -> Result<Vec<Post>> {
+        let rows = sqlx::query!(
+            "SELECT 
+                users.username as user_username,
+                posts.id as post_id,
+                posts.creator_id as post_creator_id,
+                posts.description as post_description,
+                posts.video_key as post_video_key,
+                posts.likes as post_likes,
+                posts.paid as post_paid,
+                posts.thumbnail_url as post_thumbnail_url,
+                posts.duration_secs as post_duration_secs,
+                posts.published_at as post_published_at,
+                posts.pinned_at IS NOT NULL as post_pinned,
+                posts.created_at as post_created_at
+            FROM posts 
+            INNER JOIN users
+            ON users.id = posts.creator_id
+            WHERE posts.creator_id IN (
+                SELECT user_followers.followee_id FROM user_followers
+                WHERE user_followers.follower_id = $3
+                UNION
+                SELECT subscriptions.creator_id FROM subscriptions
+                WHERE subscriptions.subscriber_id = $3
+                AND subscriptions.expires_at > CURRENT_TIMESTAMP
+            )
+            AND users.banned_at IS NULL
+            AND users.deleted_at IS NULL
+            AND posts.published_at <= CURRENT_TIMESTAMP
+            AND NOT EXISTS (
+                SELECT 1 FROM user_blocks
+                WHERE (user_blocks.blocker_id = $3 AND user_blocks.blocked_id = posts.creator_id)
+                OR (user_blocks.blocker_id = posts.creator_id AND user_blocks.blocked_id = $3)
+            )
+            AND NOT EXISTS (
+                SELECT 1 FROM user_mutes
+                WHERE user_mutes.muter_id = $3 AND user_mutes.muted_id = posts.creator_id
+            )
+            AND (posts.published_at, posts.id) < (
+                COALESCE($1, 'infinity'::TIMESTAMP WITH TIME ZONE),
+                COALESCE($2, 'ffffffff-ffff-ffff-ffff-ffffffffffff'::uuid)
+            )
+            ORDER BY posts.published_at DESC, posts.id DESC
+            LIMIT $4;
+            ",
+            cursor.as_ref().map(|cursor| cursor.published_at),
+            cursor.as_ref().map(|cursor| cursor.id),
+            &viewer_id,
+            limit
         )
         .fetch_all_ex(executor)
         .await?;
@@ -68,11 +169,20 @@ impl TryFrom<PgRow> for Post {
 
                 Uuid::from_slice(id.as_bytes())?
             },
+            creator_id: row.try_get("post_creator_id")?,
             creator_username: row.try_get("user_username")?,
             description: row.try_get("post_description")?,
-            video_url: row.try_get("post_video_url")?,
+            video_key: row.try_get("post_video_key")?,
             likes: row.try_get("post_likes")?,
             paid: row.try_get("post_paid")?,
+            preview: PostPreview {
+                thumbnail_url: row.try_get("post_thumbnail_url")?,
+                duration_secs: row.try_get("post_duration_secs")?,
+            },
+            published_at: row.try_get("post_published_at")?,
+            pinned: row.try_get("post_pinned")?,
+            // Set by the paywall when the viewer cannot watch it.
+            locked: false,
             created_at: row.try_get("post_created_at")?,
         })
     }
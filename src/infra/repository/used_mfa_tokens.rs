@@ -0,0 +1,39 @@
+use crate::domain::contracts::{
+    self,
+    repository::{Executor, SqlxExt},
+};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug)]
+pub struct UsedMfaTokenRepository;
+
+#[async_trait]
+impl contracts::repository::UsedMfaTokenRepository for UsedMfaTokenRepository {
+    #[tracing::instrument(name = "UsedMfaTokenRepository.use_token", skip_all, fields(
+        jti = %jti,
+        user_id = %user_id
+    ))]
+    async fn use_token<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        jti: Uuid,
+        user_id: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            "INSERT INTO used_mfa_tokens (jti, user_id, expires_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (jti) DO NOTHING",
+            &jti,
+            &user_id,
+            &expires_at
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
@@ -0,0 +1,48 @@
+use crate::domain::{
+    commands,
+    contracts::{
+        self,
+        repository::{Executor, SqlxExt},
+    },
+};
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[derive(Debug)]
+pub struct UserBanRepository;
+
+#[async_trait]
+impl contracts::repository::UserBanRepository for UserBanRepository {
+    #[tracing::instrument(name = "UserBanRepository.create", skip_all, fields(
+        id = %input.id,
+        user_id = %input.user_id,
+        admin_id = %input.admin_id,
+        action = input.action.as_str()
+    ))]
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::moderation::NewUserBan,
+    ) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO user_bans (
+                id,
+                user_id,
+                admin_id,
+                action,
+                reason
+            ) VALUES (
+                $1, $2, $3, $4, $5
+            )",
+            &input.id,
+            &input.user_id,
+            &input.admin_id,
+            input.action.as_str(),
+            &input.reason,
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+}
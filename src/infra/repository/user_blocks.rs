@@ -0,0 +1,135 @@
+use crate::domain::{
+    contracts::{
+        self,
+        repository::{Executor, SqlxExt},
+    },
+    queries::relationship::list_blocked::RelatedUser,
+    value_objects::cursor::Cursor,
+};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, Row};
+
+#[derive(Debug)]
+pub struct UserBlockRepository;
+
+#[async_trait]
+impl contracts::repository::UserBlockRepository for UserBlockRepository {
+    #[tracing::instrument(name = "UserBlockRepository.create", skip_all, fields(
+        blocker_id = %blocker_id,
+        blocked_id = %blocked_id
+    ))]
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        blocker_id: Uuid,
+        blocked_id: Uuid,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            "INSERT INTO user_blocks (blocker_id, blocked_id)
+            VALUES ($1, $2)
+            ON CONFLICT (blocker_id, blocked_id) DO NOTHING",
+            &blocker_id,
+            &blocked_id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[tracing::instrument(name = "UserBlockRepository.delete", skip_all, fields(
+        blocker_id = %blocker_id,
+        blocked_id = %blocked_id
+    ))]
+    async fn delete<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        blocker_id: Uuid,
+        blocked_id: Uuid,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            "DELETE FROM user_blocks WHERE blocker_id = $1 AND blocked_id = $2",
+            &blocker_id,
+            &blocked_id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[tracing::instrument(name = "UserBlockRepository.is_blocked_between", skip_all, fields(
+        user_id = %user_id,
+        other_user_id = %other_user_id
+    ))]
+    async fn is_blocked_between<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+        other_user_id: Uuid,
+    ) -> Result<bool> {
+        let row = sqlx::query!(
+            "SELECT EXISTS (
+                SELECT 1 FROM user_blocks
+                WHERE (blocker_id = $1 AND blocked_id = $2)
+                OR (blocker_id = $2 AND blocked_id = $1)
+            ) as blocked",
+            &user_id,
+            &other_user_id
+        )
+        .fetch_one_ex(executor)
+        .await?;
+
+        let blocked: Option<bool> = row.try_get("blocked")?;
+
+        Ok(blocked.unwrap_or(false))
+    }
+
+    #[tracing::instrument(name = "UserBlockRepository.list_by_blocker", skip_all, fields(
+        blocker_id = %blocker_id,
+        cursor = ?cursor
+    ))]
+    async fn list_by_blocker<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        blocker_id: Uuid,
+        cursor: Cursor,
+    ) -> Result<Vec<RelatedUser>> {
+        let rows = sqlx::query!(
+            "SELECT
+                users.id,
+                users.username,
+                users.display_name,
+                users.avatar_url,
+                user_blocks.created_at as since
+            FROM user_blocks
+            INNER JOIN users ON users.id = user_blocks.blocked_id
+            WHERE user_blocks.blocker_id = $1 AND users.deleted_at IS NULL
+            ORDER BY user_blocks.created_at DESC, users.id
+            OFFSET $2 LIMIT $3",
+            &blocker_id,
+            cursor.offset,
+            cursor.limit
+        )
+        .fetch_all_ex(executor)
+        .await?;
+
+        rows.into_iter().map(RelatedUser::try_from).collect()
+    }
+}
+
+impl TryFrom<PgRow> for RelatedUser {
+    type Error = anyhow::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            username: row.try_get("username")?,
+            display_name: row.try_get("display_name")?,
+            avatar_url: row.try_get("avatar_url")?,
+            since: row.try_get("since")?,
+        })
+    }
+}
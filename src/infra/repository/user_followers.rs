@@ -0,0 +1,160 @@
+use crate::domain::{
+    contracts::{
+        self,
+        repository::{Executor, SqlxExt},
+    },
+    queries::follow::list_followers::FollowUser,
+    value_objects::cursor::Cursor,
+};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, Row};
+
+#[derive(Debug)]
+pub struct UserFollowerRepository;
+
+#[async_trait]
+impl contracts::repository::UserFollowerRepository for UserFollowerRepository {
+    #[tracing::instrument(name = "UserFollowerRepository.create", skip_all, fields(
+        follower_id = %follower_id,
+        followee_id = %followee_id
+    ))]
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        follower_id: Uuid,
+        followee_id: Uuid,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            "INSERT INTO user_followers (follower_id, followee_id)
+            VALUES ($1, $2)
+            ON CONFLICT (follower_id, followee_id) DO NOTHING",
+            &follower_id,
+            &followee_id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[tracing::instrument(name = "UserFollowerRepository.delete", skip_all, fields(
+        follower_id = %follower_id,
+        followee_id = %followee_id
+    ))]
+    async fn delete<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        follower_id: Uuid,
+        followee_id: Uuid,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            "DELETE FROM user_followers WHERE follower_id = $1 AND followee_id = $2",
+            &follower_id,
+            &followee_id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[tracing::instrument(name = "UserFollowerRepository.list_followers", skip_all, fields(
+        viewer_id = ?viewer_id,
+        user_id = %user_id,
+        cursor = ?cursor
+    ))]
+    async fn list_followers<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        viewer_id: Option<Uuid>,
+        user_id: Uuid,
+        cursor: Cursor,
+    ) -> Result<Vec<FollowUser>> {
+        let rows = sqlx::query!(
+            "SELECT
+                users.id,
+                users.username,
+                users.display_name,
+                users.avatar_url,
+                user_followers.created_at as followed_at
+            FROM user_followers
+            INNER JOIN users ON users.id = user_followers.follower_id
+            WHERE user_followers.followee_id = $1
+            AND users.deleted_at IS NULL
+            AND users.banned_at IS NULL
+            AND NOT EXISTS (
+                SELECT 1 FROM user_blocks
+                WHERE (user_blocks.blocker_id = $4 AND user_blocks.blocked_id = users.id)
+                OR (user_blocks.blocker_id = users.id AND user_blocks.blocked_id = $4)
+            )
+            ORDER BY user_followers.created_at DESC, users.id
+            OFFSET $2 LIMIT $3",
+            &user_id,
+            cursor.offset,
+            cursor.limit,
+            viewer_id.as_ref()
+        )
+        .fetch_all_ex(executor)
+        .await?;
+
+        rows.into_iter().map(FollowUser::try_from).collect()
+    }
+
+    #[tracing::instrument(name = "UserFollowerRepository.list_following", skip_all, fields(
+        viewer_id = ?viewer_id,
+        user_id = %user_id,
+        cursor = ?cursor
+    ))]
+    async fn list_following<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        viewer_id: Option<Uuid>,
+        user_id: Uuid,
+        cursor: Cursor,
+    ) -> Result<Vec<FollowUser>> {
+        let rows = sqlx::query!(
+            "SELECT
+                users.id,
+                users.username,
+                users.display_name,
+                users.avatar_url,
+                user_followers.created_at as followed_at
+            FROM user_followers
+            INNER JOIN users ON users.id = user_followers.followee_id
+            WHERE user_followers.follower_id = $1
+            AND users.deleted_at IS NULL
+            AND users.banned_at IS NULL
+            AND NOT EXISTS (
+                SELECT 1 FROM user_blocks
+                WHERE (user_blocks.blocker_id = $4 AND user_blocks.blocked_id = users.id)
+                OR (user_blocks.blocker_id = users.id AND user_blocks.blocked_id = $4)
+            )
+            ORDER BY user_followers.created_at DESC, users.id
+            OFFSET $2 LIMIT $3",
+            &user_id,
+            cursor.offset,
+            cursor.limit,
+            viewer_id.as_ref()
+        )
+        .fetch_all_ex(executor)
+        .await?;
+
+        rows.into_iter().map(FollowUser::try_from).collect()
+    }
+}
+
+impl TryFrom<PgRow> for FollowUser {
+    type Error = anyhow::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            username: row.try_get("username")?,
+            display_name: row.try_get("display_name")?,
+            avatar_url: row.try_get("avatar_url")?,
+            followed_at: row.try_get("followed_at")?,
+        })
+    }
+}
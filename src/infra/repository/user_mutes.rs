@@ -0,0 +1,93 @@
+use crate::domain::{
+    contracts::{
+        self,
+        repository::{Executor, SqlxExt},
+    },
+    queries::relationship::list_blocked::RelatedUser,
+    value_objects::cursor::Cursor,
+};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[derive(Debug)]
+pub struct UserMuteRepository;
+
+#[async_trait]
+impl contracts::repository::UserMuteRepository for UserMuteRepository {
+    #[tracing::instrument(name = "UserMuteRepository.create", skip_all, fields(
+        muter_id = %muter_id,
+        muted_id = %muted_id
+    ))]
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        muter_id: Uuid,
+        muted_id: Uuid,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            "INSERT INTO user_mutes (muter_id, muted_id)
+            VALUES ($1, $2)
+            ON CONFLICT (muter_id, muted_id) DO NOTHING",
+            &muter_id,
+            &muted_id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[tracing::instrument(name = "UserMuteRepository.delete", skip_all, fields(
+        muter_id = %muter_id,
+        muted_id = %muted_id
+    ))]
+    async fn delete<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        muter_id: Uuid,
+        muted_id: Uuid,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            "DELETE FROM user_mutes WHERE muter_id = $1 AND muted_id = $2",
+            &muter_id,
+            &muted_id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[tracing::instrument(name = "UserMuteRepository.list_by_muter", skip_all, fields(
+        muter_id = %muter_id,
+        cursor = ?cursor
+    ))]
+    async fn list_by_muter<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        muter_id: Uuid,
+        cursor: Cursor,
+    ) -> Result<Vec<RelatedUser>> {
+        let rows = sqlx::query!(
+            "SELECT
+                users.id,
+                users.username,
+                users.display_name,
+                users.avatar_url,
+                user_mutes.created_at as since
+            FROM user_mutes
+            INNER JOIN users ON users.id = user_mutes.muted_id
+            WHERE user_mutes.muter_id = $1 AND users.deleted_at IS NULL
+            ORDER BY user_mutes.created_at DESC, users.id
+            OFFSET $2 LIMIT $3",
+            &muter_id,
+            cursor.offset,
+            cursor.limit
+        )
+        .fetch_all_ex(executor)
+        .await?;
+
+        rows.into_iter().map(RelatedUser::try_from).collect()
+    }
+}
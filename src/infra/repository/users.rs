@@ -1,14 +1,24 @@
+use crate::domain::value_objects::{
+    creator_tag::CreatorTag, password::Password, profile_image::ProfileImageKind, role::Role,
+    totp::TotpSecret,
+};
 use crate::domain::{
     commands,
     contracts::{
         self,
         repository::{Executor, SqlxExt},
     },
+    queries::{session::get_authenticated_user::AuthenticatedUser, user::get_by_id::User},
 };
 use crate::infra::uuid::Uuid;
 use anyhow::Result;
 use async_trait::async_trait;
 use chrono::Utc;
+use sqlx::Row;
+use std::str::FromStr;
+
+/// Postgres error code of unique constraint violations.
+const UNIQUE_VIOLATION: &str = "23505";
 
 #[derive(Debug)]
 pub struct UserRepository;
@@ -16,39 +26,617 @@ pub struct UserRepository;
 #[async_trait]
 impl contracts::repository::UserRepository for UserRepository {
     #[tracing::instrument(name = "UserRepository.get_by_id", skip_all, fields(id = %id))]
-    async fn get_by_id<'c>(&self, executor: &mut Executor<'c>, id: Uuid) {
-        let _row = sqlx::query!("select * from users where id = $1", &id)
-            .fetch_optional_ex(executor)
-            .await;
+    async fn get_by_id<'c>(&self, executor: &mut Executor<'c>, id: Uuid) -> Result<Option<User>> {
+        let row = sqlx::query!(
+            "SELECT
+                id,
+                username,
+                role,
+                display_name,
+                bio,
+                links,
+                avatar_url,
+                banner_url,
+                created_at,
+                (
+                    SELECT COUNT(*)
+                    FROM user_followers
+                    INNER JOIN users followers ON followers.id = user_followers.follower_id
+                    WHERE user_followers.followee_id = users.id
+                    AND followers.deleted_at IS NULL
+                    AND followers.banned_at IS NULL
+                ) as followers_count,
+                (
+                    SELECT COUNT(*)
+                    FROM user_followers
+                    INNER JOIN users followees ON followees.id = user_followers.followee_id
+                    WHERE user_followers.follower_id = users.id
+                    AND followees.deleted_at IS NULL
+                    AND followees.banned_at IS NULL
+                ) as following_count,
+                ARRAY(
+                    SELECT name FROM user_tags
+                    WHERE user_tags.user_id = users.id
+                    ORDER BY name
+                ) as tags
+            FROM users
+            WHERE id = $1 AND deleted_at IS NULL",
+            &id
+        )
+        .fetch_optional_ex(executor)
+        .await?;
+
+        match row {
+            None => Ok(None),
+            Some(row) => Ok(Some(User {
+                id: row.try_get("id")?,
+                username: row.try_get("username")?,
+                role: Role::from_str(row.try_get("role")?)?,
+                display_name: row.try_get("display_name")?,
+                bio: row.try_get("bio")?,
+                links: row.try_get("links")?,
+                avatar_url: row.try_get("avatar_url")?,
+                banner_url: row.try_get("banner_url")?,
+                created_at: row.try_get("created_at")?,
+                followers_count: row.try_get("followers_count")?,
+                following_count: row.try_get("following_count")?,
+                tags: {
+                    let tags: Vec<String> = row.try_get("tags")?;
+
+                    tags.iter()
+                        .map(|tag| CreatorTag::from_str(tag))
+                        .collect::<Result<_, _>>()?
+                },
+            })),
+        }
+    }
+
+    #[tracing::instrument(name = "UserRepository.get_authenticated_user", skip_all, fields(id = %id))]
+    async fn get_authenticated_user<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+    ) -> Result<Option<AuthenticatedUser>> {
+        let row = sqlx::query!(
+            "SELECT 
+                id,
+                role,
+                email_verified_at IS NOT NULL as email_verified,
+                mfa_enabled_at IS NOT NULL as mfa_enabled,
+                banned_at IS NOT NULL as banned,
+                (
+                    SELECT current_terms.version
+                    FROM (
+                        SELECT version FROM terms_versions
+                        WHERE published_at <= CURRENT_TIMESTAMP
+                        ORDER BY published_at DESC
+                        LIMIT 1
+                    ) current_terms
+                    WHERE NOT EXISTS (
+                        SELECT 1 FROM terms_acceptances
+                        WHERE terms_acceptances.user_id = users.id
+                        AND terms_acceptances.terms_version = current_terms.version
+                    )
+                ) as pending_terms_version
+            FROM users
+            WHERE id = $1 AND deleted_at IS NULL",
+            &id
+        )
+        .fetch_optional_ex(executor)
+        .await?;
+
+        match row {
+            None => Ok(None),
+            Some(row) => Ok(Some(AuthenticatedUser {
+                id: row.try_get("id")?,
+                role: Role::from_str(row.try_get("role")?)?,
+                email_verified: row.try_get("email_verified")?,
+                mfa_enabled: row.try_get("mfa_enabled")?,
+                banned: row.try_get("banned")?,
+                pending_terms_version: row.try_get("pending_terms_version")?,
+            })),
+        }
+    }
+
+    #[tracing::instrument(name = "UserRepository.get_credentials_by_email", skip_all)]
+    async fn get_credentials_by_email<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        email: &str,
+    ) -> Result<Option<commands::session::UserCredentials>> {
+        let row = sqlx::query!(
+            "SELECT
+                id,
+                password,
+                mfa_enabled_at IS NOT NULL as mfa_enabled
+            FROM users
+            WHERE email = $1 AND deleted_at IS NULL",
+            email
+        )
+        .fetch_optional_ex(executor)
+        .await?;
+
+        match row {
+            None => Ok(None),
+            Some(row) => Ok(Some(commands::session::UserCredentials {
+                id: row.try_get("id")?,
+                password: Password::from_hash(row.try_get("password")?),
+                mfa_enabled: row.try_get("mfa_enabled")?,
+            })),
+        }
     }
 
     async fn create<'c>(
         &self,
         executor: &mut Executor<'c>,
         input: commands::user::CreateUserInput,
-    ) -> Result<()> {
-        sqlx::query!(
+    ) -> Result<Uuid> {
+        let row = sqlx::query!(
             "INSERT INTO users(
         id,
         username,
+        username_normalized,
         email,
         password,
         accepted_terms_at,
         created_at
       ) VALUES (
         uuid_generate_v4(),
-        $1, $2, $3,
-        TO_TIMESTAMP($4),
-        TO_TIMESTAMP($5)
-      )",
-            input.username,                             // $2->username
+        $1, $2, $3, $4,
+        TO_TIMESTAMP($5),
+        TO_TIMESTAMP($6)
+      )
+      RETURNING id",
+            input.username.expose(),                    // $1->username
+            input.username.normalized(),                // $2->username_normalized
             input.email.expose(),                       // $3->email
             input.password.expose(),                    // $4->password
             input.accepted_terms_at.timestamp() as f64, // $5->accepted_terms_at
             Utc::now().timestamp() as f64,              // $6->created_at
         )
+        .fetch_one_ex(executor)
+        .await
+        .map_err(user_conflict)?;
+
+        Ok(row.try_get("id")?)
+    }
+
+    #[tracing::instrument(name = "UserRepository.mark_email_as_verified", skip_all, fields(id = %id))]
+    async fn mark_email_as_verified<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+    ) -> Result<()> {
+        sqlx::query!(
+            "UPDATE users SET email_verified_at = CURRENT_TIMESTAMP WHERE id = $1",
+            &id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "UserRepository.get_email_for_update", skip_all, fields(
+        id = %id
+    ))]
+    async fn get_email_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+    ) -> Result<Option<commands::user::UserEmail>> {
+        let row = sqlx::query!(
+            "SELECT
+                email,
+                email_verified_at IS NOT NULL as verified
+            FROM users
+            WHERE id = $1 AND deleted_at IS NULL
+            FOR UPDATE",
+            &id
+        )
+        .fetch_optional_ex(executor)
+        .await?;
+
+        match row {
+            None => Ok(None),
+            Some(row) => Ok(Some(commands::user::UserEmail {
+                email: row.try_get("email")?,
+                verified: row.try_get("verified")?,
+            })),
+        }
+    }
+
+    #[tracing::instrument(name = "UserRepository.update_password", skip_all, fields(id = %id))]
+    async fn update_password<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+        password: Password,
+    ) -> Result<()> {
+        sqlx::query!(
+            "UPDATE users SET password = $2 WHERE id = $1",
+            &id,
+            password.expose()
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "UserRepository.get_mfa_for_update", skip_all, fields(id = %id))]
+    async fn get_mfa_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+    ) -> Result<Option<commands::mfa::UserMfa>> {
+        let row = sqlx::query!(
+            "SELECT
+                email,
+                totp_secret,
+                mfa_enabled_at IS NOT NULL as mfa_enabled,
+                totp_last_used_step
+            FROM users
+            WHERE id = $1 AND deleted_at IS NULL
+            FOR UPDATE",
+            &id
+        )
+        .fetch_optional_ex(executor)
+        .await?;
+
+        match row {
+            None => Ok(None),
+            Some(row) => {
+                let totp_secret: Option<String> = row.try_get("totp_secret")?;
+
+                Ok(Some(commands::mfa::UserMfa {
+                    email: row.try_get("email")?,
+                    totp_secret: totp_secret
+                        .map(|secret| TotpSecret::from_base32(&secret))
+                        .transpose()?,
+                    mfa_enabled: row.try_get("mfa_enabled")?,
+                    totp_last_used_step: row.try_get("totp_last_used_step")?,
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(name = "UserRepository.set_totp_secret", skip_all, fields(id = %id))]
+    async fn set_totp_secret<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+        secret: &TotpSecret,
+    ) -> Result<()> {
+        sqlx::query!(
+            "UPDATE users
+            SET totp_secret = $2, totp_last_used_step = NULL
+            WHERE id = $1",
+            &id,
+            secret.to_base32()
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "UserRepository.enable_mfa", skip_all, fields(id = %id))]
+    async fn enable_mfa<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+        totp_step: i64,
+    ) -> Result<()> {
+        sqlx::query!(
+            "UPDATE users
+            SET mfa_enabled_at = CURRENT_TIMESTAMP, totp_last_used_step = $2
+            WHERE id = $1",
+            &id,
+            totp_step
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "UserRepository.set_totp_last_used_step", skip_all, fields(
+        id = %id
+    ))]
+    async fn set_totp_last_used_step<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+        totp_step: i64,
+    ) -> Result<()> {
+        sqlx::query!(
+            "UPDATE users SET totp_last_used_step = $2 WHERE id = $1",
+            &id,
+            totp_step
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "UserRepository.update_profile", skip_all, fields(id = %id))]
+    async fn update_profile<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+        changes: commands::user::ProfileChanges,
+    ) -> Result<()> {
+        // Null parameters keep the current value, empty strings clear it.
+        sqlx::query!(
+            "UPDATE users
+            SET
+                display_name = CASE WHEN $2::VARCHAR IS NULL THEN display_name ELSE NULLIF($2, '') END,
+                bio = CASE WHEN $3::VARCHAR IS NULL THEN bio ELSE NULLIF($3, '') END,
+                links = COALESCE($4, links)
+            WHERE id = $1",
+            &id,
+            changes.display_name,
+            changes.bio,
+            changes.links.as_deref()
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "UserRepository.set_profile_image", skip_all, fields(
+        id = %id,
+        kind = %kind
+    ))]
+    async fn set_profile_image<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+        kind: ProfileImageKind,
+        url: &str,
+    ) -> Result<()> {
+        match kind {
+            ProfileImageKind::Avatar => {
+                sqlx::query!("UPDATE users SET avatar_url = $2 WHERE id = $1", &id, url)
+                    .execute_ex(executor)
+                    .await?;
+            }
+            ProfileImageKind::Banner => {
+                sqlx::query!("UPDATE users SET banner_url = $2 WHERE id = $1", &id, url)
+                    .execute_ex(executor)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "UserRepository.soft_delete", skip_all, fields(id = %id))]
+    async fn soft_delete<'c>(&self, executor: &mut Executor<'c>, id: Uuid) -> Result<()> {
+        sqlx::query!(
+            "UPDATE users
+            SET deleted_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND deleted_at IS NULL",
+            &id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "UserRepository.get_personal_data", skip_all, fields(id = %id))]
+    async fn get_personal_data<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+    ) -> Result<Option<commands::user::PersonalDataProfile>> {
+        let row = sqlx::query!(
+            "SELECT
+                id,
+                username,
+                email,
+                role,
+                display_name,
+                bio,
+                links,
+                avatar_url,
+                banner_url,
+                email_verified_at,
+                mfa_enabled_at IS NOT NULL as mfa_enabled,
+                accepted_terms_at AT TIME ZONE 'UTC' as accepted_terms_at,
+                created_at
+            FROM users
+            WHERE id = $1 AND deleted_at IS NULL",
+            &id
+        )
+        .fetch_optional_ex(executor)
+        .await?;
+
+        match row {
+            None => Ok(None),
+            Some(row) => Ok(Some(commands::user::PersonalDataProfile {
+                id: row.try_get("id")?,
+                username: row.try_get("username")?,
+                email: row.try_get("email")?,
+                role: row.try_get("role")?,
+                display_name: row.try_get("display_name")?,
+                bio: row.try_get("bio")?,
+                links: row.try_get("links")?,
+                avatar_url: row.try_get("avatar_url")?,
+                banner_url: row.try_get("banner_url")?,
+                email_verified_at: row.try_get("email_verified_at")?,
+                mfa_enabled: row.try_get("mfa_enabled")?,
+                accepted_terms_at: row.try_get("accepted_terms_at")?,
+                created_at: row.try_get("created_at")?,
+            })),
+        }
+    }
+
+    #[tracing::instrument(name = "UserRepository.get_ban_status_for_update", skip_all, fields(
+        id = %id
+    ))]
+    async fn get_ban_status_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+    ) -> Result<Option<commands::moderation::UserBanStatus>> {
+        let row = sqlx::query!(
+            "SELECT
+                role,
+                banned_at IS NOT NULL as banned
+            FROM users
+            WHERE id = $1 AND deleted_at IS NULL
+            FOR UPDATE",
+            &id
+        )
+        .fetch_optional_ex(executor)
+        .await?;
+
+        match row {
+            None => Ok(None),
+            Some(row) => Ok(Some(commands::moderation::UserBanStatus {
+                role: Role::from_str(row.try_get("role")?)?,
+                banned: row.try_get("banned")?,
+            })),
+        }
+    }
+
+    #[tracing::instrument(name = "UserRepository.set_banned", skip_all, fields(
+        id = %id,
+        banned = %banned
+    ))]
+    async fn set_banned<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+        banned: bool,
+    ) -> Result<()> {
+        sqlx::query!(
+            "UPDATE users
+            SET banned_at = CASE WHEN $2 THEN CURRENT_TIMESTAMP ELSE NULL END
+            WHERE id = $1",
+            &id,
+            banned
+        )
         .execute_ex(executor)
         .await?;
+
+        Ok(())
+    }
+}
+
+/// Turns violations of the unique constraints of the users table
+/// into a `CreateUserError` naming the field that is taken.
+fn user_conflict(error: sqlx::Error) -> anyhow::Error {
+    if let sqlx::Error::Database(db_error) = &error {
+        if db_error.code().as_deref() == Some(UNIQUE_VIOLATION) {
+            match db_error.constraint() {
+                Some("users_username_key" | "users_username_normalized_idx") => {
+                    return commands::user::CreateUserError::UsernameTaken.into()
+                }
+                Some("users_email_key") => {
+                    return commands::user::CreateUserError::EmailTaken.into()
+                }
+                _ => {}
+            }
+        }
+    }
+
+    error.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{Executor as _, PgPool, Row};
+
+    use crate::infra::uuid::Uuid;
+
+    const ADD_USERNAME_NORMALIZED_MIGRATION: &str =
+        include_str!("../../../migrations/20230306091530_add_username_normalized_to_users.sql");
+
+    #[tokio::test]
+    async fn migration_renames_usernames_that_collide_once_normalized(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let pool = PgPool::connect(&std::env::var("DATABASE_RW_URL")?).await?;
+
+        // The migration runs against a temporary table that shadows the users table,
+        // and everything is rolled back when the transaction is dropped.
+        let mut tx = pool.begin().await?;
+
+        tx.execute(
+            "CREATE TEMPORARY TABLE users (
+                id UUID PRIMARY KEY,
+                username VARCHAR(255) NOT NULL,
+                created_at TIMESTAMP NOT NULL
+            )",
+        )
+        .await?;
+
+        let oldest_id = Uuid::new_v4();
+        let same_but_case_id = Uuid::new_v4();
+        let same_but_width_id = Uuid::new_v4();
+        let other_id = Uuid::new_v4();
+
+        for (position, (id, username)) in [
+            (oldest_id, "Alice"),
+            (same_but_case_id, "alice"),
+            (same_but_width_id, "ＡＬＩＣＥ"),
+            (other_id, "bob"),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            sqlx::query(
+                "INSERT INTO users (id, username, created_at)
+                VALUES ($1, $2, TIMESTAMP '2023-01-01' + $3 * INTERVAL '1 day')",
+            )
+            .bind(id)
+            .bind(username)
+            .bind(position as i32)
+            .execute(&mut tx)
+            .await?;
+        }
+
+        tx.execute(ADD_USERNAME_NORMALIZED_MIGRATION).await?;
+
+        let rows = sqlx::query("SELECT id, username, username_normalized FROM users")
+            .fetch_all(&mut tx)
+            .await?;
+
+        let user = |id: Uuid| -> Result<(String, String), sqlx::Error> {
+            let row = rows
+                .iter()
+                .find(|row| row.get::<Uuid, _>("id") == id)
+                .expect("user not found");
+
+            Ok((
+                row.try_get("username")?,
+                row.try_get("username_normalized")?,
+            ))
+        };
+
+        // The oldest account and the ones that do not collide keep their usernames.
+        assert_eq!(user(oldest_id)?, ("Alice".to_owned(), "alice".to_owned()));
+        assert_eq!(user(other_id)?, ("bob".to_owned(), "bob".to_owned()));
+
+        for (id, username) in [
+            (same_but_case_id, "alice"),
+            (same_but_width_id, "ＡＬＩＣＥ"),
+        ] {
+            let suffix = id.to_string().replace('-', "")[..8].to_owned();
+
+            let (renamed, normalized) = user(id)?;
+
+            assert_eq!(renamed, format!("{username}_{suffix}"));
+            assert_eq!(normalized, format!("alice_{suffix}"));
+        }
+
         Ok(())
     }
 }
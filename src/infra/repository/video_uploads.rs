@@ -0,0 +1,99 @@
+use crate::domain::{
+    commands,
+    contracts::{
+        self,
+        repository::{Executor, SqlxExt},
+    },
+};
+use crate::infra::uuid::Uuid;
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::Row;
+
+#[derive(Debug)]
+pub struct VideoUploadRepository;
+
+#[async_trait]
+impl contracts::repository::VideoUploadRepository for VideoUploadRepository {
+    #[tracing::instrument(name = "VideoUploadRepository.create", skip_all, fields(
+        id = %input.id,
+        user_id = %input.user_id
+    ))]
+    async fn create<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        input: commands::video::NewVideoUpload,
+    ) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO video_uploads (
+                id,
+                user_id,
+                expires_at
+            ) VALUES (
+                $1, $2, $3
+            )",
+            &input.id,
+            &input.user_id,
+            &input.expires_at,
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "VideoUploadRepository.get_for_update", skip_all, fields(
+        user_id = %user_id,
+        id = %id
+    ))]
+    async fn get_for_update<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<commands::video::VideoUpload>> {
+        let row = sqlx::query!(
+            "SELECT
+                id,
+                expires_at,
+                post_id
+            FROM video_uploads
+            WHERE id = $1 AND user_id = $2
+            FOR UPDATE",
+            &id,
+            &user_id
+        )
+        .fetch_optional_ex(executor)
+        .await?;
+
+        match row {
+            None => Ok(None),
+            Some(row) => Ok(Some(commands::video::VideoUpload {
+                id: row.try_get("id")?,
+                expires_at: row.try_get("expires_at")?,
+                post_id: row.try_get("post_id")?,
+            })),
+        }
+    }
+
+    #[tracing::instrument(name = "VideoUploadRepository.set_post_id", skip_all, fields(
+        id = %id,
+        post_id = %post_id
+    ))]
+    async fn set_post_id<'c>(
+        &self,
+        executor: &mut Executor<'c>,
+        id: Uuid,
+        post_id: Uuid,
+    ) -> Result<()> {
+        sqlx::query!(
+            "UPDATE video_uploads SET post_id = $2 WHERE id = $1",
+            &id,
+            &post_id
+        )
+        .execute_ex(executor)
+        .await?;
+
+        Ok(())
+    }
+}
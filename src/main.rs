@@ -3,8 +3,8 @@ mod domain;
 mod infra;
 mod presentation;
 
-use anyhow::Result;
-use std::net::SocketAddr;
+use anyhow::{Context, Result};
+use std::{net::SocketAddr, sync::Arc};
 use tracing::info;
 use tracing_bunyan_formatter::{BunyanFormattingLayer, JsonStorageLayer};
 use tracing_subscriber::layer::SubscriberExt;
@@ -26,14 +26,22 @@ async fn main() -> Result<()> {
 
     tracing::subscriber::set_global_default(subscriber).unwrap();
 
-    let app = presentation::rest::router().await?;
+    let deps = Arc::new(
+        presentation::rest::deps()
+            .await
+            .context("instantiating dependencies")?,
+    );
+
+    presentation::jobs::spawn(Arc::clone(&deps));
+
+    let app = presentation::rest::router_with_deps(deps);
 
     let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
 
     info!("server started on {}", addr);
 
     axum::Server::bind(&addr)
-        .serve(app.into_make_service())
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
         .await
         .unwrap();
 
@@ -0,0 +1,58 @@
+//! Work that runs in the background while the api is up.
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use tracing::{error, info};
+
+use crate::domain::{
+    commands,
+    constants::{OBJECT_PURGE_INTERVAL_SECS, PERSONAL_DATA_EXPORT_INTERVAL_SECS},
+    contracts::{context::Context, deps::Deps},
+};
+
+/// Starts running scheduled object purges every `OBJECT_PURGE_INTERVAL_SECS`
+/// and building requested personal data exports every `PERSONAL_DATA_EXPORT_INTERVAL_SECS`.
+pub fn spawn(deps: Arc<Deps>) {
+    spawn_personal_data_exports(Arc::clone(&deps));
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(OBJECT_PURGE_INTERVAL_SECS));
+
+        loop {
+            interval.tick().await;
+
+            let ctx = Context::from(HashMap::from([(
+                "job".to_owned(),
+                "object_purge".to_owned(),
+            )]));
+
+            match commands::object_purge::run_due(&deps, &ctx).await {
+                Ok(0) => {}
+                Ok(purges) => info!(purges, "scheduled object purges ran"),
+                Err(error) => error!(?error, "unable to run scheduled object purges"),
+            }
+        }
+    });
+}
+
+fn spawn_personal_data_exports(deps: Arc<Deps>) {
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(PERSONAL_DATA_EXPORT_INTERVAL_SECS));
+
+        loop {
+            interval.tick().await;
+
+            let ctx = Context::from(HashMap::from([(
+                "job".to_owned(),
+                "personal_data_export".to_owned(),
+            )]));
+
+            match commands::user::run_pending_exports(&deps, &ctx).await {
+                Ok(0) => {}
+                Ok(exports) => info!(exports, "personal data exports built"),
+                Err(error) => error!(?error, "unable to build personal data exports"),
+            }
+        }
+    });
+}
@@ -0,0 +1,213 @@
+use axum::extract::Path;
+use axum::{Extension, Json};
+use hyper::StatusCode;
+use std::sync::Arc;
+use tracing::error;
+
+use crate::domain::{commands, contracts::deps::Deps};
+use crate::infra::uuid::Uuid;
+use crate::presentation::rest::errors::error_into_response;
+use crate::presentation::rest::extensions::context::ExtractContext;
+use crate::presentation::rest::extensions::user::ExtractAuth;
+use crate::presentation::rest::view_models;
+
+#[tracing::instrument(name = "POST /v1/admin/users/:id/ban", skip_all, fields(
+    user_id = %user_id,
+    payload = ?payload,
+    ctx = ?ctx
+))]
+pub async fn ban_user(
+    ExtractAuth(auth): ExtractAuth,
+    Path(user_id): Path<Uuid>,
+    Json(payload): Json<view_models::admin::BanUserInput>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<StatusCode, axum::response::Response> {
+    let fields = view_models::admin::BanFields::try_from(payload)?;
+
+    let input = commands::moderation::BanUserInput {
+        admin_id: auth.user_id,
+        role: auth.role,
+        user_id,
+        reason: fields.reason,
+    };
+
+    if let Err(error) = commands::moderation::ban_user(&deps, &ctx, input).await {
+        error!(?error, "unable to ban user");
+        return Err(error_into_response(error));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(name = "POST /v1/admin/users/:id/unban", skip_all, fields(
+    user_id = %user_id,
+    payload = ?payload,
+    ctx = ?ctx
+))]
+pub async fn unban_user(
+    ExtractAuth(auth): ExtractAuth,
+    Path(user_id): Path<Uuid>,
+    Json(payload): Json<view_models::admin::BanUserInput>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<StatusCode, axum::response::Response> {
+    let fields = view_models::admin::BanFields::try_from(payload)?;
+
+    let input = commands::moderation::UnbanUserInput {
+        admin_id: auth.user_id,
+        role: auth.role,
+        user_id,
+        reason: fields.reason,
+    };
+
+    if let Err(error) = commands::moderation::unban_user(&deps, &ctx, input).await {
+        error!(?error, "unable to unban user");
+        return Err(error_into_response(error));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::constants::X_REQUEST_ID_HEADER_NAME;
+    use crate::domain::value_objects::role::Role;
+    use crate::infra::factory;
+    use crate::infra::uuid::Uuid;
+    use crate::presentation::rest::traits::{RequestBuilderExt, ResponseExt};
+    use crate::presentation::rest::{deps, router_with_deps, view_models};
+    use axum::http::Request;
+    use hyper::{Body, Method, StatusCode};
+    use tower::Service;
+
+    fn ban_request(
+        admin_id: Uuid,
+        user_id: Uuid,
+        action: &str,
+    ) -> Result<Request<Body>, Box<dyn std::error::Error>> {
+        Ok(Request::builder()
+            .method(Method::POST)
+            .uri(format!("/v1/admin/users/{user_id}/{action}"))
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(admin_id)
+            .json(&view_models::admin::BanUserInput {
+                reason: "spam".to_owned(),
+            })?)
+    }
+
+    fn list_api_keys_request(user_id: Uuid) -> Result<Request<Body>, Box<dyn std::error::Error>> {
+        Ok(Request::builder()
+            .method(Method::GET)
+            .uri("/v1/users/me/api-keys")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .body(Body::empty())?)
+    }
+
+    #[tokio::test]
+    async fn banned_users_cannot_use_the_app() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let admin_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Admin).await?;
+
+        let user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let response = app.call(ban_request(admin_id, user_id, "ban")?).await?;
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = app.call(ban_request(admin_id, user_id, "ban")?).await?;
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = app.call(list_api_keys_request(user_id)?).await?;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let body: serde_json::Value = response.json().await?;
+
+        assert_eq!(body["message"], "user is banned");
+
+        let response = app.call(ban_request(admin_id, user_id, "unban")?).await?;
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = app.call(list_api_keys_request(user_id)?).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn only_admins_can_ban_users() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let creator_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let response = app.call(ban_request(creator_id, user_id, "ban")?).await?;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = app.call(list_api_keys_request(user_id)?).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn posts_of_banned_creators_are_not_in_the_timeline(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let admin_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Admin).await?;
+
+        let creator_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let post_id =
+            factory::post::create_for_user(creator_id, &mut deps.db.write().await?).await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let response = app.call(ban_request(admin_id, creator_id, "ban")?).await?;
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri("/v1/timeline")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .body(Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let timeline: view_models::timeline::TimelineOutput = response.json().await?;
+
+        assert!(timeline.posts.iter().all(|post| post.id != post_id));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,217 @@
+use axum::extract::Path;
+use axum::{Extension, Json};
+use hyper::StatusCode;
+use std::sync::Arc;
+use tracing::error;
+
+use crate::domain::{commands, contracts::deps::Deps, queries};
+use crate::infra::uuid::Uuid;
+use crate::presentation::rest::errors::error_into_response;
+use crate::presentation::rest::extensions::context::ExtractContext;
+use crate::presentation::rest::extensions::user::ExtractAuth;
+use crate::presentation::rest::view_models;
+
+#[tracing::instrument(name = "POST /v1/users/me/api-keys", skip_all, fields(
+    payload = ?payload,
+    ctx = ?ctx
+))]
+pub async fn create_api_key(
+    ExtractAuth(auth): ExtractAuth,
+    Json(payload): Json<view_models::api_key::CreateApiKeyInput>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<(StatusCode, Json<view_models::api_key::CreateApiKeyOutput>), axum::response::Response>
+{
+    let fields = view_models::api_key::ApiKeyFields::try_from(payload)?;
+
+    let input = commands::api_key::CreateApiKeyInput {
+        user_id: auth.user_id,
+        role: auth.role,
+        mfa_authenticated: auth.mfa_authenticated,
+        name: fields.name,
+        scopes: fields.scopes,
+    };
+
+    match commands::api_key::create(&deps, &ctx, input).await {
+        Ok(output) => Ok((
+            StatusCode::CREATED,
+            Json(view_models::api_key::CreateApiKeyOutput {
+                id: output.id,
+                key: output.key,
+                prefix: output.prefix,
+            }),
+        )),
+        Err(error) => {
+            error!(?error, "unable to create api key");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[tracing::instrument(name = "GET /v1/users/me/api-keys", skip_all, fields(
+    ctx = ?ctx
+))]
+pub async fn list_api_keys(
+    ExtractAuth(auth): ExtractAuth,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<Vec<view_models::api_key::ApiKeyOutput>>, axum::response::Response> {
+    match queries::api_key::list::handle(&deps, &ctx, auth.user_id).await {
+        Ok(keys) => Ok(Json(
+            keys.into_iter()
+                .map(view_models::api_key::ApiKeyOutput::from)
+                .collect(),
+        )),
+        Err(error) => {
+            error!(?error, "unable to list api keys");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[tracing::instrument(name = "DELETE /v1/users/me/api-keys/:id", skip_all, fields(
+    api_key_id = %api_key_id,
+    ctx = ?ctx
+))]
+pub async fn revoke_api_key(
+    ExtractAuth(auth): ExtractAuth,
+    Path(api_key_id): Path<Uuid>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<StatusCode, axum::response::Response> {
+    let input = commands::api_key::RevokeApiKeyInput {
+        user_id: auth.user_id,
+        api_key_id,
+    };
+
+    if let Err(error) = commands::api_key::revoke(&deps, &ctx, input).await {
+        error!(?error, "unable to revoke api key");
+        return Err(error_into_response(error));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::constants::{X_API_KEY_HEADER_NAME, X_REQUEST_ID_HEADER_NAME};
+    use crate::domain::value_objects::role::Role;
+    use crate::infra::factory;
+    use crate::presentation::rest::traits::{RequestBuilderExt, ResponseExt};
+    use crate::presentation::rest::{deps, router, view_models};
+    use axum::http::Request;
+    use hyper::{Body, Method, StatusCode};
+    use tower::Service;
+
+    #[tokio::test]
+    async fn creators_can_upload_videos_with_api_keys() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let user_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let mut app = router().await?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/users/me/api-keys")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_mfa_user_auth(user_id)
+            .json(&view_models::api_key::CreateApiKeyInput {
+                name: "ci".to_owned(),
+                scopes: vec!["videos:upload".to_owned()],
+            })?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let api_key: view_models::api_key::CreateApiKeyOutput = response.json().await?;
+
+        assert!(api_key.key.starts_with(&api_key.prefix));
+
+        let upload = |key: &str| {
+            Request::builder()
+                .method(Method::POST)
+                .uri("/v1/videos")
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .header(X_API_KEY_HEADER_NAME, key)
+                .body(Body::empty())
+        };
+
+        let response = app.call(upload(&api_key.key)?).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri("/v1/users/me/api-keys")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .body(Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let keys: Vec<view_models::api_key::ApiKeyOutput> = response.json().await?;
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].id, api_key.id);
+        assert_eq!(keys[0].scopes, vec!["videos:upload".to_owned()]);
+        assert!(keys[0].last_used_at.is_some());
+
+        let req = Request::builder()
+            .method(Method::DELETE)
+            .uri(format!("/v1/users/me/api-keys/{}", api_key.id))
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .body(Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = app.call(upload(&api_key.key)?).await?;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn api_keys_require_mfa_session() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let user_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let mut app = router().await?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/users/me/api-keys")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .json(&view_models::api_key::CreateApiKeyInput {
+                name: "ci".to_owned(),
+                scopes: vec![],
+            })?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,261 @@
+use axum::extract::Query;
+use axum::{Extension, Json};
+use serde::Deserialize;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::error;
+
+use crate::domain::constants::CREATORS_LIMIT;
+use crate::domain::errors::ValidationError;
+use crate::domain::{
+    commands,
+    contracts::deps::Deps,
+    queries,
+    value_objects::{creator_tag::CreatorTag, cursor::Cursor},
+};
+use crate::presentation::rest::errors::error_into_response;
+use crate::presentation::rest::extensions::context::ExtractContext;
+use crate::presentation::rest::extensions::user::ExtractAuth;
+use crate::presentation::rest::view_models;
+
+#[derive(Debug, Deserialize)]
+pub struct ListCreatorsQuery {
+    tag: String,
+    #[serde(default)]
+    cursor: i64,
+}
+
+#[tracing::instrument(name = "PUT /v1/users/me/tags", skip_all, fields(
+    payload = ?payload,
+    ctx = ?ctx
+))]
+pub async fn set_tags(
+    ExtractAuth(auth): ExtractAuth,
+    Json(payload): Json<view_models::creator::SetCreatorTagsInput>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<view_models::creator::CreatorTagsOutput>, axum::response::Response> {
+    let fields = view_models::creator::CreatorTagsFields::try_from(payload)?;
+
+    let input = commands::creator_tag::SetCreatorTagsInput {
+        user_id: auth.user_id,
+        role: auth.role,
+        tags: fields.tags,
+    };
+
+    match commands::creator_tag::set(&deps, &ctx, input).await {
+        Ok(tags) => Ok(Json(tags.into())),
+        Err(error) => {
+            error!(?error, "unable to set creator tags");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[tracing::instrument(name = "GET /v1/creators", skip_all, fields(
+    payload = ?payload,
+    ctx = ?ctx
+))]
+pub async fn list_creators(
+    Query(payload): Query<ListCreatorsQuery>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<Vec<view_models::creator::CreatorOutput>>, axum::response::Response> {
+    let tag = CreatorTag::from_str(&payload.tag).map_err(ValidationError::from)?;
+
+    let cursor = Cursor {
+        offset: payload.cursor.max(0),
+        limit: CREATORS_LIMIT,
+    };
+
+    match queries::creator::list_by_tag::handle(&deps, &ctx, tag, cursor).await {
+        Ok(creators) => Ok(Json(
+            creators
+                .into_iter()
+                .map(view_models::creator::CreatorOutput::from)
+                .collect(),
+        )),
+        Err(error) => {
+            error!(?error, "unable to list creators");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[tracing::instrument(name = "GET /v1/creators/tags", skip_all, fields(ctx = ?ctx))]
+pub async fn list_tags(
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<Vec<view_models::creator::TagCountOutput>>, axum::response::Response> {
+    match queries::creator::list_tags::handle(&deps, &ctx).await {
+        Ok(tags) => Ok(Json(
+            tags.into_iter()
+                .map(view_models::creator::TagCountOutput::from)
+                .collect(),
+        )),
+        Err(error) => {
+            error!(?error, "unable to list tags");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::constants::X_REQUEST_ID_HEADER_NAME;
+    use crate::domain::value_objects::role::Role;
+    use crate::infra::factory;
+    use crate::infra::uuid::Uuid;
+    use crate::presentation::rest::traits::{
+        follow_request, RequestBuilderExt, ResponseExt, RouterExt,
+    };
+    use crate::presentation::rest::{deps, router_with_deps, view_models};
+    use axum::http::Request;
+    use axum::Router;
+    use hyper::{Body, Method, StatusCode};
+    use tower::Service;
+
+    fn set_tags_request(
+        user_id: Uuid,
+        tags: &[&str],
+    ) -> Result<Request<Body>, Box<dyn std::error::Error>> {
+        Ok(Request::builder()
+            .method(Method::PUT)
+            .uri("/v1/users/me/tags")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .json(&view_models::creator::SetCreatorTagsInput {
+                tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            })?)
+    }
+
+    async fn cooking_creators_count(app: &mut Router) -> Result<i32, Box<dyn std::error::Error>> {
+        let tags: Vec<view_models::creator::TagCountOutput> =
+            app.get_json("/v1/creators/tags").await?;
+
+        Ok(tags
+            .into_iter()
+            .find(|tag| tag.name == "cooking")
+            .map(|tag| tag.creators_count)
+            .unwrap_or_default())
+    }
+
+    #[tokio::test]
+    async fn creators_can_tag_themselves() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let creator_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let viewer_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let response = app
+            .call(set_tags_request(
+                creator_id,
+                &["  Music", "FITNESS", "music"],
+            )?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let output: view_models::creator::CreatorTagsOutput = response.json().await?;
+
+        assert_eq!(output.tags, vec!["fitness", "music"]);
+
+        let profile: view_models::user::UserProfileOutput =
+            app.get_json(&format!("/v1/users/{creator_id}")).await?;
+
+        assert_eq!(profile.tags, vec!["fitness", "music"]);
+
+        let response = app.call(set_tags_request(creator_id, &["gaming"])?).await?;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let response = app.call(set_tags_request(viewer_id, &["music"])?).await?;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn creators_are_discovered_by_tag() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let count_before = cooking_creators_count(&mut app).await?;
+
+        let mut creator_ids = vec![];
+
+        // The first creator gets the most followers.
+        for followers in [2, 1] {
+            let creator_id =
+                factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+            let response = app
+                .call(set_tags_request(creator_id, &["cooking"])?)
+                .await?;
+
+            assert_eq!(response.status(), StatusCode::OK);
+
+            for _ in 0..followers {
+                let follower_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+                let response = app
+                    .call(follow_request(Method::POST, follower_id, creator_id)?)
+                    .await?;
+
+                assert_eq!(response.status(), StatusCode::NO_CONTENT);
+            }
+
+            creator_ids.push(creator_id);
+        }
+
+        assert_eq!(cooking_creators_count(&mut app).await?, count_before + 2);
+
+        let mut found = vec![];
+        let mut cursor = 0;
+
+        loop {
+            let creators: Vec<view_models::creator::CreatorOutput> = app
+                .get_json(&format!("/v1/creators?tag=Cooking&cursor={cursor}"))
+                .await?;
+
+            if creators.is_empty() {
+                break;
+            }
+
+            cursor += creators.len();
+
+            found.extend(
+                creators
+                    .into_iter()
+                    .filter(|creator| creator_ids.contains(&creator.id))
+                    .map(|creator| creator.id),
+            );
+        }
+
+        assert_eq!(found, creator_ids);
+
+        let response = app.call(set_tags_request(creator_ids[0], &[])?).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        assert_eq!(cooking_creators_count(&mut app).await?, count_before + 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,236 @@
+use axum::extract::{Path, Query};
+use axum::{Extension, Json};
+use hyper::StatusCode;
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::error;
+
+use crate::domain::constants::FOLLOWS_LIMIT;
+use crate::domain::{commands, contracts::deps::Deps, queries, value_objects::cursor::Cursor};
+use crate::infra::uuid::Uuid;
+use crate::presentation::rest::errors::error_into_response;
+use crate::presentation::rest::extensions::context::ExtractContext;
+use crate::presentation::rest::extensions::user::ExtractAuth;
+use crate::presentation::rest::view_models;
+
+#[derive(Debug, Deserialize)]
+pub struct ListFollowsQuery {
+    #[serde(default)]
+    cursor: i64,
+}
+
+impl From<ListFollowsQuery> for Cursor {
+    fn from(input: ListFollowsQuery) -> Self {
+        Cursor {
+            offset: input.cursor.max(0),
+            limit: FOLLOWS_LIMIT,
+        }
+    }
+}
+
+#[tracing::instrument(name = "POST /v1/users/:id/follow", skip_all, fields(
+    user_id = %user_id,
+    ctx = ?ctx
+))]
+pub async fn follow(
+    ExtractAuth(auth): ExtractAuth,
+    Path(user_id): Path<Uuid>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<StatusCode, axum::response::Response> {
+    let input = commands::follow::FollowInput {
+        follower_id: auth.user_id,
+        followee_id: user_id,
+    };
+
+    if let Err(error) = commands::follow::follow(&deps, &ctx, input).await {
+        error!(?error, "unable to follow user");
+        return Err(error_into_response(error));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(name = "DELETE /v1/users/:id/follow", skip_all, fields(
+    user_id = %user_id,
+    ctx = ?ctx
+))]
+pub async fn unfollow(
+    ExtractAuth(auth): ExtractAuth,
+    Path(user_id): Path<Uuid>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<StatusCode, axum::response::Response> {
+    let input = commands::follow::UnfollowInput {
+        follower_id: auth.user_id,
+        followee_id: user_id,
+    };
+
+    if let Err(error) = commands::follow::unfollow(&deps, &ctx, input).await {
+        error!(?error, "unable to unfollow user");
+        return Err(error_into_response(error));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(name = "GET /v1/users/:id/followers", skip_all, fields(
+    user_id = %user_id,
+    payload = ?payload,
+    ctx = ?ctx
+))]
+pub async fn list_followers(
+    auth: Option<ExtractAuth>,
+    Path(user_id): Path<Uuid>,
+    Query(payload): Query<ListFollowsQuery>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<Vec<view_models::follow::FollowUserOutput>>, axum::response::Response> {
+    let viewer_id = auth.map(|ExtractAuth(auth)| auth.user_id);
+    let cursor: Cursor = payload.into();
+
+    match queries::follow::list_followers::handle(&deps, &ctx, viewer_id, user_id, cursor).await {
+        Ok(users) => Ok(Json(
+            users
+                .into_iter()
+                .map(view_models::follow::FollowUserOutput::from)
+                .collect(),
+        )),
+        Err(error) => {
+            error!(?error, "unable to list followers");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[tracing::instrument(name = "GET /v1/users/:id/following", skip_all, fields(
+    user_id = %user_id,
+    payload = ?payload,
+    ctx = ?ctx
+))]
+pub async fn list_following(
+    auth: Option<ExtractAuth>,
+    Path(user_id): Path<Uuid>,
+    Query(payload): Query<ListFollowsQuery>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<Vec<view_models::follow::FollowUserOutput>>, axum::response::Response> {
+    let viewer_id = auth.map(|ExtractAuth(auth)| auth.user_id);
+    let cursor: Cursor = payload.into();
+
+    match queries::follow::list_following::handle(&deps, &ctx, viewer_id, user_id, cursor).await {
+        Ok(users) => Ok(Json(
+            users
+                .into_iter()
+                .map(view_models::follow::FollowUserOutput::from)
+                .collect(),
+        )),
+        Err(error) => {
+            error!(?error, "unable to list followed users");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::value_objects::role::Role;
+    use crate::infra::factory;
+    use crate::infra::uuid::Uuid;
+    use crate::presentation::rest::traits::{follow_request, RouterExt};
+    use crate::presentation::rest::{deps, router_with_deps, view_models};
+    use hyper::{Method, StatusCode};
+    use tower::Service;
+
+    #[tokio::test]
+    async fn following_is_idempotent() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let creator_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let follower_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        for _ in 0..2 {
+            let response = app
+                .call(follow_request(Method::POST, follower_id, creator_id)?)
+                .await?;
+
+            assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        }
+
+        let profile: view_models::user::UserProfileOutput =
+            app.get_json(&format!("/v1/users/{creator_id}")).await?;
+
+        assert_eq!(profile.followers_count, 1);
+        assert_eq!(profile.following_count, 0);
+
+        let followers: Vec<view_models::follow::FollowUserOutput> = app
+            .get_json(&format!("/v1/users/{creator_id}/followers"))
+            .await?;
+
+        assert_eq!(
+            followers.iter().map(|user| user.id).collect::<Vec<_>>(),
+            vec![follower_id]
+        );
+
+        let following: Vec<view_models::follow::FollowUserOutput> = app
+            .get_json(&format!("/v1/users/{follower_id}/following?cursor=0"))
+            .await?;
+
+        assert_eq!(
+            following.iter().map(|user| user.id).collect::<Vec<_>>(),
+            vec![creator_id]
+        );
+
+        for _ in 0..2 {
+            let response = app
+                .call(follow_request(Method::DELETE, follower_id, creator_id)?)
+                .await?;
+
+            assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        }
+
+        let profile: view_models::user::UserProfileOutput =
+            app.get_json(&format!("/v1/users/{creator_id}")).await?;
+
+        assert_eq!(profile.followers_count, 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn only_creators_can_be_followed() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let viewer_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        let follower_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let response = app
+            .call(follow_request(Method::POST, follower_id, viewer_id)?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let response = app
+            .call(follow_request(Method::POST, follower_id, Uuid::new_v4())?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        Ok(())
+    }
+}
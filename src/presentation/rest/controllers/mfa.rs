@@ -0,0 +1,384 @@
+use axum::{Extension, Json};
+use std::sync::Arc;
+use tracing::error;
+
+use crate::domain::{commands, contracts::deps::Deps};
+use crate::presentation::rest::errors::error_into_response;
+use crate::presentation::rest::extensions::context::ExtractContext;
+use crate::presentation::rest::extensions::user::ExtractAuth;
+use crate::presentation::rest::view_models;
+
+#[tracing::instrument(name = "POST /v1/users/me/mfa/totp", skip_all, fields(
+    ctx = ?ctx
+))]
+pub async fn enroll_totp(
+    ExtractAuth(auth): ExtractAuth,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<view_models::mfa::EnrollTotpOutput>, axum::response::Response> {
+    let input = commands::mfa::EnrollTotpInput {
+        user_id: auth.user_id,
+        role: auth.role,
+    };
+
+    match commands::mfa::enroll(&deps, &ctx, input).await {
+        Ok(output) => Ok(Json(output.into())),
+        Err(error) => {
+            error!(?error, "unable to start totp enrollment");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[tracing::instrument(name = "POST /v1/users/me/mfa/totp/confirm", skip_all, fields(
+    ctx = ?ctx
+))]
+pub async fn confirm_totp(
+    ExtractAuth(auth): ExtractAuth,
+    Json(payload): Json<view_models::mfa::ConfirmTotpInput>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<view_models::mfa::ConfirmTotpOutput>, axum::response::Response> {
+    let input = commands::mfa::ConfirmTotpInput {
+        user_id: auth.user_id,
+        code: payload.code,
+    };
+
+    match commands::mfa::confirm(&deps, &ctx, input).await {
+        Ok(output) => Ok(Json(output.into())),
+        Err(error) => {
+            error!(?error, "unable to confirm totp enrollment");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::constants::X_REQUEST_ID_HEADER_NAME;
+    use crate::domain::value_objects::{role::Role, totp::TotpSecret, totp::TOTP_STEP_SECS};
+    use crate::infra::factory;
+    use crate::presentation::rest::traits::{RequestBuilderExt, ResponseExt};
+    use crate::presentation::rest::{deps, router, view_models};
+    use axum::http::Request;
+    use chrono::Utc;
+    use fake::{faker::internet::en::Password, Fake};
+    use hyper::{Body, Method, StatusCode};
+    use tower::Service;
+
+    #[tokio::test]
+    async fn creators_can_log_in_with_totp_and_recovery_codes(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let password: String = Password(12..20).fake();
+
+        let (user_id, email) = factory::user::create_with_password(
+            &mut deps.db.write().await?,
+            Role::Creator,
+            &password,
+        )
+        .await?;
+
+        let mut app = router().await?;
+
+        let login = || {
+            Request::builder()
+                .method(Method::POST)
+                .uri("/v1/sessions")
+                .header("Content-Type", "application/json")
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .json(&view_models::session::CreateSessionInput {
+                    email: email.clone(),
+                    password: password.clone(),
+                })
+        };
+
+        let verify = |mfa_token: &str, code: Option<String>, recovery_code: Option<String>| {
+            Request::builder()
+                .method(Method::POST)
+                .uri("/v1/sessions/mfa")
+                .header("Content-Type", "application/json")
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .json(&view_models::session::VerifyMfaInput {
+                    mfa_token: mfa_token.to_owned(),
+                    code,
+                    recovery_code,
+                })
+        };
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/users/me/mfa/totp")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .body(Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let enrollment: view_models::mfa::EnrollTotpOutput = response.json().await?;
+
+        assert!(enrollment.otpauth_uri.starts_with("otpauth://totp/"));
+        assert!(enrollment.qrcode_svg.contains("<svg"));
+
+        // Mfa is not required until the enrollment is confirmed.
+        let response = app.call(login()?).await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let secret = TotpSecret::from_base32(&enrollment.secret)?;
+
+        let now = Utc::now().timestamp();
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/users/me/mfa/totp/confirm")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .json(&view_models::mfa::ConfirmTotpInput {
+                code: secret.code_at(now),
+            })?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let confirmation: view_models::mfa::ConfirmTotpOutput = response.json().await?;
+
+        assert_eq!(confirmation.recovery_codes.len(), 10);
+
+        let response = app.call(login()?).await?;
+
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let challenge: view_models::session::MfaChallengeOutput = response.json().await?;
+
+        // The code used to confirm the enrollment cannot be used again.
+        let response = app
+            .call(verify(
+                &challenge.mfa_token,
+                Some(secret.code_at(now)),
+                None,
+            )?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let next_code = secret.code_at(now + TOTP_STEP_SECS);
+
+        let response = app
+            .call(verify(&challenge.mfa_token, Some(next_code.clone()), None)?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let session: view_models::session::CreateSessionOutput = response.json().await?;
+
+        // Sessions created with the second factor can upload videos.
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/videos")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .header(
+                crate::domain::constants::AUTHORIZATION_HEADER_NAME,
+                format!("Bearer {}", session.access_token),
+            )
+            .body(Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // The mfa token can only be exchanged for a session once.
+        let response = app
+            .call(verify(
+                &challenge.mfa_token,
+                None,
+                Some(confirmation.recovery_codes[0].clone()),
+            )?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let body: serde_json::Value = response.json().await?;
+
+        assert_eq!(body["message"], "mfa token is invalid or has expired");
+
+        let response = app.call(login()?).await?;
+
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let challenge: view_models::session::MfaChallengeOutput = response.json().await?;
+
+        let response = app
+            .call(verify(&challenge.mfa_token, Some(next_code), None)?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        // Recovery codes can be used once.
+        let recovery_code = confirmation.recovery_codes[0].to_uppercase();
+
+        let response = app
+            .call(verify(
+                &challenge.mfa_token,
+                None,
+                Some(recovery_code.clone()),
+            )?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let response = app.call(login()?).await?;
+
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let challenge: view_models::session::MfaChallengeOutput = response.json().await?;
+
+        let response = app
+            .call(verify(&challenge.mfa_token, None, Some(recovery_code))?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        // Access tokens cannot be used as mfa tokens.
+        let response = app
+            .call(verify(
+                &session.access_token,
+                None,
+                Some(confirmation.recovery_codes[1].clone()),
+            )?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mfa_tokens_are_exchanged_for_a_session_once() -> Result<(), Box<dyn std::error::Error>>
+    {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let password: String = Password(12..20).fake();
+
+        let (user_id, email) = factory::user::create_with_password(
+            &mut deps.db.write().await?,
+            Role::Creator,
+            &password,
+        )
+        .await?;
+
+        let mut app = router().await?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/users/me/mfa/totp")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .body(Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let enrollment: view_models::mfa::EnrollTotpOutput = response.json().await?;
+
+        let secret = TotpSecret::from_base32(&enrollment.secret)?;
+
+        let now = Utc::now().timestamp();
+
+        // Confirmed with the code of the previous step so the next two are still unused.
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/users/me/mfa/totp/confirm")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .json(&view_models::mfa::ConfirmTotpInput {
+                code: secret.code_at(now - TOTP_STEP_SECS),
+            })?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/sessions")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .json(&view_models::session::CreateSessionInput { email, password })?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let challenge: view_models::session::MfaChallengeOutput = response.json().await?;
+
+        let verify = |code: String| {
+            Request::builder()
+                .method(Method::POST)
+                .uri("/v1/sessions/mfa")
+                .header("Content-Type", "application/json")
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .json(&view_models::session::VerifyMfaInput {
+                    mfa_token: challenge.mfa_token.clone(),
+                    code: Some(code),
+                    recovery_code: None,
+                })
+        };
+
+        let response = app.call(verify(secret.code_at(now))?).await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        // The code is valid but the token was already exchanged.
+        let response = app
+            .call(verify(secret.code_at(now + TOTP_STEP_SECS))?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let body: serde_json::Value = response.json().await?;
+
+        assert_eq!(body["message"], "mfa token is invalid or has expired");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn viewers_cannot_enable_mfa() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        let mut app = router().await?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/users/me/mfa/totp")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .body(Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        Ok(())
+    }
+}
@@ -1,5 +1,16 @@
+pub mod admin;
+pub mod api_key;
+pub mod creator;
+pub mod follow;
 pub mod health_check;
+pub mod mfa;
+pub mod oidc;
+pub mod password_reset;
 pub mod pix_payment;
+pub mod post;
+pub mod relationship;
+pub mod session;
+pub mod terms;
 pub mod timeline;
 pub mod user;
 pub mod video;
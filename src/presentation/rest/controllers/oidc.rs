@@ -0,0 +1,513 @@
+use axum::{response::IntoResponse, Extension, Json};
+use hyper::StatusCode;
+use std::sync::Arc;
+use tracing::error;
+
+use crate::domain::{commands, contracts::deps::Deps};
+use crate::presentation::rest::errors::error_into_response;
+use crate::presentation::rest::extensions::context::ExtractContext;
+use crate::presentation::rest::extensions::user::ExtractAuth;
+use crate::presentation::rest::view_models;
+
+/// Returns the url of the issuer the client must send the user to.
+/// The issuer sends the user back to the configured redirect uri
+/// with the parameters expected by `POST /v1/sessions/oidc/callback`.
+#[tracing::instrument(name = "POST /v1/sessions/oidc", skip_all, fields(
+    ctx = ?ctx
+))]
+pub async fn start_oidc_login(
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<view_models::oidc::StartOidcLoginOutput>, axum::response::Response> {
+    let input = commands::session::StartOidcLoginInput { link_user_id: None };
+
+    match commands::session::start_oidc_login(&deps, &ctx, input).await {
+        Ok(output) => Ok(Json(output.into())),
+        Err(error) => {
+            error!(?error, "unable to start oidc login");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+/// Like `POST /v1/sessions/oidc`, but the identity the user logs in with
+/// at the issuer is linked to the account of the logged in user.
+#[tracing::instrument(name = "POST /v1/users/me/identities", skip_all, fields(
+    ctx = ?ctx
+))]
+pub async fn start_identity_link(
+    ExtractAuth(auth): ExtractAuth,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<view_models::oidc::StartOidcLoginOutput>, axum::response::Response> {
+    let input = commands::session::StartOidcLoginInput {
+        link_user_id: Some(auth.user_id),
+    };
+
+    match commands::session::start_oidc_login(&deps, &ctx, input).await {
+        Ok(output) => Ok(Json(output.into())),
+        Err(error) => {
+            error!(?error, "unable to start identity link");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+/// Responds like `POST /v1/sessions`, users with two-factor authentication
+/// enabled get 202 with an mfa token.
+#[tracing::instrument(name = "POST /v1/sessions/oidc/callback", skip_all, fields(
+    ctx = ?ctx
+))]
+pub async fn complete_oidc_login(
+    Json(payload): Json<view_models::oidc::CompleteOidcLoginInput>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<axum::response::Response, axum::response::Response> {
+    match commands::session::complete_oidc_login(&deps, &ctx, payload.into()).await {
+        Ok(commands::session::CreateSessionResult::Created(output)) => Ok((
+            StatusCode::CREATED,
+            Json(view_models::session::CreateSessionOutput::from(output)),
+        )
+            .into_response()),
+        Ok(commands::session::CreateSessionResult::MfaRequired(challenge)) => Ok((
+            StatusCode::ACCEPTED,
+            Json(view_models::session::MfaChallengeOutput::from(challenge)),
+        )
+            .into_response()),
+        Err(error) => {
+            error!(?error, "unable to complete oidc login");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use crate::config::OidcConfig;
+    use crate::domain::constants::X_REQUEST_ID_HEADER_NAME;
+    use crate::domain::contracts::deps::Deps;
+    use crate::domain::value_objects::role::Role;
+    use crate::infra::{factory, jwt, oidc::OidcIdentityProvider, uuid::Uuid};
+    use crate::presentation::rest::traits::{RequestBuilderExt, ResponseExt};
+    use crate::presentation::rest::{deps, router_with_deps, view_models};
+    use axum::{
+        extract::Form,
+        http::{header::CONTENT_TYPE, Request},
+        routing::{get, post},
+        Extension, Json, Router,
+    };
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    use chrono::Utc;
+    use fake::{
+        faker::internet::en::{FreeEmail, Password},
+        Fake,
+    };
+    use hyper::{Body, Method, StatusCode};
+    use jsonwebtoken::{EncodingKey, Header};
+    use serde_json::{json, Value};
+    use sha2::{Digest, Sha256};
+    use tower::Service;
+
+    const CLIENT_ID: &str = "betarme-test";
+
+    /// Issuer served from the test, it hands out id tokens signed with
+    /// the key in testdata for the codes registered with `sign_in`.
+    #[derive(Clone)]
+    struct MockIssuer {
+        url: String,
+        codes: Arc<Mutex<HashMap<String, IssuedCode>>>,
+    }
+
+    struct IssuedCode {
+        nonce: String,
+        code_challenge: String,
+        subject: String,
+        email: String,
+        email_verified: bool,
+    }
+
+    impl MockIssuer {
+        async fn start() -> Result<Self, Box<dyn std::error::Error>> {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+
+            let issuer = Self {
+                url: format!("http://{}", listener.local_addr()?),
+                codes: Arc::default(),
+            };
+
+            let app = Router::new()
+                .route("/.well-known/openid-configuration", get(discovery))
+                .route("/jwks", get(jwks))
+                .route("/token", post(token))
+                .layer(Extension(issuer.clone()));
+
+            tokio::spawn(axum::Server::from_tcp(listener)?.serve(app.into_make_service()));
+
+            Ok(issuer)
+        }
+
+        /// Does what the issuer does after the user logs in: issues a code
+        /// for the authorization request. Returns the code and the state.
+        fn sign_in(
+            &self,
+            authorization_url: &str,
+            subject: &str,
+            email: &str,
+            email_verified: bool,
+        ) -> Result<(String, String), Box<dyn std::error::Error>> {
+            let (endpoint, query) = authorization_url
+                .split_once('?')
+                .ok_or("authorization url has no query")?;
+
+            assert_eq!(endpoint, format!("{}/authorize", self.url));
+
+            let mut params: HashMap<String, String> = serde_urlencoded::from_str(query)?;
+
+            assert_eq!(params["client_id"], CLIENT_ID);
+            assert_eq!(params["code_challenge_method"], "S256");
+
+            let code: String = Password(32..33).fake();
+
+            self.codes.lock().unwrap().insert(
+                code.clone(),
+                IssuedCode {
+                    nonce: params.remove("nonce").ok_or("missing nonce")?,
+                    code_challenge: params.remove("code_challenge").ok_or("missing challenge")?,
+                    subject: subject.to_owned(),
+                    email: email.to_owned(),
+                    email_verified,
+                },
+            );
+
+            Ok((code, params.remove("state").ok_or("missing state")?))
+        }
+    }
+
+    async fn discovery(Extension(issuer): Extension<MockIssuer>) -> Json<Value> {
+        Json(json!({
+            "issuer": issuer.url,
+            "authorization_endpoint": format!("{}/authorize", issuer.url),
+            "token_endpoint": format!("{}/token", issuer.url),
+            "jwks_uri": format!("{}/jwks", issuer.url),
+        }))
+    }
+
+    async fn jwks() -> ([(hyper::header::HeaderName, &'static str); 1], &'static str) {
+        (
+            [(CONTENT_TYPE, "application/json")],
+            include_str!("./testdata/oidc_jwks.json"),
+        )
+    }
+
+    async fn token(
+        Extension(issuer): Extension<MockIssuer>,
+        Form(form): Form<HashMap<String, String>>,
+    ) -> Result<Json<Value>, StatusCode> {
+        let issued = issuer
+            .codes
+            .lock()
+            .unwrap()
+            .remove(&form["code"])
+            .ok_or(StatusCode::BAD_REQUEST)?;
+
+        let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(form["code_verifier"].as_bytes()));
+
+        if challenge != issued.code_challenge || form["client_id"] != CLIENT_ID {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+
+        let now = Utc::now().timestamp();
+
+        let id_token = jsonwebtoken::encode(
+            &Header {
+                kid: Some("test".to_owned()),
+                ..Header::new(jsonwebtoken::Algorithm::RS256)
+            },
+            &json!({
+                "iss": issuer.url,
+                "sub": issued.subject,
+                "aud": CLIENT_ID,
+                "iat": now,
+                "exp": now + 300,
+                "nonce": issued.nonce,
+                "email": issued.email,
+                "email_verified": issued.email_verified,
+            }),
+            &EncodingKey::from_rsa_pem(include_bytes!("./testdata/oidc_private_key.pem")).unwrap(),
+        )
+        .unwrap();
+
+        Ok(Json(json!({
+            "access_token": "unused",
+            "token_type": "Bearer",
+            "id_token": id_token,
+        })))
+    }
+
+    async fn deps_with_issuer(
+        issuer: &MockIssuer,
+    ) -> Result<Arc<Deps>, Box<dyn std::error::Error>> {
+        let deps = deps().await?;
+
+        let identity_provider = OidcIdentityProvider::new(
+            Arc::clone(&deps.http),
+            OidcConfig {
+                issuer_url: issuer.url.clone(),
+                client_id: CLIENT_ID.to_owned(),
+                client_secret: None,
+                redirect_uri: "http://localhost:3000/oidc/callback".to_owned(),
+            },
+        );
+
+        Ok(Arc::new(Deps {
+            identity_provider: Some(Arc::new(identity_provider)),
+            ..deps
+        }))
+    }
+
+    /// Returns the authorization url and the verifier kept by the client.
+    async fn start(app: &mut Router) -> Result<(String, String), Box<dyn std::error::Error>> {
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/sessions/oidc")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .body(Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let output: view_models::oidc::StartOidcLoginOutput = response.json().await?;
+
+        Ok((output.authorization_url, output.verifier))
+    }
+
+    async fn start_link(
+        app: &mut Router,
+        user_id: Uuid,
+    ) -> Result<(String, String), Box<dyn std::error::Error>> {
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/users/me/identities")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .body(Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let output: view_models::oidc::StartOidcLoginOutput = response.json().await?;
+
+        Ok((output.authorization_url, output.verifier))
+    }
+
+    fn callback(code: &str, state: &str, verifier: &str) -> anyhow::Result<hyper::Request<Body>> {
+        Request::builder()
+            .method(Method::POST)
+            .uri("/v1/sessions/oidc/callback")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .json(&view_models::oidc::CompleteOidcLoginInput {
+                code: code.to_owned(),
+                state: state.to_owned(),
+                verifier: verifier.to_owned(),
+            })
+    }
+
+    #[tokio::test]
+    async fn identities_are_only_linked_to_existing_users_by_the_user(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let issuer = MockIssuer::start().await?;
+
+        let deps = deps_with_issuer(&issuer).await?;
+
+        let (user_id, email) = factory::user::create_with_password(
+            &mut deps.db.write().await?,
+            Role::Viewer,
+            &Password(12..20).fake::<String>(),
+        )
+        .await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let subject = user_id.to_string();
+
+        // Owning an identity with the same email is not enough to log in to the account.
+        let (authorization_url, verifier) = start(&mut app).await?;
+        let (code, state) = issuer.sign_in(&authorization_url, &subject, &email, true)?;
+
+        let response = app.call(callback(&code, &state, &verifier)?).await?;
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let (authorization_url, verifier) = start_link(&mut app, user_id).await?;
+        let (code, state) = issuer.sign_in(&authorization_url, &subject, &email, true)?;
+
+        let response = app.call(callback(&code, &state, &verifier)?).await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let session: view_models::session::CreateSessionOutput = response.json().await?;
+
+        let claims: jwt::Claims = jwt::verify(&deps.config.jwt, &session.access_token)?;
+
+        assert_eq!(claims.sub, user_id);
+
+        // The state can only be used once.
+        let (authorization_url, _) = start(&mut app).await?;
+        let (code, _) = issuer.sign_in(&authorization_url, &subject, &email, true)?;
+
+        let response = app.call(callback(&code, &state, &verifier)?).await?;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        // Once linked, the identity logs in the same user even if its email changes.
+        let other_email: String = FreeEmail().fake();
+
+        let (authorization_url, verifier) = start(&mut app).await?;
+        let (code, state) = issuer.sign_in(&authorization_url, &subject, &other_email, false)?;
+
+        let response = app.call(callback(&code, &state, &verifier)?).await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let session: view_models::session::CreateSessionOutput = response.json().await?;
+
+        let claims: jwt::Claims = jwt::verify(&deps.config.jwt, &session.access_token)?;
+
+        assert_eq!(claims.sub, user_id);
+
+        // Other users cannot take over the identity.
+        let other_user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        let (authorization_url, verifier) = start_link(&mut app, other_user_id).await?;
+        let (code, state) = issuer.sign_in(&authorization_url, &subject, &email, true)?;
+
+        let response = app.call(callback(&code, &state, &verifier)?).await?;
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn logins_are_completed_by_the_client_that_started_them(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let issuer = MockIssuer::start().await?;
+
+        let deps = deps_with_issuer(&issuer).await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let subject: String = Password(16..17).fake();
+        let email: String = FreeEmail().fake();
+
+        // Someone sends their own code and state to another client.
+        let (authorization_url, _) = start(&mut app).await?;
+        let (code, state) = issuer.sign_in(&authorization_url, &subject, &email, true)?;
+
+        let (_, other_verifier) = start(&mut app).await?;
+
+        let response = app.call(callback(&code, &state, &other_verifier)?).await?;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn new_identities_need_a_verified_email() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let issuer = MockIssuer::start().await?;
+
+        let deps = deps_with_issuer(&issuer).await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let subject: String = Password(16..17).fake();
+        let email: String = FreeEmail().fake();
+
+        let (authorization_url, verifier) = start(&mut app).await?;
+        let (code, state) = issuer.sign_in(&authorization_url, &subject, &email, false)?;
+
+        let response = app.call(callback(&code, &state, &verifier)?).await?;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        // A new user is created for identities with a verified email nobody uses.
+        let (authorization_url, verifier) = start(&mut app).await?;
+        let (code, state) = issuer.sign_in(&authorization_url, &subject, &email, true)?;
+
+        let response = app.call(callback(&code, &state, &verifier)?).await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        // Codes the issuer issued for another login are rejected.
+        let (authorization_url, verifier) = start(&mut app).await?;
+        let (_, state) = issuer.sign_in(&authorization_url, &subject, &email, true)?;
+        let (authorization_url, _) = start(&mut app).await?;
+        let (code, _) = issuer.sign_in(&authorization_url, &subject, &email, true)?;
+
+        let response = app.call(callback(&code, &state, &verifier)?).await?;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn banned_users_cannot_log_in_with_an_identity() -> Result<(), Box<dyn std::error::Error>>
+    {
+        dotenv::dotenv().ok();
+
+        let issuer = MockIssuer::start().await?;
+
+        let deps = deps_with_issuer(&issuer).await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let subject: String = Password(16..17).fake();
+        let email: String = FreeEmail().fake();
+
+        let (authorization_url, verifier) = start(&mut app).await?;
+        let (code, state) = issuer.sign_in(&authorization_url, &subject, &email, true)?;
+
+        let response = app.call(callback(&code, &state, &verifier)?).await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let session: view_models::session::CreateSessionOutput = response.json().await?;
+
+        let claims: jwt::Claims = jwt::verify(&deps.config.jwt, &session.access_token)?;
+
+        deps.repos
+            .users
+            .set_banned(&mut deps.db.write().await?, claims.sub, true)
+            .await?;
+
+        let (authorization_url, verifier) = start(&mut app).await?;
+        let (code, state) = issuer.sign_in(&authorization_url, &subject, &email, true)?;
+
+        let response = app.call(callback(&code, &state, &verifier)?).await?;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let body: serde_json::Value = response.json().await?;
+
+        assert_eq!(body["message"], "user is banned");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,199 @@
+use axum::{Extension, Json};
+use hyper::StatusCode;
+use std::sync::Arc;
+use tracing::error;
+
+use crate::domain::{commands, contracts::deps::Deps};
+use crate::presentation::rest::errors::error_into_response;
+use crate::presentation::rest::extensions::context::ExtractContext;
+use crate::presentation::rest::view_models;
+
+/// Always answers with 202 so it cannot be used to find out
+/// if there's an account with the email.
+#[tracing::instrument(name = "POST /v1/password-resets", skip_all, fields(
+    ctx = ?ctx
+))]
+pub async fn request_password_reset(
+    Json(payload): Json<view_models::password_reset::RequestPasswordResetInput>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> StatusCode {
+    if let Err(error) = commands::password_reset::request(&deps, &ctx, payload.into()).await {
+        error!(?error, "unable to request password reset");
+    }
+
+    StatusCode::ACCEPTED
+}
+
+#[tracing::instrument(name = "POST /v1/password-resets/confirm", skip_all, fields(
+    ctx = ?ctx
+))]
+pub async fn confirm_password_reset(
+    Json(payload): Json<view_models::password_reset::ConfirmPasswordResetInput>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<StatusCode, axum::response::Response> {
+    if let Err(error) = commands::password_reset::confirm(&deps, &ctx, payload.into()).await {
+        error!(?error, "unable to confirm password reset");
+        return Err(error_into_response(error));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::constants::X_REQUEST_ID_HEADER_NAME;
+    use crate::domain::contracts::{deps::Deps, mailer::Mailer};
+    use crate::infra::mailer::memory::InMemory;
+    use crate::presentation::rest::traits::{RequestBuilderExt, ResponseExt};
+    use crate::presentation::rest::{deps, router_with_deps, view_models};
+    use axum::http::Request;
+    use fake::{
+        faker::internet::en::{FreeEmail, Password},
+        Fake, Faker,
+    };
+    use hyper::{Method, StatusCode};
+    use tower::Service;
+
+    #[tokio::test]
+    async fn can_reset_password() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let mailer = Arc::new(InMemory::default());
+
+        let deps = Arc::new(Deps {
+            mailer: Arc::clone(&mailer) as Arc<dyn Mailer>,
+            ..deps().await?
+        });
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let user: view_models::register::RegisterInput = Faker.fake();
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/users")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .json(&user)?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let login = |password: String| {
+            Request::builder()
+                .method(Method::POST)
+                .uri("/v1/sessions")
+                .header("Content-Type", "application/json")
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .json(&view_models::session::CreateSessionInput {
+                    email: user.email.clone(),
+                    password,
+                })
+        };
+
+        let response = app.call(login(user.password.clone())?).await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let session: view_models::session::CreateSessionOutput = response.json().await?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/password-resets")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .json(&view_models::password_reset::RequestPasswordResetInput {
+                email: user.email.clone(),
+            })?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let mails = mailer.wait_for(&user.email, 2).await;
+
+        // The first email is the one sent to verify the email address.
+        assert_eq!(mails.len(), 2);
+
+        // The token is the last line of the email.
+        let token = mails[1].body.trim_end().lines().last().unwrap().to_owned();
+
+        let new_password: String = Password(12..20).fake();
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/password-resets/confirm")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .json(&view_models::password_reset::ConfirmPasswordResetInput {
+                token,
+                new_password: new_password.clone(),
+            })?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = app.call(login(user.password.clone())?).await?;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app.call(login(new_password)?).await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        // Sessions created before the reset are revoked.
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/sessions/refresh")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .json(&view_models::session::RefreshSessionInput {
+                refresh_token: session.refresh_token,
+            })?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unknown_email_is_accepted() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let mailer = Arc::new(InMemory::default());
+
+        let deps = Arc::new(Deps {
+            mailer: Arc::clone(&mailer) as Arc<dyn Mailer>,
+            ..deps().await?
+        });
+
+        let mut app = router_with_deps(deps);
+
+        let email: String = FreeEmail().fake();
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/password-resets")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .json(&view_models::password_reset::RequestPasswordResetInput {
+                email: email.clone(),
+            })?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        assert!(mailer.sent_to(&email).is_empty());
+
+        Ok(())
+    }
+}
@@ -6,6 +6,7 @@ use crate::domain::commands;
 use crate::domain::contracts::deps::Deps;
 use crate::presentation::rest::errors::error_into_response;
 use crate::presentation::rest::extensions::context::ExtractContext;
+use crate::presentation::rest::extensions::user::ExtractAuth;
 use crate::presentation::rest::view_models;
 use crate::presentation::rest::view_models::pix_payment::StartPixPaymentInput;
 
@@ -14,11 +15,19 @@ use crate::presentation::rest::view_models::pix_payment::StartPixPaymentInput;
     ctx = ?ctx
 ))]
 pub async fn start_pix_payment(
+    ExtractAuth(auth): ExtractAuth,
     Json(payload): Json<StartPixPaymentInput>,
     Extension(deps): Extension<Arc<Deps>>,
     ExtractContext(ctx): ExtractContext,
 ) -> Result<Json<view_models::pix_payment::StartPixPaymentOutput>, axum::response::Response> {
-    match commands::pix_payment::start_payment(&deps, &ctx, payload.into()).await {
+    let input = commands::pix_payment::StartPixPaymentInput {
+        user_id: auth.user_id,
+        email_verified: auth.email_verified,
+        creator_id: payload.creator_id,
+        subscription: payload.subscription.into(),
+    };
+
+    match commands::pix_payment::start_payment(&deps, &ctx, input).await {
         Ok(output) => Ok(Json(output.into())),
         Err(error) => {
             error!(?error, "unable to start pix payment");
@@ -33,11 +42,12 @@ mod tests {
     use std::sync::Arc;
 
     use crate::domain::constants::X_REQUEST_ID_HEADER_NAME;
+    use crate::infra::factory;
     use crate::infra::uuid::Uuid;
     use crate::presentation::rest::traits::{RequestBuilderExt, ResponseExt};
     use crate::presentation::rest::{deps, router, view_models};
     use axum::http::Request;
-    use hyper::Method;
+    use hyper::{Method, StatusCode};
     use tower::ServiceExt;
 
     #[tokio::test]
@@ -46,6 +56,8 @@ mod tests {
 
         let deps = deps().await?;
 
+        let user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
         let app = router().await?;
 
         let body = view_models::pix_payment::StartPixPaymentInput {
@@ -58,6 +70,7 @@ mod tests {
             .uri("/v1/payments/pix")
             .header("Content-Type", "application/json")
             .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
             .extension(Arc::new(deps))
             .json(&body)?;
 
@@ -72,4 +85,35 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn unverified_users_cannot_start_pix_payment() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = deps().await?;
+
+        let user_id = factory::user::create_unverified(&mut deps.db.write().await?).await?;
+
+        let app = router().await?;
+
+        let body = view_models::pix_payment::StartPixPaymentInput {
+            creator_id: Uuid::new_v4(),
+            subscription: view_models::pix_payment::SubscriptionInput::Monthly,
+        };
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/payments/pix")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .extension(Arc::new(deps))
+            .json(&body)?;
+
+        let response = app.oneshot(req).await?;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,349 @@
+use axum::extract::Path;
+use axum::{Extension, Json};
+use hyper::StatusCode;
+use std::sync::Arc;
+use tracing::error;
+
+use crate::domain::{commands, contracts::deps::Deps, queries};
+use crate::infra::uuid::Uuid;
+use crate::presentation::rest::errors::error_into_response;
+use crate::presentation::rest::extensions::context::ExtractContext;
+use crate::presentation::rest::extensions::user::ExtractAuth;
+use crate::presentation::rest::view_models;
+
+/// Creates a post with a video uploaded through `POST /v1/videos`.
+#[tracing::instrument(name = "POST /v1/posts", skip_all, fields(
+    payload = ?payload,
+    ctx = ?ctx
+))]
+pub async fn create_post(
+    ExtractAuth(auth): ExtractAuth,
+    Json(payload): Json<view_models::post::CreatePostInput>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<(StatusCode, Json<view_models::post::CreatePostOutput>), axum::response::Response> {
+    let fields = view_models::post::PostFields::try_from(payload)?;
+
+    let input = commands::post::CreatePostInput {
+        user_id: auth.user_id,
+        role: auth.role,
+        video_id: fields.video_id,
+        description: fields.description,
+        paid: fields.paid,
+        duration_secs: fields.duration_secs,
+    };
+
+    match commands::post::create_post(&deps, &ctx, input).await {
+        Ok(output) => Ok((
+            StatusCode::CREATED,
+            Json(view_models::post::CreatePostOutput { id: output.id }),
+        )),
+        Err(error) => {
+            error!(?error, "unable to create post");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+/// Returns a short lived url to watch the video of a post.
+/// Paid posts require an active subscription to the creator.
+#[tracing::instrument(name = "GET /v1/posts/:id/media", skip_all, fields(
+    post_id = %post_id,
+    ctx = ?ctx
+))]
+pub async fn get_post_media(
+    auth: Option<ExtractAuth>,
+    Path(post_id): Path<Uuid>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<view_models::post::PostMediaOutput>, axum::response::Response> {
+    let viewer_id = auth.map(|ExtractAuth(auth)| auth.user_id);
+
+    match queries::post::get_media::handle(&deps, &ctx, viewer_id, post_id).await {
+        Ok(output) => Ok(Json(view_models::post::PostMediaOutput::from(output))),
+        Err(error) => {
+            error!(?error, "unable to get post media");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::constants::X_REQUEST_ID_HEADER_NAME;
+    use crate::domain::value_objects::role::Role;
+    use crate::infra::factory;
+    use crate::infra::uuid::Uuid;
+    use crate::presentation::rest::traits::{RequestBuilderExt, ResponseExt};
+    use crate::presentation::rest::{deps, router_with_deps, view_models};
+    use axum::{body::Body, http::Request, Router};
+    use chrono::Utc;
+    use hyper::StatusCode;
+    use reqwest::multipart::{self, Part};
+    use tower::Service;
+
+    /// Starts a video upload and uploads the video and the thumbnail
+    /// to object storage, returns the id of the video.
+    async fn upload_video(
+        app: &mut Router,
+        user_id: Uuid,
+        thumbnail: Option<Vec<u8>>,
+    ) -> Result<Uuid, Box<dyn std::error::Error>> {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/videos")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .body(Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let upload: view_models::video::StartVideoUploadOutput = response.json().await?;
+
+        let files = [
+            (
+                upload.presigned_url,
+                include_bytes!("./testdata/image1.png").to_vec(),
+                "video/mp4",
+            ),
+            (
+                upload.thumbnail_presigned_url,
+                thumbnail.unwrap_or_default(),
+                "image/jpeg",
+            ),
+        ];
+
+        for (presigned_url, file, mime) in files {
+            if file.is_empty() {
+                continue;
+            }
+
+            let mut form = multipart::Form::new();
+            for field in presigned_url.form_data_fields.iter() {
+                form = form.text(field.name.clone(), field.value.clone());
+            }
+
+            form = form.part("file", Part::bytes(file).mime_str(mime)?);
+
+            let response = reqwest::Client::new()
+                .post(presigned_url.endpoint)
+                .multipart(form)
+                .send()
+                .await?;
+
+            assert!(response.status().is_success());
+        }
+
+        Ok(upload.video_id)
+    }
+
+    fn create_post_request(
+        user_id: Uuid,
+        video_id: Uuid,
+    ) -> Result<Request<Body>, Box<dyn std::error::Error>> {
+        Ok(Request::builder()
+            .method("POST")
+            .uri("/v1/posts")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .json(&view_models::post::CreatePostInput {
+                video_id,
+                description: "Morning run".to_owned(),
+                paid: true,
+                duration_secs: 95,
+            })?)
+    }
+
+    fn media_request(
+        viewer_id: Option<Uuid>,
+        post_id: Uuid,
+    ) -> Result<Request<Body>, Box<dyn std::error::Error>> {
+        let builder = Request::builder()
+            .method("GET")
+            .uri(format!("/v1/posts/{post_id}/media"))
+            .header(X_REQUEST_ID_HEADER_NAME, 1);
+
+        let builder = match viewer_id {
+            None => builder,
+            Some(viewer_id) => builder.with_user_auth(viewer_id),
+        };
+
+        Ok(builder.body(Body::empty())?)
+    }
+
+    #[tokio::test]
+    async fn posts_are_created_with_the_preview_of_the_video(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let creator_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        // Only the first bytes of a jpeg are checked.
+        let mut thumbnail = vec![0xff, 0xd8, 0xff, 0xe0];
+        thumbnail.resize(200, 0);
+
+        let video_id = upload_video(&mut app, creator_id, Some(thumbnail)).await?;
+
+        let response = app.call(create_post_request(creator_id, video_id)?).await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let post: view_models::post::CreatePostOutput = response.json().await?;
+
+        // Each upload can only be used once.
+        let response = app.call(create_post_request(creator_id, video_id)?).await?;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // Locked posts still show the preview.
+        let req = Request::builder()
+            .method("GET")
+            .uri(format!("/v1/users/{creator_id}/posts"))
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .body(Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let feed: view_models::timeline::TimelineOutput = response.json().await?;
+
+        assert_eq!(feed.posts.len(), 1);
+        assert_eq!(feed.posts[0].id, post.id);
+        assert!(feed.posts[0].locked);
+        assert_eq!(feed.posts[0].preview.duration_secs, Some(95));
+
+        let thumbnail_url = feed.posts[0]
+            .preview
+            .thumbnail_url
+            .clone()
+            .ok_or("thumbnail_url is missing")?;
+
+        assert!(thumbnail_url.ends_with(&format!("thumbnails/{creator_id}/{video_id}")));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn posts_cannot_be_created_before_the_thumbnail_is_uploaded(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let creator_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let other_creator_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let video_id = upload_video(&mut app, creator_id, None).await?;
+
+        let response = app.call(create_post_request(creator_id, video_id)?).await?;
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        // The video belongs to another creator.
+        let response = app
+            .call(create_post_request(other_creator_id, video_id)?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn only_subscribers_and_the_creator_get_the_media_of_paid_posts(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let creator_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let post_id =
+            factory::post::create_with_paid(creator_id, true, &mut deps.db.write().await?).await?;
+
+        let subscriber_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        factory::subscription::create(&mut deps.db.write().await?, subscriber_id, creator_id)
+            .await?;
+
+        let other_user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let cases = [
+            (None, StatusCode::FORBIDDEN),
+            (Some(other_user_id), StatusCode::FORBIDDEN),
+            (Some(subscriber_id), StatusCode::OK),
+            (Some(creator_id), StatusCode::OK),
+        ];
+
+        for (viewer_id, status) in cases {
+            let response = app.call(media_request(viewer_id, post_id)?).await?;
+
+            assert_eq!(response.status(), status);
+
+            if status == StatusCode::OK {
+                let media: view_models::post::PostMediaOutput = response.json().await?;
+
+                assert!(media.video_url.contains("X-Amz-Signature"));
+                assert!(media.expires_at > Utc::now());
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn drafts_are_only_found_by_the_creator() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let creator_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let post_id =
+            factory::post::create_with_paid(creator_id, false, &mut deps.db.write().await?).await?;
+
+        factory::post::set_published_at(post_id, None, &mut deps.db.write().await?).await?;
+
+        let other_user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let response = app
+            .call(media_request(Some(other_user_id), post_id)?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = app.call(media_request(Some(creator_id), post_id)?).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.call(media_request(None, Uuid::new_v4())?).await?;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,572 @@
+use axum::extract::{Path, Query};
+use axum::{Extension, Json};
+use hyper::StatusCode;
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::error;
+
+use crate::domain::constants::RELATIONSHIPS_LIMIT;
+use crate::domain::{commands, contracts::deps::Deps, queries, value_objects::cursor::Cursor};
+use crate::infra::uuid::Uuid;
+use crate::presentation::rest::errors::error_into_response;
+use crate::presentation::rest::extensions::context::ExtractContext;
+use crate::presentation::rest::extensions::user::ExtractAuth;
+use crate::presentation::rest::view_models;
+
+#[derive(Debug, Deserialize)]
+pub struct ListRelationshipsQuery {
+    #[serde(default)]
+    cursor: i64,
+}
+
+impl From<ListRelationshipsQuery> for Cursor {
+    fn from(input: ListRelationshipsQuery) -> Self {
+        Cursor {
+            offset: input.cursor.max(0),
+            limit: RELATIONSHIPS_LIMIT,
+        }
+    }
+}
+
+#[tracing::instrument(name = "POST /v1/users/:id/block", skip_all, fields(
+    user_id = %user_id,
+    ctx = ?ctx
+))]
+pub async fn block(
+    ExtractAuth(auth): ExtractAuth,
+    Path(user_id): Path<Uuid>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<StatusCode, axum::response::Response> {
+    let input = commands::relationship::BlockInput {
+        blocker_id: auth.user_id,
+        blocked_id: user_id,
+    };
+
+    if let Err(error) = commands::relationship::block(&deps, &ctx, input).await {
+        error!(?error, "unable to block user");
+        return Err(error_into_response(error));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(name = "DELETE /v1/users/:id/block", skip_all, fields(
+    user_id = %user_id,
+    ctx = ?ctx
+))]
+pub async fn unblock(
+    ExtractAuth(auth): ExtractAuth,
+    Path(user_id): Path<Uuid>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<StatusCode, axum::response::Response> {
+    let input = commands::relationship::UnblockInput {
+        blocker_id: auth.user_id,
+        blocked_id: user_id,
+    };
+
+    if let Err(error) = commands::relationship::unblock(&deps, &ctx, input).await {
+        error!(?error, "unable to unblock user");
+        return Err(error_into_response(error));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(name = "POST /v1/users/:id/mute", skip_all, fields(
+    user_id = %user_id,
+    ctx = ?ctx
+))]
+pub async fn mute(
+    ExtractAuth(auth): ExtractAuth,
+    Path(user_id): Path<Uuid>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<StatusCode, axum::response::Response> {
+    let input = commands::relationship::MuteInput {
+        muter_id: auth.user_id,
+        muted_id: user_id,
+    };
+
+    if let Err(error) = commands::relationship::mute(&deps, &ctx, input).await {
+        error!(?error, "unable to mute user");
+        return Err(error_into_response(error));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(name = "DELETE /v1/users/:id/mute", skip_all, fields(
+    user_id = %user_id,
+    ctx = ?ctx
+))]
+pub async fn unmute(
+    ExtractAuth(auth): ExtractAuth,
+    Path(user_id): Path<Uuid>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<StatusCode, axum::response::Response> {
+    let input = commands::relationship::UnmuteInput {
+        muter_id: auth.user_id,
+        muted_id: user_id,
+    };
+
+    if let Err(error) = commands::relationship::unmute(&deps, &ctx, input).await {
+        error!(?error, "unable to unmute user");
+        return Err(error_into_response(error));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(name = "GET /v1/users/me/blocks", skip_all, fields(
+    payload = ?payload,
+    ctx = ?ctx
+))]
+pub async fn list_blocked(
+    ExtractAuth(auth): ExtractAuth,
+    Query(payload): Query<ListRelationshipsQuery>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<Vec<view_models::relationship::RelatedUserOutput>>, axum::response::Response> {
+    match queries::relationship::list_blocked::handle(&deps, &ctx, auth.user_id, payload.into())
+        .await
+    {
+        Ok(users) => Ok(Json(
+            users
+                .into_iter()
+                .map(view_models::relationship::RelatedUserOutput::from)
+                .collect(),
+        )),
+        Err(error) => {
+            error!(?error, "unable to list blocked users");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[tracing::instrument(name = "GET /v1/users/me/mutes", skip_all, fields(
+    payload = ?payload,
+    ctx = ?ctx
+))]
+pub async fn list_muted(
+    ExtractAuth(auth): ExtractAuth,
+    Query(payload): Query<ListRelationshipsQuery>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<Vec<view_models::relationship::RelatedUserOutput>>, axum::response::Response> {
+    match queries::relationship::list_muted::handle(&deps, &ctx, auth.user_id, payload.into()).await
+    {
+        Ok(users) => Ok(Json(
+            users
+                .into_iter()
+                .map(view_models::relationship::RelatedUserOutput::from)
+                .collect(),
+        )),
+        Err(error) => {
+            error!(?error, "unable to list muted users");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::constants::X_REQUEST_ID_HEADER_NAME;
+    use crate::domain::value_objects::role::Role;
+    use crate::infra::factory;
+    use crate::infra::uuid::Uuid;
+    use crate::presentation::rest::traits::{
+        follow_request, RequestBuilderExt, ResponseExt, RouterExt,
+    };
+    use crate::presentation::rest::{deps, router_with_deps, view_models};
+    use axum::http::Request;
+    use axum::Router;
+    use hyper::{Body, Method, StatusCode};
+    use tower::Service;
+
+    fn request(
+        method: Method,
+        uri: String,
+        user_id: Uuid,
+    ) -> Result<Request<Body>, Box<dyn std::error::Error>> {
+        Ok(Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .body(Body::empty())?)
+    }
+
+    async fn timeline_post_ids(
+        app: &mut Router,
+        viewer_id: Uuid,
+    ) -> Result<Vec<Uuid>, Box<dyn std::error::Error>> {
+        let response = app
+            .call(request(Method::GET, "/v1/timeline".to_owned(), viewer_id)?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let timeline: view_models::timeline::TimelineOutput = response.json().await?;
+
+        Ok(timeline.posts.into_iter().map(|post| post.id).collect())
+    }
+
+    async fn follow_user_ids(
+        app: &mut Router,
+        uri: String,
+        viewer_id: Option<Uuid>,
+    ) -> Result<Vec<Uuid>, Box<dyn std::error::Error>> {
+        let users: Vec<view_models::follow::FollowUserOutput> = match viewer_id {
+            None => app.get_json(&uri).await?,
+            Some(viewer_id) => {
+                let response = app.call(request(Method::GET, uri, viewer_id)?).await?;
+
+                assert_eq!(response.status(), StatusCode::OK);
+
+                response.json().await?
+            }
+        };
+
+        Ok(users.into_iter().map(|user| user.id).collect())
+    }
+
+    #[tokio::test]
+    async fn blocked_users_cannot_see_each_other() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let creator_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let post_id =
+            factory::post::create_for_user(creator_id, &mut deps.db.write().await?).await?;
+
+        let user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        // Followed by the user and following the creator.
+        let other_creator_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        factory::subscription::create(&mut deps.db.write().await?, user_id, creator_id).await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        for (follower_id, followee_id) in [
+            (user_id, creator_id),
+            (user_id, other_creator_id),
+            (other_creator_id, creator_id),
+        ] {
+            let response = app
+                .call(follow_request(Method::POST, follower_id, followee_id)?)
+                .await?;
+
+            assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        }
+
+        assert!(timeline_post_ids(&mut app, user_id)
+            .await?
+            .contains(&post_id));
+
+        let response = app
+            .call(request(
+                Method::POST,
+                format!("/v1/users/{user_id}/block"),
+                creator_id,
+            )?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        assert!(!timeline_post_ids(&mut app, user_id)
+            .await?
+            .contains(&post_id));
+
+        // The block ended the subscription.
+        assert!(deps
+            .repos
+            .subscriptions
+            .list_active_creator_ids(&mut deps.db.read().await?, user_id, &[creator_id])
+            .await?
+            .is_empty());
+
+        // Each user is left out of the follow lists the other one sees.
+        let followers_uri = format!("/v1/users/{other_creator_id}/followers");
+
+        assert!(follow_user_ids(&mut app, followers_uri.clone(), None)
+            .await?
+            .contains(&user_id));
+        assert!(!follow_user_ids(&mut app, followers_uri, Some(creator_id))
+            .await?
+            .contains(&user_id));
+
+        let following_uri = format!("/v1/users/{other_creator_id}/following");
+
+        assert!(follow_user_ids(&mut app, following_uri.clone(), None)
+            .await?
+            .contains(&creator_id));
+        assert!(!follow_user_ids(&mut app, following_uri, Some(user_id))
+            .await?
+            .contains(&creator_id));
+
+        for (viewer_id, user_id) in [(user_id, creator_id), (creator_id, user_id)] {
+            let response = app
+                .call(request(
+                    Method::GET,
+                    format!("/v1/users/{user_id}"),
+                    viewer_id,
+                )?)
+                .await?;
+
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+
+        let response = app
+            .call(request(
+                Method::POST,
+                format!("/v1/users/{creator_id}/follow"),
+                user_id,
+            )?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = app
+            .call(request(
+                Method::GET,
+                "/v1/users/me/blocks".to_owned(),
+                creator_id,
+            )?)
+            .await?;
+
+        let blocked: Vec<view_models::relationship::RelatedUserOutput> = response.json().await?;
+
+        assert_eq!(
+            blocked.iter().map(|user| user.id).collect::<Vec<_>>(),
+            vec![user_id]
+        );
+
+        let response = app
+            .call(request(
+                Method::DELETE,
+                format!("/v1/users/{user_id}/block"),
+                creator_id,
+            )?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = app
+            .call(request(
+                Method::GET,
+                format!("/v1/users/{creator_id}"),
+                user_id,
+            )?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let profile: view_models::user::UserProfileOutput = response.json().await?;
+
+        // The block removed the follow of the user but not the one of the other creator.
+        assert_eq!(profile.followers_count, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn blocking_ends_subscriptions_in_both_directions(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        // Either user can be the one that blocks.
+        for first_user_blocks in [true, false] {
+            let first_id =
+                factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+            let second_id =
+                factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+            factory::subscription::create(&mut deps.db.write().await?, first_id, second_id).await?;
+            factory::subscription::create(&mut deps.db.write().await?, second_id, first_id).await?;
+
+            let (blocker_id, blocked_id) = if first_user_blocks {
+                (first_id, second_id)
+            } else {
+                (second_id, first_id)
+            };
+
+            let response = app
+                .call(request(
+                    Method::POST,
+                    format!("/v1/users/{blocked_id}/block"),
+                    blocker_id,
+                )?)
+                .await?;
+
+            assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+            for (subscriber_id, creator_id) in [(first_id, second_id), (second_id, first_id)] {
+                assert!(deps
+                    .repos
+                    .subscriptions
+                    .list_active_creator_ids(
+                        &mut deps.db.read().await?,
+                        subscriber_id,
+                        &[creator_id]
+                    )
+                    .await?
+                    .is_empty());
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn blocked_users_are_left_out_of_follow_lists() -> Result<(), Box<dyn std::error::Error>>
+    {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let creator_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let blocker_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let blocked_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        // Both users follow the creator and are followed by them.
+        for (follower_id, followee_id) in [
+            (blocker_id, creator_id),
+            (blocked_id, creator_id),
+            (creator_id, blocker_id),
+            (creator_id, blocked_id),
+        ] {
+            let response = app
+                .call(follow_request(Method::POST, follower_id, followee_id)?)
+                .await?;
+
+            assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        }
+
+        let response = app
+            .call(request(
+                Method::POST,
+                format!("/v1/users/{blocked_id}/block"),
+                blocker_id,
+            )?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        for uri in [
+            format!("/v1/users/{creator_id}/followers"),
+            format!("/v1/users/{creator_id}/following"),
+        ] {
+            let user_ids = follow_user_ids(&mut app, uri.clone(), None).await?;
+
+            assert!(user_ids.contains(&blocker_id));
+            assert!(user_ids.contains(&blocked_id));
+
+            for (viewer_id, hidden_id) in [(blocker_id, blocked_id), (blocked_id, blocker_id)] {
+                let user_ids = follow_user_ids(&mut app, uri.clone(), Some(viewer_id)).await?;
+
+                assert!(!user_ids.contains(&hidden_id));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn muted_creators_are_hidden_from_the_timeline() -> Result<(), Box<dyn std::error::Error>>
+    {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let creator_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let post_id =
+            factory::post::create_for_user(creator_id, &mut deps.db.write().await?).await?;
+
+        let user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        assert!(timeline_post_ids(&mut app, user_id)
+            .await?
+            .contains(&post_id));
+
+        let response = app
+            .call(request(
+                Method::POST,
+                format!("/v1/users/{creator_id}/mute"),
+                user_id,
+            )?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        assert!(!timeline_post_ids(&mut app, user_id)
+            .await?
+            .contains(&post_id));
+
+        // Muting only hides the creator, the profile can still be seen.
+        let response = app
+            .call(request(
+                Method::GET,
+                format!("/v1/users/{creator_id}"),
+                user_id,
+            )?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .call(request(
+                Method::DELETE,
+                format!("/v1/users/{creator_id}/mute"),
+                user_id,
+            )?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = app
+            .call(request(
+                Method::GET,
+                "/v1/users/me/mutes".to_owned(),
+                user_id,
+            )?)
+            .await?;
+
+        let muted: Vec<view_models::relationship::RelatedUserOutput> = response.json().await?;
+
+        assert!(muted.is_empty());
+
+        assert!(timeline_post_ids(&mut app, user_id)
+            .await?
+            .contains(&post_id));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,552 @@
+use axum::{response::IntoResponse, Extension, Json};
+use hyper::StatusCode;
+use std::sync::Arc;
+use tracing::error;
+
+use crate::domain::{commands, contracts::deps::Deps};
+use crate::presentation::rest::errors::error_into_response;
+use crate::presentation::rest::extensions::client_ip::ExtractClientIp;
+use crate::presentation::rest::extensions::context::ExtractContext;
+use crate::presentation::rest::extensions::user::ExtractAuthPendingTerms;
+use crate::presentation::rest::view_models;
+
+/// Users with two-factor authentication enabled get 202 with an mfa token
+/// that must be sent to `POST /v1/sessions/mfa` along with the second factor.
+#[tracing::instrument(name = "POST /v1/sessions", skip_all, fields(
+    ctx = ?ctx
+))]
+pub async fn create_session(
+    Json(payload): Json<view_models::session::CreateSessionInput>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+    ExtractClientIp(ip): ExtractClientIp,
+) -> Result<axum::response::Response, axum::response::Response> {
+    let input = commands::session::CreateSessionInput {
+        ip,
+        ..payload.into()
+    };
+
+    match commands::session::create(&deps, &ctx, input).await {
+        Ok(commands::session::CreateSessionResult::Created(output)) => Ok((
+            StatusCode::CREATED,
+            Json(view_models::session::CreateSessionOutput::from(output)),
+        )
+            .into_response()),
+        Ok(commands::session::CreateSessionResult::MfaRequired(challenge)) => Ok((
+            StatusCode::ACCEPTED,
+            Json(view_models::session::MfaChallengeOutput::from(challenge)),
+        )
+            .into_response()),
+        Err(error) => {
+            error!(?error, "unable to create session");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[tracing::instrument(name = "POST /v1/sessions/mfa", skip_all, fields(
+    ctx = ?ctx
+))]
+pub async fn verify_mfa(
+    Json(payload): Json<view_models::session::VerifyMfaInput>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+    ExtractClientIp(ip): ExtractClientIp,
+) -> Result<(StatusCode, Json<view_models::session::CreateSessionOutput>), axum::response::Response>
+{
+    let input = commands::session::VerifyMfaInput {
+        ip,
+        ..payload.try_into()?
+    };
+
+    match commands::session::verify_mfa(&deps, &ctx, input).await {
+        Ok(output) => Ok((StatusCode::CREATED, Json(output.into()))),
+        Err(error) => {
+            error!(?error, "unable to verify second factor");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[tracing::instrument(name = "POST /v1/sessions/refresh", skip_all, fields(
+    ctx = ?ctx
+))]
+pub async fn refresh_session(
+    Json(payload): Json<view_models::session::RefreshSessionInput>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<view_models::session::CreateSessionOutput>, axum::response::Response> {
+    match commands::session::refresh(&deps, &ctx, payload.into()).await {
+        Ok(output) => Ok(Json(output.into())),
+        Err(error) => {
+            error!(?error, "unable to refresh session");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+/// Users that have not accepted the current terms can still log out.
+#[tracing::instrument(name = "DELETE /v1/sessions/current", skip_all, fields(
+    ctx = ?ctx
+))]
+pub async fn delete_current_session(
+    ExtractAuthPendingTerms(auth): ExtractAuthPendingTerms,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<StatusCode, axum::response::Response> {
+    let input = commands::session::DeleteSessionInput {
+        session_id: auth.session_id,
+    };
+
+    if let Err(error) = commands::session::delete(&deps, &ctx, input).await {
+        error!(?error, "unable to delete session");
+        return Err(error_into_response(error));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Users that have not accepted the current terms can still log out.
+#[tracing::instrument(name = "DELETE /v1/sessions", skip_all, fields(
+    ctx = ?ctx
+))]
+pub async fn delete_all_sessions(
+    ExtractAuthPendingTerms(auth): ExtractAuthPendingTerms,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<StatusCode, axum::response::Response> {
+    let input = commands::session::DeleteAllSessionsInput {
+        user_id: auth.user_id,
+    };
+
+    if let Err(error) = commands::session::delete_all(&deps, &ctx, input).await {
+        error!(?error, "unable to delete sessions");
+        return Err(error_into_response(error));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::config::Config;
+    use crate::domain::constants::{
+        AUTHORIZATION_HEADER_NAME, LOGIN_LOCKOUT_BASE_SECS, LOGIN_MAX_FAILED_ATTEMPTS_PER_ACCOUNT,
+        LOGIN_MAX_FAILED_ATTEMPTS_PER_IP, X_FORWARDED_FOR_HEADER_NAME, X_REQUEST_ID_HEADER_NAME,
+    };
+    use crate::domain::contracts::deps::Deps;
+    use crate::domain::value_objects::role::Role;
+    use crate::infra::factory;
+    use crate::presentation::rest::traits::{RequestBuilderExt, ResponseExt};
+    use crate::presentation::rest::{deps, router, router_with_deps, view_models};
+    use axum::{http::Request, Router};
+    use fake::{
+        faker::internet::en::{FreeEmail, Password},
+        Fake,
+    };
+    use hyper::{header::RETRY_AFTER, Body, Method, StatusCode};
+    use tower::Service;
+
+    /// Registers a new user and logs in with it.
+    async fn login(
+        app: &mut Router,
+        deps: &Arc<Deps>,
+    ) -> Result<view_models::session::CreateSessionOutput, Box<dyn std::error::Error>> {
+        let user = view_models::register::RegisterInput {
+            username: factory::user::username(),
+            email: FreeEmail().fake(),
+            password: Password(12..20).fake(),
+            accepted_terms_version: factory::terms::CURRENT_VERSION.to_owned(),
+        };
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/users")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .extension(Arc::clone(deps))
+            .json(&user)?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/sessions")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .extension(Arc::clone(deps))
+            .json(&view_models::session::CreateSessionInput {
+                email: user.email,
+                password: user.password,
+            })?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        Ok(response.json().await?)
+    }
+
+    async fn refresh(
+        app: &mut Router,
+        deps: &Arc<Deps>,
+        refresh_token: &str,
+    ) -> Result<hyper::Response<axum::body::BoxBody>, Box<dyn std::error::Error>> {
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/sessions/refresh")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .extension(Arc::clone(deps))
+            .json(&view_models::session::RefreshSessionInput {
+                refresh_token: refresh_token.to_owned(),
+            })?;
+
+        Ok(app.call(req).await?)
+    }
+
+    async fn delete_session(
+        app: &mut Router,
+        deps: &Arc<Deps>,
+        uri: &str,
+        access_token: &str,
+    ) -> Result<hyper::Response<axum::body::BoxBody>, Box<dyn std::error::Error>> {
+        let req = Request::builder()
+            .method(Method::DELETE)
+            .uri(uri)
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .header(AUTHORIZATION_HEADER_NAME, format!("Bearer {access_token}"))
+            .extension(Arc::clone(deps))
+            .body(Body::empty())?;
+
+        Ok(app.call(req).await?)
+    }
+
+    #[tokio::test]
+    async fn can_create_session() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let mut app = router().await?;
+
+        let user = view_models::register::RegisterInput {
+            username: factory::user::username(),
+            email: FreeEmail().fake(),
+            password: Password(12..20).fake(),
+            accepted_terms_version: factory::terms::CURRENT_VERSION.to_owned(),
+        };
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/users")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .extension(Arc::clone(&deps))
+            .json(&user)?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        // Wrong password.
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/sessions")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .extension(Arc::clone(&deps))
+            .json(&view_models::session::CreateSessionInput {
+                email: user.email.clone(),
+                password: format!("{}-wrong", user.password),
+            })?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        // Right password.
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/sessions")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .extension(Arc::clone(&deps))
+            .json(&view_models::session::CreateSessionInput {
+                email: user.email.clone(),
+                password: user.password.clone(),
+            })?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let body: view_models::session::CreateSessionOutput = response.json().await?;
+
+        assert!(!body.access_token.is_empty());
+        assert_eq!(body.token_type, "Bearer");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn locks_account_after_too_many_failed_attempts() -> Result<(), Box<dyn std::error::Error>>
+    {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let mut app = router().await?;
+
+        let user = view_models::register::RegisterInput {
+            username: factory::user::username(),
+            email: FreeEmail().fake(),
+            password: Password(12..20).fake(),
+            accepted_terms_version: factory::terms::CURRENT_VERSION.to_owned(),
+        };
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/users")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .extension(Arc::clone(&deps))
+            .json(&user)?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let login = |password: String| {
+            Request::builder()
+                .method(Method::POST)
+                .uri("/v1/sessions")
+                .header("Content-Type", "application/json")
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .extension(Arc::clone(&deps))
+                .json(&view_models::session::CreateSessionInput {
+                    email: user.email.clone(),
+                    password,
+                })
+        };
+
+        for _ in 0..LOGIN_MAX_FAILED_ATTEMPTS_PER_ACCOUNT {
+            let response = app.call(login(format!("{}-wrong", user.password))?).await?;
+
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+
+        // The account is locked even if the password is right.
+        let response = app.call(login(user.password.clone())?).await?;
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        let retry_after: i64 = response
+            .headers()
+            .get(RETRY_AFTER)
+            .expect("retry-after header")
+            .to_str()?
+            .parse()?;
+
+        assert!(retry_after > 0 && retry_after <= LOGIN_LOCKOUT_BASE_SECS);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn refresh_rotates_token_and_detects_reuse() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let mut app = router().await?;
+
+        let session = login(&mut app, &deps).await?;
+
+        let response = refresh(&mut app, &deps, &session.refresh_token).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let rotated: view_models::session::CreateSessionOutput = response.json().await?;
+
+        assert_ne!(rotated.refresh_token, session.refresh_token);
+
+        // Using the first refresh token again revokes the whole session.
+        let response = refresh(&mut app, &deps, &session.refresh_token).await?;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = refresh(&mut app, &deps, &rotated.refresh_token).await?;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = delete_session(
+            &mut app,
+            &deps,
+            "/v1/sessions/current",
+            &rotated.access_token,
+        )
+        .await?;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn banned_users_cannot_log_in_or_refresh() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let password: String = Password(12..20).fake();
+
+        let (user_id, email) = factory::user::create_with_password(
+            &mut deps.db.write().await?,
+            Role::Viewer,
+            &password,
+        )
+        .await?;
+
+        let login = || {
+            Request::builder()
+                .method(Method::POST)
+                .uri("/v1/sessions")
+                .header("Content-Type", "application/json")
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .json(&view_models::session::CreateSessionInput {
+                    email: email.clone(),
+                    password: password.clone(),
+                })
+        };
+
+        let response = app.call(login()?).await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let session: view_models::session::CreateSessionOutput = response.json().await?;
+
+        deps.repos
+            .users
+            .set_banned(&mut deps.db.write().await?, user_id, true)
+            .await?;
+
+        let response = app.call(login()?).await?;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let body: serde_json::Value = response.json().await?;
+
+        assert_eq!(body["message"], "user is banned");
+
+        let response = refresh(&mut app, &deps, &session.refresh_token).await?;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let body: serde_json::Value = response.json().await?;
+
+        assert_eq!(body["message"], "user is banned");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn can_delete_current_session() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let mut app = router().await?;
+
+        let session = login(&mut app, &deps).await?;
+
+        let response = delete_session(
+            &mut app,
+            &deps,
+            "/v1/sessions/current",
+            &session.access_token,
+        )
+        .await?;
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = delete_session(
+            &mut app,
+            &deps,
+            "/v1/sessions/current",
+            &session.access_token,
+        )
+        .await?;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = refresh(&mut app, &deps, &session.refresh_token).await?;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn clients_cannot_choose_their_ip_behind_a_proxy(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = deps().await?;
+
+        let deps = Arc::new(Deps {
+            config: Arc::new(Config {
+                trusted_proxy_hops: 1,
+                ..Config::from_env()?
+            }),
+            ..deps
+        });
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        // Address the proxy received the request from.
+        let ip = format!(
+            "10.{}.{}.{}",
+            rand::random::<u8>(),
+            rand::random::<u8>(),
+            rand::random::<u8>()
+        );
+
+        let login = || {
+            // Entries before the one added by the proxy are sent by the client.
+            let spoofed_ip = format!("192.0.2.{}", rand::random::<u8>());
+
+            Request::builder()
+                .method(Method::POST)
+                .uri("/v1/sessions")
+                .header("Content-Type", "application/json")
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .header(X_FORWARDED_FOR_HEADER_NAME, format!("{spoofed_ip}, {ip}"))
+                .json(&view_models::session::CreateSessionInput {
+                    email: FreeEmail().fake(),
+                    password: Password(12..20).fake(),
+                })
+        };
+
+        for _ in 0..LOGIN_MAX_FAILED_ATTEMPTS_PER_IP {
+            let response = app.call(login()?).await?;
+
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+
+        let response = app.call(login()?).await?;
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        Ok(())
+    }
+}
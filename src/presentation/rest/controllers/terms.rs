@@ -0,0 +1,169 @@
+use axum::{Extension, Json};
+use hyper::StatusCode;
+use std::sync::Arc;
+use tracing::error;
+
+use crate::domain::{commands, contracts::deps::Deps, queries};
+use crate::presentation::rest::errors::error_into_response;
+use crate::presentation::rest::extensions::context::ExtractContext;
+use crate::presentation::rest::extensions::user::ExtractAuthPendingTerms;
+use crate::presentation::rest::view_models;
+
+#[tracing::instrument(name = "GET /v1/terms/current", skip_all, fields(
+    ctx = ?ctx
+))]
+pub async fn get_current_terms(
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<view_models::terms::TermsVersionOutput>, axum::response::Response> {
+    match queries::terms::get_current::handle(&deps, &ctx).await {
+        Ok(terms) => Ok(Json(terms.into())),
+        Err(error) => {
+            error!(?error, "unable to get current terms");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[tracing::instrument(name = "POST /v1/users/me/terms", skip_all, fields(
+    payload = ?payload,
+    ctx = ?ctx
+))]
+pub async fn accept_terms(
+    ExtractAuthPendingTerms(auth): ExtractAuthPendingTerms,
+    Json(payload): Json<view_models::terms::AcceptTermsInput>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<StatusCode, axum::response::Response> {
+    let input = commands::terms::AcceptTermsInput {
+        user_id: auth.user_id,
+        version: payload.accepted_terms_version,
+    };
+
+    if let Err(error) = commands::terms::accept_terms(&deps, &ctx, input).await {
+        error!(?error, "unable to accept terms");
+        return Err(error_into_response(error));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::constants::X_REQUEST_ID_HEADER_NAME;
+    use crate::infra::factory;
+    use crate::presentation::rest::traits::{RequestBuilderExt, ResponseExt};
+    use crate::presentation::rest::{deps, router_with_deps, view_models};
+    use axum::http::Request;
+    use fake::{Fake, Faker};
+    use hyper::{Body, StatusCode};
+    use serde_json::Value;
+    use tower::Service;
+
+    #[tokio::test]
+    async fn users_must_accept_the_current_terms() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        factory::terms::remove_acceptances(&mut deps.db.write().await?, user_id).await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let list_blocks = || {
+            Request::builder()
+                .method("GET")
+                .uri("/v1/users/me/blocks")
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .with_user_auth(user_id)
+                .body(Body::empty())
+        };
+
+        let response = app.call(list_blocks()?).await?;
+
+        assert_eq!(response.status(), StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS);
+
+        let body: Value = response.json().await?;
+
+        assert_eq!(body["terms_version"], factory::terms::CURRENT_VERSION);
+
+        let cases = [
+            ("1999-01-01", StatusCode::UNPROCESSABLE_ENTITY),
+            (factory::terms::CURRENT_VERSION, StatusCode::NO_CONTENT),
+        ];
+
+        for (version, status) in cases {
+            let req = Request::builder()
+                .method("POST")
+                .uri("/v1/users/me/terms")
+                .header("Content-Type", "application/json")
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .with_user_auth(user_id)
+                .json(view_models::terms::AcceptTermsInput {
+                    accepted_terms_version: version.to_owned(),
+                })?;
+
+            let response = app.call(req).await?;
+
+            assert_eq!(response.status(), status);
+        }
+
+        let response = app.call(list_blocks()?).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn registration_requires_the_current_terms() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let response = app
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri("/v1/terms/current")
+                    .header(X_REQUEST_ID_HEADER_NAME, 1)
+                    .body(Body::empty())?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let terms: view_models::terms::TermsVersionOutput = response.json().await?;
+
+        assert_eq!(terms.version, factory::terms::CURRENT_VERSION);
+
+        let user = view_models::register::RegisterInput {
+            accepted_terms_version: "1999-01-01".to_owned(),
+            ..Faker.fake()
+        };
+
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/users")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .json(&user)?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let error: view_models::ValidationError = response.json().await?;
+
+        assert_eq!(error.name, "accepted_terms_version");
+
+        Ok(())
+    }
+}
@@ -1,41 +1,42 @@
-use axum::extract::Query;
+use axum::extract::{Path, Query};
 use axum::{Extension, Json};
 use serde::Deserialize;
 use std::sync::Arc;
 use tracing::error;
 
-use crate::domain::constants::TIMELINE_LIMIT;
-use crate::domain::{contracts::deps::Deps, queries, value_objects::cursor::Cursor};
+use crate::domain::{contracts::deps::Deps, queries};
+use crate::infra::uuid::Uuid;
 use crate::presentation::rest::errors::error_into_response;
 use crate::presentation::rest::extensions::context::ExtractContext;
+use crate::presentation::rest::extensions::user::ExtractAuth;
 use crate::presentation::rest::view_models;
 
 #[derive(Debug, Deserialize)]
 pub struct GetTimelineQuery {
-    cursor: i64,
+    /// The `next_cursor` of the previous page, not sent to get the first page.
+    cursor: Option<String>,
 }
 
+/// The explore timeline, with posts of every creator.
 #[tracing::instrument(name = "GET /v1/timeline", skip_all, fields(
     payload = ?payload,
     ctx = ?ctx
 ))]
 pub async fn get_timeline(
+    auth: Option<ExtractAuth>,
     Query(payload): Query<GetTimelineQuery>,
     Extension(deps): Extension<Arc<Deps>>,
     ExtractContext(ctx): ExtractContext,
-) -> Result<Json<Vec<view_models::timeline::PostOutput>>, axum::response::Response> {
-    let cursor = Cursor {
-        offset: payload.cursor,
-        limit: TIMELINE_LIMIT,
-    };
-
-    match queries::timeline::get_timeline::handle(&deps, &ctx, cursor).await {
-        Ok(posts) => Ok(Json(
-            posts
-                .into_iter()
-                .map(view_models::timeline::PostOutput::from)
-                .collect(),
-        )),
+) -> Result<Json<view_models::timeline::TimelineOutput>, axum::response::Response> {
+    let cursor = view_models::timeline::decode_cursor(payload.cursor, &deps.config.cursor.secret)?;
+
+    let viewer_id = auth.map(|ExtractAuth(auth)| auth.user_id);
+
+    match queries::timeline::get_timeline::handle(&deps, &ctx, viewer_id, cursor).await {
+        Ok(page) => Ok(Json(view_models::timeline::TimelineOutput::new(
+            page,
+            &deps.config.cursor.secret,
+        ))),
         Err(error) => {
             error!(?error, "unable to fetch timeline");
 
@@ -44,16 +45,130 @@ pub async fn get_timeline(
     }
 }
 
+/// Posts of the creators the user follows or subscribes to.
+#[tracing::instrument(name = "GET /v1/timeline/following", skip_all, fields(
+    payload = ?payload,
+    ctx = ?ctx
+))]
+pub async fn get_following_timeline(
+    ExtractAuth(auth): ExtractAuth,
+    Query(payload): Query<GetTimelineQuery>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<view_models::timeline::TimelineOutput>, axum::response::Response> {
+    let cursor = view_models::timeline::decode_cursor(payload.cursor, &deps.config.cursor.secret)?;
+
+    match queries::timeline::get_following_timeline::handle(&deps, &ctx, auth.user_id, cursor).await
+    {
+        Ok(page) => Ok(Json(view_models::timeline::TimelineOutput::new(
+            page,
+            &deps.config.cursor.secret,
+        ))),
+        Err(error) => {
+            error!(?error, "unable to fetch following timeline");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+/// Posts of a single creator, pinned posts first.
+#[tracing::instrument(name = "GET /v1/users/:id/posts", skip_all, fields(
+    user_id = %user_id,
+    payload = ?payload,
+    ctx = ?ctx
+))]
+pub async fn get_creator_feed(
+    auth: Option<ExtractAuth>,
+    Path(user_id): Path<Uuid>,
+    Query(payload): Query<GetTimelineQuery>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<view_models::timeline::TimelineOutput>, axum::response::Response> {
+    let cursor = view_models::timeline::decode_cursor(payload.cursor, &deps.config.cursor.secret)?;
+
+    let viewer_id = auth.map(|ExtractAuth(auth)| auth.user_id);
+
+    match queries::post::list_by_creator::handle(&deps, &ctx, viewer_id, user_id, cursor).await {
+        Ok(page) => Ok(Json(view_models::timeline::TimelineOutput::new(
+            page,
+            &deps.config.cursor.secret,
+        ))),
+        Err(error) => {
+            error!(?error, "unable to fetch creator feed");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
 
-    use crate::domain::constants::X_REQUEST_ID_HEADER_NAME;
+    use crate::domain::constants::{PINNED_POSTS_LIMIT, X_REQUEST_ID_HEADER_NAME};
+    use crate::domain::value_objects::{post_cursor::PostCursor, role::Role};
     use crate::infra::factory;
-    use crate::presentation::rest::{deps, router};
-    use axum::{body::Body, http::Request};
+    use crate::infra::uuid::Uuid;
+    use crate::presentation::rest::traits::{follow_request, RequestBuilderExt, ResponseExt};
+    use crate::presentation::rest::{deps, router, router_with_deps, view_models};
+    use axum::{body::Body, http::Request, Router};
+    use chrono::Utc;
+    use hyper::{Method, StatusCode};
     use rand::Rng;
-    use tower::ServiceExt;
+    use std::collections::HashSet;
+    use tower::{Service, ServiceExt};
+
+    /// Follows the cursors of the explore timeline until the post is found.
+    async fn find_post(
+        app: &mut Router,
+        viewer_id: Option<Uuid>,
+        post_id: Uuid,
+    ) -> Result<view_models::timeline::PostOutput, Box<dyn std::error::Error>> {
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let response = app
+                .call(timeline_request(viewer_id, cursor.as_deref())?)
+                .await?;
+
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let timeline: view_models::timeline::TimelineOutput = response.json().await?;
+
+            if let Some(post) = timeline.posts.into_iter().find(|post| post.id == post_id) {
+                return Ok(post);
+            }
+
+            cursor = Some(
+                timeline
+                    .next_cursor
+                    .ok_or("post not found in the timeline")?,
+            );
+        }
+    }
+
+    fn timeline_request(
+        viewer_id: Option<Uuid>,
+        cursor: Option<&str>,
+    ) -> Result<Request<Body>, Box<dyn std::error::Error>> {
+        let uri = match cursor {
+            None => "/v1/timeline".to_owned(),
+            Some(cursor) => format!("/v1/timeline?cursor={cursor}"),
+        };
+
+        let builder = Request::builder()
+            .method("GET")
+            .uri(uri)
+            .header(X_REQUEST_ID_HEADER_NAME, 1);
+
+        let builder = match viewer_id {
+            None => builder,
+            Some(viewer_id) => builder.with_user_auth(viewer_id),
+        };
+
+        Ok(builder.body(Body::empty())?)
+    }
 
     #[tokio::test]
     #[ignore]
@@ -94,7 +209,7 @@ mod tests {
 
         let req = Request::builder()
             .method("GET")
-            .uri("/v1/timeline?cursor=0")
+            .uri("/v1/timeline")
             .header("Content-Type", "application/json")
             .header(X_REQUEST_ID_HEADER_NAME, 1)
             .extension(Arc::new(deps))
@@ -106,4 +221,426 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn pages_do_not_repeat_posts() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let creator_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let mut post_ids = HashSet::new();
+
+        for _ in 0..3 {
+            post_ids.insert(
+                factory::post::create_for_user(creator_id, &mut deps.db.write().await?).await?,
+            );
+        }
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let mut seen: HashSet<Uuid> = HashSet::new();
+        let mut last: Option<view_models::timeline::PostOutput> = None;
+        let mut cursor: Option<String> = None;
+
+        // Posts created by other tests in the meantime go to the top,
+        // so ours are found by following the cursors.
+        while !post_ids.is_subset(&seen) {
+            let response = app.call(timeline_request(None, cursor.as_deref())?).await?;
+
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let timeline: view_models::timeline::TimelineOutput = response.json().await?;
+
+            for post in timeline.posts {
+                assert!(seen.insert(post.id), "post {} was returned twice", post.id);
+
+                if let Some(last) = &last {
+                    assert!((post.published_at, post.id) < (last.published_at, last.id));
+                }
+
+                last = Some(post);
+            }
+
+            cursor = match timeline.next_cursor {
+                None => break,
+                Some(cursor) => Some(cursor),
+            };
+        }
+
+        assert!(post_ids.is_subset(&seen));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_cursors_that_were_not_issued_by_us() -> Result<(), Box<dyn std::error::Error>>
+    {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let forged = PostCursor {
+            published_at: Utc::now(),
+            id: Uuid::new_v4(),
+        }
+        .encode("another-secret");
+
+        for cursor in [forged.as_str(), "not-a-cursor"] {
+            let response = app.call(timeline_request(None, Some(cursor))?).await?;
+
+            assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+            let error: view_models::ValidationError = response.json().await?;
+
+            assert_eq!(error.name, "cursor");
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn following_timeline_only_has_followed_and_subscribed_creators(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let mut creator_ids = vec![];
+        let mut post_ids = vec![];
+
+        for _ in 0..3 {
+            let creator_id =
+                factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+            post_ids.push(
+                factory::post::create_for_user(creator_id, &mut deps.db.write().await?).await?,
+            );
+
+            creator_ids.push(creator_id);
+        }
+
+        let (followed_id, subscribed_id) = (creator_ids[0], creator_ids[1]);
+
+        let user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        factory::subscription::create(&mut deps.db.write().await?, user_id, subscribed_id).await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let response = app
+            .call(follow_request(Method::POST, user_id, followed_id)?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = app
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri("/v1/timeline/following")
+                    .header(X_REQUEST_ID_HEADER_NAME, 1)
+                    .with_user_auth(user_id)
+                    .body(Body::empty())?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let timeline: view_models::timeline::TimelineOutput = response.json().await?;
+
+        let ids: Vec<Uuid> = timeline.posts.into_iter().map(|post| post.id).collect();
+
+        assert_eq!(ids, vec![post_ids[1], post_ids[0]]);
+        assert_eq!(timeline.next_cursor, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn posts_of_deleted_creators_are_not_in_the_timelines(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let creator_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let post_id =
+            factory::post::create_for_user(creator_id, &mut deps.db.write().await?).await?;
+
+        let user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        factory::subscription::create(&mut deps.db.write().await?, user_id, creator_id).await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let following_timeline_request = || {
+            Request::builder()
+                .method("GET")
+                .uri("/v1/timeline/following")
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .with_user_auth(user_id)
+                .body(Body::empty())
+        };
+
+        let response = app.call(following_timeline_request()?).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let timeline: view_models::timeline::TimelineOutput = response.json().await?;
+
+        let ids: Vec<Uuid> = timeline.posts.into_iter().map(|post| post.id).collect();
+
+        assert_eq!(ids, vec![post_id]);
+
+        let response = app
+            .call(
+                Request::builder()
+                    .method("DELETE")
+                    .uri("/v1/users/me")
+                    .header(X_REQUEST_ID_HEADER_NAME, 1)
+                    .with_user_auth(creator_id)
+                    .body(Body::empty())?,
+            )
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = app.call(following_timeline_request()?).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let timeline: view_models::timeline::TimelineOutput = response.json().await?;
+
+        assert!(timeline.posts.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn paid_posts_are_locked_for_viewers_that_are_not_subscribed(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let creator_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let post_id =
+            factory::post::create_with_paid(creator_id, true, &mut deps.db.write().await?).await?;
+
+        let subscriber_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        factory::subscription::create(&mut deps.db.write().await?, subscriber_id, creator_id)
+            .await?;
+
+        let other_user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let cases = [
+            (None, true),
+            (Some(other_user_id), true),
+            (Some(subscriber_id), false),
+            (Some(creator_id), false),
+        ];
+
+        for (viewer_id, locked) in cases {
+            let post = find_post(&mut app, viewer_id, post_id).await?;
+
+            assert_eq!(post.locked, locked);
+            assert!(post.preview.thumbnail_url.is_some());
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn creator_feed_has_pinned_posts_first_and_drafts_only_for_the_creator(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let creator_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let mut post_ids = vec![];
+
+        for _ in 0..5 {
+            post_ids.push(
+                factory::post::create_for_user(creator_id, &mut deps.db.write().await?).await?,
+            );
+        }
+
+        let (oldest, pinned, draft, scheduled, newest) = (
+            post_ids[0],
+            post_ids[1],
+            post_ids[2],
+            post_ids[3],
+            post_ids[4],
+        );
+
+        factory::post::pin(pinned, &mut deps.db.write().await?).await?;
+        factory::post::set_published_at(draft, None, &mut deps.db.write().await?).await?;
+        factory::post::set_published_at(
+            scheduled,
+            Some(Utc::now() + chrono::Duration::days(1)),
+            &mut deps.db.write().await?,
+        )
+        .await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let cases = [
+            (None, vec![pinned, newest, oldest]),
+            (
+                Some(creator_id),
+                vec![pinned, scheduled, newest, draft, oldest],
+            ),
+        ];
+
+        for (viewer_id, expected) in cases {
+            let builder = Request::builder()
+                .method("GET")
+                .uri(format!("/v1/users/{creator_id}/posts"))
+                .header(X_REQUEST_ID_HEADER_NAME, 1);
+
+            let builder = match viewer_id {
+                None => builder,
+                Some(viewer_id) => builder.with_user_auth(viewer_id),
+            };
+
+            let response = app.call(builder.body(Body::empty())?).await?;
+
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let feed: view_models::timeline::TimelineOutput = response.json().await?;
+
+            let ids: Vec<Uuid> = feed.posts.iter().map(|post| post.id).collect();
+
+            assert_eq!(ids, expected);
+            assert!(feed.posts[0].pinned);
+        }
+
+        let req = Request::builder()
+            .method("GET")
+            .uri(format!("/v1/users/{}/posts", Uuid::new_v4()))
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .body(Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        Ok(())
+    }
+
+    fn creator_feed_request(
+        creator_id: Uuid,
+        viewer_id: Option<Uuid>,
+    ) -> Result<Request<Body>, Box<dyn std::error::Error>> {
+        let builder = Request::builder()
+            .method("GET")
+            .uri(format!("/v1/users/{creator_id}/posts"))
+            .header(X_REQUEST_ID_HEADER_NAME, 1);
+
+        let builder = match viewer_id {
+            None => builder,
+            Some(viewer_id) => builder.with_user_auth(viewer_id),
+        };
+
+        Ok(builder.body(Body::empty())?)
+    }
+
+    #[tokio::test]
+    async fn scheduled_posts_are_ordered_by_the_time_they_are_published(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let creator_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let scheduled =
+            factory::post::create_for_user(creator_id, &mut deps.db.write().await?).await?;
+        let published =
+            factory::post::create_for_user(creator_id, &mut deps.db.write().await?).await?;
+
+        // The post was scheduled before the other one was created and went live after it.
+        factory::post::set_published_at(
+            published,
+            Some(Utc::now() - chrono::Duration::hours(1)),
+            &mut deps.db.write().await?,
+        )
+        .await?;
+        factory::post::set_published_at(
+            scheduled,
+            Some(Utc::now() - chrono::Duration::minutes(1)),
+            &mut deps.db.write().await?,
+        )
+        .await?;
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let response = app.call(creator_feed_request(creator_id, None)?).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let feed: view_models::timeline::TimelineOutput = response.json().await?;
+
+        let ids: Vec<Uuid> = feed.posts.iter().map(|post| post.id).collect();
+
+        assert_eq!(ids, vec![scheduled, published]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn pinned_posts_beyond_the_limit_are_part_of_the_creator_feed(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let creator_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let mut post_ids = vec![];
+
+        for _ in 0..PINNED_POSTS_LIMIT + 1 {
+            let post_id =
+                factory::post::create_for_user(creator_id, &mut deps.db.write().await?).await?;
+
+            factory::post::pin(post_id, &mut deps.db.write().await?).await?;
+
+            post_ids.push(post_id);
+        }
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let response = app.call(creator_feed_request(creator_id, None)?).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let feed: view_models::timeline::TimelineOutput = response.json().await?;
+
+        let ids: Vec<Uuid> = feed.posts.iter().map(|post| post.id).collect();
+
+        // The most recently pinned posts come first, the oldest one is back in the feed.
+        post_ids.reverse();
+
+        assert_eq!(ids, post_ids);
+        assert_eq!(feed.next_cursor, None);
+
+        Ok(())
+    }
 }
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use axum::extract::Path;
 use axum::{response::IntoResponse, Extension, Json};
 use chrono::Utc;
 use hyper::StatusCode;
@@ -8,10 +9,12 @@ use tracing::error;
 use crate::domain::commands::user::CreateUserInput;
 use crate::domain::contracts::deps::Deps;
 use crate::domain::errors::ValidationError;
-use crate::domain::value_objects::{email::Email, password::Password};
-use crate::domain::{self, commands};
+use crate::domain::value_objects::{email::Email, password::Password, username::Username};
+use crate::domain::{self, commands, queries};
+use crate::infra::uuid::Uuid;
 use crate::presentation::rest::errors::error_into_response;
 use crate::presentation::rest::extensions::context::ExtractContext;
+use crate::presentation::rest::extensions::user::ExtractAuth;
 use crate::presentation::rest::view_models;
 
 impl From<ValidationError> for axum::response::Response {
@@ -44,15 +47,232 @@ pub async fn register(
     Ok(StatusCode::CREATED)
 }
 
+#[tracing::instrument(name = "POST /v1/users/verify-email", skip_all, fields(
+    ctx = ?ctx
+))]
+pub async fn verify_email(
+    Json(payload): Json<view_models::user::VerifyEmailInput>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<StatusCode, axum::response::Response> {
+    if let Err(error) = commands::user::verify_email(&deps, &ctx, payload.into()).await {
+        error!(?error, "unable to verify email");
+        return Err(error_into_response(error));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(name = "POST /v1/users/me/verification-email", skip_all, fields(
+    ctx = ?ctx
+))]
+pub async fn resend_verification_email(
+    ExtractAuth(auth): ExtractAuth,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<StatusCode, axum::response::Response> {
+    let input = commands::user::ResendVerificationEmailInput {
+        user_id: auth.user_id,
+    };
+
+    if let Err(error) = commands::user::resend_verification_email(&deps, &ctx, input).await {
+        error!(?error, "unable to resend verification email");
+        return Err(error_into_response(error));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(name = "GET /v1/users/:id", skip_all, fields(
+    user_id = %user_id,
+    ctx = ?ctx
+))]
+pub async fn get_user(
+    auth: Option<ExtractAuth>,
+    Path(user_id): Path<Uuid>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<view_models::user::UserProfileOutput>, axum::response::Response> {
+    let viewer_id = auth.map(|ExtractAuth(auth)| auth.user_id);
+
+    match queries::user::get_by_id::handle(&deps, &ctx, viewer_id, user_id).await {
+        Ok(user) => Ok(Json(user.into())),
+        Err(error) => {
+            error!(?error, "unable to get user");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[tracing::instrument(name = "PATCH /v1/users/me", skip_all, fields(
+    payload = ?payload,
+    ctx = ?ctx
+))]
+pub async fn update_profile(
+    ExtractAuth(auth): ExtractAuth,
+    Json(payload): Json<view_models::user::UpdateProfileInput>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<view_models::user::UserProfileOutput>, axum::response::Response> {
+    let input = commands::user::UpdateProfileInput {
+        user_id: auth.user_id,
+        changes: payload.try_into()?,
+    };
+
+    if let Err(error) = commands::user::update_profile(&deps, &ctx, input).await {
+        error!(?error, "unable to update profile");
+        return Err(error_into_response(error));
+    }
+
+    match queries::user::get_by_id::handle(&deps, &ctx, None, auth.user_id).await {
+        Ok(user) => Ok(Json(user.into())),
+        Err(error) => {
+            error!(?error, "unable to get user");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[tracing::instrument(name = "POST /v1/users/me/images", skip_all, fields(
+    payload = ?payload,
+    ctx = ?ctx
+))]
+pub async fn start_profile_image_upload(
+    ExtractAuth(auth): ExtractAuth,
+    Json(payload): Json<view_models::user::StartProfileImageUploadInput>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<view_models::user::StartProfileImageUploadOutput>, axum::response::Response> {
+    let fields = view_models::user::ProfileImageFields::try_from(payload)?;
+
+    let input = commands::user::StartProfileImageUploadInput {
+        user_id: auth.user_id,
+        kind: fields.kind,
+        content_type: fields.content_type,
+    };
+
+    match commands::user::start_profile_image_upload(&deps, &ctx, input).await {
+        Ok(output) => Ok(Json(output.into())),
+        Err(error) => {
+            error!(?error, "unable to start profile image upload");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[tracing::instrument(name = "POST /v1/users/me/images/:id/confirm", skip_all, fields(
+    upload_id = %upload_id,
+    ctx = ?ctx
+))]
+pub async fn confirm_profile_image_upload(
+    ExtractAuth(auth): ExtractAuth,
+    Path(upload_id): Path<Uuid>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<view_models::user::ConfirmProfileImageUploadOutput>, axum::response::Response> {
+    let input = commands::user::ConfirmProfileImageUploadInput {
+        user_id: auth.user_id,
+        upload_id,
+    };
+
+    match commands::user::confirm_profile_image_upload(&deps, &ctx, input).await {
+        Ok(output) => Ok(Json(output.into())),
+        Err(error) => {
+            error!(?error, "unable to confirm profile image upload");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[tracing::instrument(name = "DELETE /v1/users/me", skip_all, fields(
+    ctx = ?ctx
+))]
+pub async fn delete_account(
+    ExtractAuth(auth): ExtractAuth,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<StatusCode, axum::response::Response> {
+    let input = commands::user::DeleteAccountInput {
+        user_id: auth.user_id,
+    };
+
+    if let Err(error) = commands::user::delete_account(&deps, &ctx, input).await {
+        error!(?error, "unable to delete account");
+        return Err(error_into_response(error));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// The archive is built in the background, its status is checked with
+/// `GET /v1/users/me/export/:id`.
+#[tracing::instrument(name = "POST /v1/users/me/export", skip_all, fields(
+    ctx = ?ctx
+))]
+pub async fn request_personal_data_export(
+    ExtractAuth(auth): ExtractAuth,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<
+    (
+        StatusCode,
+        Json<view_models::user::RequestPersonalDataExportOutput>,
+    ),
+    axum::response::Response,
+> {
+    let input = commands::user::RequestPersonalDataExportInput {
+        user_id: auth.user_id,
+    };
+
+    match commands::user::request_personal_data_export(&deps, &ctx, input).await {
+        Ok(output) => Ok((StatusCode::ACCEPTED, Json(output.into()))),
+        Err(error) => {
+            error!(?error, "unable to request personal data export");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
+#[tracing::instrument(name = "GET /v1/users/me/export/:id", skip_all, fields(
+    ctx = ?ctx,
+    export_id = %export_id
+))]
+pub async fn get_personal_data_export(
+    ExtractAuth(auth): ExtractAuth,
+    Path(export_id): Path<Uuid>,
+    Extension(deps): Extension<Arc<Deps>>,
+    ExtractContext(ctx): ExtractContext,
+) -> Result<Json<view_models::user::PersonalDataExportOutput>, axum::response::Response> {
+    match queries::user::get_personal_data_export::handle(&deps, &ctx, auth.user_id, export_id)
+        .await
+    {
+        Ok(export) => Ok(Json(export.into())),
+        Err(error) => {
+            error!(?error, "unable to get personal data export");
+
+            Err(error_into_response(error))
+        }
+    }
+}
+
 impl TryFrom<view_models::register::RegisterInput> for domain::commands::user::CreateUserInput {
     type Error = ValidationError;
 
     fn try_from(input: view_models::register::RegisterInput) -> Result<Self, Self::Error> {
+        let username = Username::try_from(input.username)?;
+        let password = Password::new(&input.password, username.normalized(), &input.email)?;
+
         Ok(CreateUserInput {
-            username: input.username,
+            username,
             email: Email::try_from(input.email)?,
-            password: Password::try_from(input.password.as_str())?,
+            password,
             accepted_terms_at: Utc::now(),
+            accepted_terms_version: Some(input.accepted_terms_version),
         })
     }
 }
@@ -61,14 +281,23 @@ impl TryFrom<view_models::register::RegisterInput> for domain::commands::user::C
 mod tests {
     use super::*;
     use crate::{
-        domain::constants::X_REQUEST_ID_HEADER_NAME, presentation::rest::traits::RequestBuilderExt,
+        domain::constants::X_REQUEST_ID_HEADER_NAME,
+        presentation::rest::traits::{RequestBuilderExt, ResponseExt},
+    };
+    use axum::http::{header::RETRY_AFTER, Request};
+    use fake::{
+        faker::internet::en::{FreeEmail, Password},
+        Dummy, Fake, Faker,
     };
-    use axum::http::Request;
-    use fake::{faker::internet::en::FreeEmail, Dummy, Fake, Faker};
     use rand::Rng;
-    use tower::ServiceExt;
+    use reqwest::multipart::{self, Part};
+    use std::collections::HashMap;
+    use tower::{Service, ServiceExt};
 
-    use crate::presentation::rest::{deps, router};
+    use crate::domain::contracts::{context::Context, mailer::Mailer};
+    use crate::infra::factory;
+    use crate::infra::mailer::memory::InMemory;
+    use crate::presentation::rest::{deps, router, router_with_deps};
 
     impl Dummy<Faker> for view_models::register::RegisterInput {
         fn dummy_with_rng<R: Rng + ?Sized>(
@@ -76,9 +305,10 @@ mod tests {
             _rng: &mut R,
         ) -> view_models::register::RegisterInput {
             view_models::register::RegisterInput {
-                username: Faker.fake(),
+                username: factory::user::username(),
                 email: FreeEmail().fake(),
-                password: Faker.fake(),
+                password: Password(12..20).fake(),
+                accepted_terms_version: factory::terms::CURRENT_VERSION.to_owned(),
             }
         }
     }
@@ -105,4 +335,735 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn rejects_usernames_and_emails_that_are_taken() -> Result<(), Box<dyn std::error::Error>>
+    {
+        dotenv::dotenv().ok();
+
+        let mut app = router().await?;
+
+        let user: view_models::register::RegisterInput = Faker.fake();
+        let other: view_models::register::RegisterInput = Faker.fake();
+
+        let cases = [
+            (&user.username, &user.email, StatusCode::CREATED, None),
+            (
+                &user.username.to_uppercase(),
+                &other.email,
+                StatusCode::CONFLICT,
+                Some(("username", "the username is already taken")),
+            ),
+            (
+                &other.username,
+                &user.email,
+                StatusCode::CONFLICT,
+                Some(("email", "the email is already registered")),
+            ),
+        ];
+
+        for (username, email, status, expected_error) in cases {
+            let req = Request::builder()
+                .method("POST")
+                .uri("/v1/users")
+                .header("Content-Type", "application/json")
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .json(&view_models::register::RegisterInput {
+                    username: username.clone(),
+                    email: email.clone(),
+                    password: user.password.clone(),
+                    accepted_terms_version: user.accepted_terms_version.clone(),
+                })?;
+
+            let response = app.call(req).await?;
+
+            assert_eq!(response.status(), status);
+
+            if let Some((name, message)) = expected_error {
+                let error: view_models::ValidationError = response.json().await?;
+
+                assert_eq!(error.name, name);
+                assert_eq!(error.message, message);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_passwords_that_do_not_follow_the_policy(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let mut app = router().await?;
+
+        let user: view_models::register::RegisterInput = Faker.fake();
+
+        let cases: [(&str, &str); 3] = [
+            ("short1!", "the password must have at least 8 characters"),
+            ("password123", "the password is too common"),
+            (
+                &format!("{}-2023", user.username),
+                "the password must not contain the username",
+            ),
+        ];
+
+        for (password, message) in cases {
+            let req = Request::builder()
+                .method("POST")
+                .uri("/v1/users")
+                .header("Content-Type", "application/json")
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .json(&view_models::register::RegisterInput {
+                    password: password.to_owned(),
+                    username: user.username.clone(),
+                    email: user.email.clone(),
+                    accepted_terms_version: user.accepted_terms_version.clone(),
+                })?;
+
+            let response = app.call(req).await?;
+
+            assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+            let error: view_models::ValidationError = response.json().await?;
+
+            assert_eq!(error.name, "password");
+            assert_eq!(error.message, message);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_usernames_that_do_not_follow_the_rules(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let mut app = router().await?;
+
+        let user: view_models::register::RegisterInput = Faker.fake();
+
+        let cases: [(&str, &str); 4] = [
+            ("ab", "the username must have between 3 and 30 characters"),
+            ("jane doe", "the username can only contain letters, numbers, dots and underscores, and must start with a letter or a number"),
+            ("Admin ", "the username is reserved"),
+            ("ＡＤＭＩＮ", "the username is reserved"),
+        ];
+
+        for (username, message) in cases {
+            let req = Request::builder()
+                .method("POST")
+                .uri("/v1/users")
+                .header("Content-Type", "application/json")
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .json(&view_models::register::RegisterInput {
+                    username: username.to_owned(),
+                    email: user.email.clone(),
+                    password: user.password.clone(),
+                    accepted_terms_version: user.accepted_terms_version.clone(),
+                })?;
+
+            let response = app.call(req).await?;
+
+            assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+            let error: view_models::ValidationError = response.json().await?;
+
+            assert_eq!(error.name, "username");
+            assert_eq!(error.message, message);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn verify_email() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let mailer = Arc::new(InMemory::default());
+
+        let deps = Arc::new(Deps {
+            mailer: Arc::clone(&mailer) as Arc<dyn Mailer>,
+            ..deps().await?
+        });
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let user: view_models::register::RegisterInput = Faker.fake();
+
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/users")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .json(&user)?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let mails = mailer.sent_to(&user.email);
+
+        assert_eq!(mails.len(), 1);
+
+        // The token is the last line of the email.
+        let token = mails[0].body.trim_end().lines().last().unwrap().to_owned();
+
+        let verify = |token: String| {
+            Request::builder()
+                .method("POST")
+                .uri("/v1/users/verify-email")
+                .header("Content-Type", "application/json")
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .json(&view_models::user::VerifyEmailInput { token })
+        };
+
+        let response = app.call(verify(token.clone())?).await?;
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        // Tokens can be used only once.
+        let response = app.call(verify(token)?).await?;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn resending_the_verification_email_invalidates_the_previous_token(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let mailer = Arc::new(InMemory::default());
+
+        let deps = Arc::new(Deps {
+            mailer: Arc::clone(&mailer) as Arc<dyn Mailer>,
+            ..deps().await?
+        });
+
+        let mut app = router_with_deps(Arc::clone(&deps));
+
+        let user: view_models::register::RegisterInput = Faker.fake();
+
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/users")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .json(&user)?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let user_id = deps
+            .repos
+            .users
+            .get_credentials_by_email(&mut deps.db.read().await?, &user.email)
+            .await?
+            .unwrap()
+            .id;
+
+        let resend = || {
+            Request::builder()
+                .method("POST")
+                .uri("/v1/users/me/verification-email")
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .with_user_auth(user_id)
+                .body(hyper::Body::empty())
+        };
+
+        let response = app.call(resend()?).await?;
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let mails = mailer.sent_to(&user.email);
+
+        assert_eq!(mails.len(), 2);
+
+        // The token is the last line of the email.
+        let tokens: Vec<String> = mails
+            .iter()
+            .map(|mail| mail.body.trim_end().lines().last().unwrap().to_owned())
+            .collect();
+
+        assert_ne!(tokens[0], tokens[1]);
+
+        let verify = |token: &str| {
+            Request::builder()
+                .method("POST")
+                .uri("/v1/users/verify-email")
+                .header("Content-Type", "application/json")
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .json(&view_models::user::VerifyEmailInput {
+                    token: token.to_owned(),
+                })
+        };
+
+        // The first token stopped working when the second one was sent.
+        let response = app.call(verify(&tokens[0])?).await?;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = app.call(verify(&tokens[1])?).await?;
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        // There's nothing to resend once the email is verified.
+        let response = app.call(resend()?).await?;
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_user_returns_the_public_profile() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = deps().await?;
+
+        let user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        let mut app = router().await?;
+
+        let get = |user_id: Uuid| {
+            Request::builder()
+                .method("GET")
+                .uri(format!("/v1/users/{user_id}"))
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .body(hyper::Body::empty())
+        };
+
+        let response = app.call(get(user_id)?).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let profile: serde_json::Value = response.json().await?;
+
+        assert_eq!(profile["id"], user_id.to_string());
+        assert!(profile.get("email").is_none());
+        assert!(profile.get("password").is_none());
+
+        let response = app.call(get(Uuid::new_v4())?).await?;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        factory::user::soft_delete(&mut deps.db.write().await?, user_id).await?;
+
+        let response = app.call(get(user_id)?).await?;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_profile() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = deps().await?;
+
+        let user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        let mut app = router().await?;
+
+        let patch = |input: &view_models::user::UpdateProfileInput| {
+            Request::builder()
+                .method("PATCH")
+                .uri("/v1/users/me")
+                .header("Content-Type", "application/json")
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .with_user_auth(user_id)
+                .json(input)
+        };
+
+        let response = app
+            .call(patch(&view_models::user::UpdateProfileInput {
+                display_name: Some("Jane Doe".to_owned()),
+                bio: Some("Running every morning".to_owned()),
+                links: Some(vec!["https://example.com/jane".to_owned()]),
+            })?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let profile: view_models::user::UserProfileOutput = response.json().await?;
+
+        assert_eq!(profile.display_name.as_deref(), Some("Jane Doe"));
+        assert_eq!(profile.bio.as_deref(), Some("Running every morning"));
+        assert_eq!(profile.links, vec!["https://example.com/jane"]);
+
+        // Fields that are not sent are left unchanged.
+        let response = app
+            .call(patch(&view_models::user::UpdateProfileInput {
+                bio: Some(String::new()),
+                ..Default::default()
+            })?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let profile: view_models::user::UserProfileOutput = response.json().await?;
+
+        assert_eq!(profile.display_name.as_deref(), Some("Jane Doe"));
+        assert_eq!(profile.bio, None);
+
+        let response = app
+            .call(patch(&view_models::user::UpdateProfileInput {
+                links: Some(vec!["javascript:alert(1)".to_owned()]),
+                ..Default::default()
+            })?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let error: view_models::ValidationError = response.json().await?;
+
+        assert_eq!(error.name, "links");
+
+        Ok(())
+    }
+
+    /// Starts an upload for the user and sends `file` to the presigned url.
+    /// Returns the id of the upload.
+    async fn upload_profile_image(
+        app: &mut axum::Router,
+        user_id: Uuid,
+        kind: &str,
+        file: &'static [u8],
+    ) -> Result<Uuid, Box<dyn std::error::Error>> {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/users/me/images")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .json(&view_models::user::StartProfileImageUploadInput {
+                kind: kind.to_owned(),
+                content_type: "image/png".to_owned(),
+            })?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let upload: view_models::user::StartProfileImageUploadOutput = response.json().await?;
+
+        let mut form = multipart::Form::new();
+        for field in upload.presigned_url.form_data_fields.iter() {
+            form = form.text(field.name.clone(), field.value.clone());
+        }
+
+        form = form.part(
+            "file",
+            Part::bytes(file)
+                .file_name("image.png")
+                .mime_str("image/png")?,
+        );
+
+        let response = reqwest::Client::new()
+            .post(upload.presigned_url.endpoint)
+            .multipart(form)
+            .send()
+            .await?;
+
+        assert!(response.status().is_success());
+
+        Ok(upload.upload_id)
+    }
+
+    fn confirm_profile_image_upload_request(
+        user_id: Uuid,
+        upload_id: Uuid,
+    ) -> Result<Request<hyper::Body>, axum::http::Error> {
+        Request::builder()
+            .method("POST")
+            .uri(format!("/v1/users/me/images/{upload_id}/confirm"))
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .body(hyper::Body::empty())
+    }
+
+    #[tokio::test]
+    async fn confirmed_avatars_are_shown_in_the_profile() -> Result<(), Box<dyn std::error::Error>>
+    {
+        dotenv::dotenv().ok();
+
+        let deps = deps().await?;
+
+        let user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        let mut app = router().await?;
+
+        let upload_id = upload_profile_image(
+            &mut app,
+            user_id,
+            "avatar",
+            include_bytes!("./testdata/image1.png"),
+        )
+        .await?;
+
+        // Only the user that started the upload can confirm it.
+        let other_user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        let response = app
+            .call(confirm_profile_image_upload_request(
+                other_user_id,
+                upload_id,
+            )?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = app
+            .call(confirm_profile_image_upload_request(user_id, upload_id)?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let confirmed: view_models::user::ConfirmProfileImageUploadOutput = response.json().await?;
+
+        assert_eq!(confirmed.kind, "avatar");
+
+        let req = Request::builder()
+            .method("GET")
+            .uri(format!("/v1/users/{user_id}"))
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .body(hyper::Body::empty())?;
+
+        let profile: view_models::user::UserProfileOutput = app.call(req).await?.json().await?;
+
+        assert_eq!(profile.avatar_url, Some(confirmed.url));
+        assert_eq!(profile.banner_url, None);
+
+        // Uploads can be confirmed only once.
+        let response = app
+            .call(confirm_profile_image_upload_request(user_id, upload_id)?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_uploads_that_are_not_images() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = deps().await?;
+
+        let user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        let mut app = router().await?;
+
+        let upload_id = upload_profile_image(
+            &mut app,
+            user_id,
+            "banner",
+            b"<svg xmlns=\"http://www.w3.org/2000/svg\"><desc>not an image</desc><script>alert(document.cookie)</script></svg>\n",
+        )
+        .await?;
+
+        let response = app
+            .call(confirm_profile_image_upload_request(user_id, upload_id)?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/users/me/images")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .json(&view_models::user::StartProfileImageUploadInput {
+                kind: "avatar".to_owned(),
+                content_type: "image/svg+xml".to_owned(),
+            })?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let error: view_models::ValidationError = response.json().await?;
+
+        assert_eq!(error.name, "content_type");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn deleted_accounts_cannot_be_used() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = deps().await?;
+
+        let user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        let mut app = router().await?;
+
+        let req = Request::builder()
+            .method("DELETE")
+            .uri("/v1/users/me")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .body(hyper::Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let req = Request::builder()
+            .method("GET")
+            .uri(format!("/v1/users/{user_id}"))
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .body(hyper::Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // Access tokens issued before the deletion stop being accepted.
+        let req = Request::builder()
+            .method("PATCH")
+            .uri("/v1/users/me")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .json(view_models::user::UpdateProfileInput::default())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn exports_personal_data_with_media() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = deps().await?;
+
+        let user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        let mut app = router().await?;
+
+        let image = include_bytes!("./testdata/image1.png");
+
+        let upload_id = upload_profile_image(&mut app, user_id, "avatar", image).await?;
+
+        let response = app
+            .call(confirm_profile_image_upload_request(user_id, upload_id)?)
+            .await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/users/me/export")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .body(hyper::Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let export: view_models::user::RequestPersonalDataExportOutput = response.json().await?;
+
+        assert_eq!(export.status, "pending");
+
+        let export_request = || {
+            Request::builder()
+                .method("GET")
+                .uri(format!("/v1/users/me/export/{}", export.id))
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .with_user_auth(user_id)
+                .body(hyper::Body::empty())
+        };
+
+        let response = app.call(export_request()?).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let output: view_models::user::PersonalDataExportOutput = response.json().await?;
+
+        assert_eq!(output.status, "pending");
+        assert!(output.download_url.is_none());
+        assert!(output.completed_at.is_none());
+
+        let ctx = Context::from(HashMap::from([(
+            "job".to_owned(),
+            "personal_data_export".to_owned(),
+        )]));
+
+        // Exports requested by other tests may be waiting before this one.
+        while commands::user::run_pending_exports(&deps, &ctx).await? > 0 {}
+
+        let response = app.call(export_request()?).await?;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let output: view_models::user::PersonalDataExportOutput = response.json().await?;
+
+        assert_eq!(output.status, "ready");
+        assert!(output.completed_at.is_some());
+
+        let response = reqwest::get(output.download_url.unwrap()).await?;
+
+        assert!(response.status().is_success());
+
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(response.bytes().await?))?;
+
+        let personal_data: serde_json::Value =
+            serde_json::from_reader(archive.by_name("personal_data.json")?)?;
+
+        assert_eq!(personal_data["profile"]["id"], user_id.to_string());
+        assert!(personal_data["profile"]["email"].is_string());
+        assert!(personal_data["profile"].get("password").is_none());
+
+        let mut avatar = vec![];
+        std::io::Read::read_to_end(
+            &mut archive.by_name(&format!("media/profile-images/{upload_id}"))?,
+            &mut avatar,
+        )?;
+
+        assert_eq!(avatar, image.to_vec());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn personal_data_exports_are_rate_limited() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = deps().await?;
+
+        let user_id = factory::user::create(&mut deps.db.write().await?).await?;
+
+        let mut app = router().await?;
+
+        let export_request = || {
+            Request::builder()
+                .method("POST")
+                .uri("/v1/users/me/export")
+                .header(X_REQUEST_ID_HEADER_NAME, 1)
+                .with_user_auth(user_id)
+                .body(hyper::Body::empty())
+        };
+
+        let response = app.call(export_request()?).await?;
+
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let response = app.call(export_request()?).await?;
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(response.headers().contains_key(RETRY_AFTER));
+
+        Ok(())
+    }
 }
@@ -1,7 +1,7 @@
 use crate::domain::{commands, contracts::deps::Deps};
 use crate::presentation::rest::errors::error_into_response;
 use crate::presentation::rest::extensions::context::ExtractContext;
-use crate::presentation::rest::extensions::user::ExtractAuth;
+use crate::presentation::rest::extensions::user::ExtractAuthOrApiKey;
 use crate::presentation::rest::view_models;
 use axum::{Extension, Json};
 use std::sync::Arc;
@@ -11,12 +11,17 @@ use tracing::error;
     ctx = ?ctx
 ))]
 pub async fn start_video_upload(
-    ExtractAuth(auth): ExtractAuth,
+    ExtractAuthOrApiKey(auth): ExtractAuthOrApiKey,
     Extension(deps): Extension<Arc<Deps>>,
     ExtractContext(ctx): ExtractContext,
 ) -> Result<Json<view_models::video::StartVideoUploadOutput>, axum::response::Response> {
     let input = commands::video::StartVideoUploadInput {
         user_id: auth.user_id,
+        role: auth.role,
+        email_verified: auth.email_verified,
+        mfa_enabled: auth.mfa_enabled,
+        mfa_authenticated: auth.mfa_authenticated,
+        api_key_scopes: auth.api_key_scopes,
     };
 
     match commands::video::start_video_upload(&deps, &ctx, input).await {
@@ -49,6 +54,7 @@ mod start_video_upload_tests {
     use reqwest::multipart::{self, Part};
 
     use super::*;
+    use crate::domain::value_objects::role::Role;
 
     #[tokio::test]
     async fn can_get_presigned_post_url_and_upload() -> Result<(), Box<dyn std::error::Error>> {
@@ -58,7 +64,7 @@ mod start_video_upload_tests {
 
         let mut executor = deps.db.write().await?;
 
-        let user_id = factory::user::create(&mut executor).await?;
+        let user_id = factory::user::create_with_role(&mut executor, Role::Creator).await?;
 
         let mut app = router().await?;
 
@@ -83,6 +89,10 @@ mod start_video_upload_tests {
             .presigned_url
             .endpoint
             .is_empty());
+        assert!(!start_video_video_upload_response_body
+            .thumbnail_presigned_url
+            .endpoint
+            .is_empty());
 
         let file = include_bytes!("./testdata/image1.png");
         assert!(!file.is_empty());
@@ -133,4 +143,207 @@ mod start_video_upload_tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn viewers_cannot_upload_videos() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let mut executor = deps.db.write().await?;
+
+        let user_id = factory::user::create_with_role(&mut executor, Role::Viewer).await?;
+
+        let mut app = router().await?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/videos")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .extension(Arc::clone(&deps))
+            .body(Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), hyper::StatusCode::FORBIDDEN);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn creators_without_mfa_can_upload_videos() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let user_id =
+            factory::user::create_with_role(&mut deps.db.write().await?, Role::Creator).await?;
+
+        let mut app = router().await?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/videos")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .extension(Arc::clone(&deps))
+            .body(Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), hyper::StatusCode::OK);
+
+        let body: view_models::video::StartVideoUploadOutput = response.json().await?;
+
+        assert!(!body.presigned_url.endpoint.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn creators_with_mfa_enabled_must_log_in_with_mfa_to_upload_videos(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let mut executor = deps.db.write().await?;
+
+        let user_id = factory::user::create_with_role(&mut executor, Role::Creator).await?;
+
+        factory::user::enable_mfa(&mut executor, user_id).await?;
+
+        let mut app = router().await?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/videos")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_user_auth(user_id)
+            .extension(Arc::clone(&deps))
+            .body(Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), hyper::StatusCode::FORBIDDEN);
+
+        let body: serde_json::Value = response.json().await?;
+
+        assert_eq!(
+            body["message"],
+            "two-factor authentication is required to upload videos"
+        );
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/videos")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .with_mfa_user_auth(user_id)
+            .extension(Arc::clone(&deps))
+            .body(Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert!(response.status().is_success());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_expired_access_token() -> Result<(), Box<dyn std::error::Error>> {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let mut app = router().await?;
+
+        let issued_at = chrono::Utc::now().timestamp() - 120;
+
+        let token = crate::infra::jwt::sign(
+            &deps.config.jwt,
+            &crate::infra::jwt::Claims {
+                sub: crate::infra::uuid::Uuid::new_v4(),
+                sid: crate::infra::uuid::Uuid::new_v4(),
+                iat: issued_at,
+                exp: issued_at + 60,
+                mfa: false,
+            },
+        )?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/videos")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .header(
+                crate::domain::constants::AUTHORIZATION_HEADER_NAME,
+                format!("Bearer {token}"),
+            )
+            .extension(Arc::clone(&deps))
+            .body(Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), hyper::StatusCode::UNAUTHORIZED);
+
+        let body: serde_json::Value = response.json().await?;
+
+        assert_eq!(body["message"], "jwt token has expired");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_access_token_signed_with_unknown_key() -> Result<(), Box<dyn std::error::Error>>
+    {
+        dotenv::dotenv().ok();
+
+        let deps = Arc::new(deps().await?);
+
+        let mut app = router().await?;
+
+        let unknown_key = crate::config::JwtConfig {
+            signing_keys: vec![crate::config::SigningKey {
+                kid: "unknown".to_owned(),
+                secret: "unknown-secret".to_owned(),
+            }],
+            active_key_id: "unknown".to_owned(),
+        };
+
+        let now = chrono::Utc::now().timestamp();
+
+        let token = crate::infra::jwt::sign(
+            &unknown_key,
+            &crate::infra::jwt::Claims {
+                sub: crate::infra::uuid::Uuid::new_v4(),
+                sid: crate::infra::uuid::Uuid::new_v4(),
+                iat: now,
+                exp: now + 60,
+                mfa: false,
+            },
+        )?;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/v1/videos")
+            .header("Content-Type", "application/json")
+            .header(X_REQUEST_ID_HEADER_NAME, 1)
+            .header(
+                crate::domain::constants::AUTHORIZATION_HEADER_NAME,
+                format!("Bearer {token}"),
+            )
+            .extension(Arc::clone(&deps))
+            .body(Body::empty())?;
+
+        let response = app.call(req).await?;
+
+        assert_eq!(response.status(), hyper::StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }
 }
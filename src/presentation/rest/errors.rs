@@ -1,14 +1,311 @@
 //! Contains functions to make it easier to deal with
 //! errors that may be returned to the user.
 
-use axum::response::IntoResponse;
+use axum::{http::header::RETRY_AFTER, response::IntoResponse, Json};
 use hyper::StatusCode;
+use serde_json::json;
 
+use crate::domain::{
+    commands, contracts::identity_provider::IdentityProviderError, errors::ValidationError,
+    queries, value_objects::password::PasswordError,
+};
+use crate::presentation::rest::view_models;
+
+/// Decides which http status code to use based on the real error.
+/// Errors that the client cannot do anything about become a 500
+/// without details.
 #[tracing::instrument(name = "rest::errors::error_into_response", skip_all, fields(
     error = ?error
 ))]
 pub fn error_into_response(error: anyhow::Error) -> axum::response::Response {
-    // TODO: downcast anyhow::Error and decide which http status code to use
-    // based on the real error.
+    if let Some(error) = error.downcast_ref::<commands::session::LoginLockedError>() {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(RETRY_AFTER, error.retry_after_secs.to_string())],
+            Json(json!({ "message": error.to_string() })),
+        )
+            .into_response();
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::user::PersonalDataExportRateLimitedError>()
+    {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(RETRY_AFTER, error.retry_after_secs.to_string())],
+            Json(json!({ "message": error.to_string() })),
+        )
+            .into_response();
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::session::CreateSessionError>() {
+        return match error {
+            commands::session::CreateSessionError::InvalidCredentials => {
+                message(StatusCode::UNAUTHORIZED, error)
+            }
+            commands::session::CreateSessionError::UserBanned => {
+                message(StatusCode::FORBIDDEN, error)
+            }
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::session::RefreshSessionError>() {
+        return match error {
+            commands::session::RefreshSessionError::InvalidRefreshToken
+            | commands::session::RefreshSessionError::RefreshTokenReused => {
+                message(StatusCode::UNAUTHORIZED, error)
+            }
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::video::UploadVideoError>() {
+        return match error {
+            commands::video::UploadVideoError::UserNotAllowedToUploadVideos
+            | commands::video::UploadVideoError::EmailNotVerified
+            | commands::video::UploadVideoError::MfaRequired
+            | commands::video::UploadVideoError::ApiKeyMissingScope => {
+                message(StatusCode::FORBIDDEN, error)
+            }
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::post::CreatePostError>() {
+        return match error {
+            commands::post::CreatePostError::UserNotAllowedToCreatePosts => {
+                message(StatusCode::FORBIDDEN, error)
+            }
+            commands::post::CreatePostError::UploadNotFound => {
+                message(StatusCode::NOT_FOUND, error)
+            }
+            commands::post::CreatePostError::VideoNotUploaded
+            | commands::post::CreatePostError::ThumbnailNotUploaded => {
+                message(StatusCode::CONFLICT, error)
+            }
+            commands::post::CreatePostError::InvalidThumbnail => {
+                message(StatusCode::UNPROCESSABLE_ENTITY, error)
+            }
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::pix_payment::StartPixPaymentError>() {
+        return match error {
+            commands::pix_payment::StartPixPaymentError::EmailNotVerified
+            | commands::pix_payment::StartPixPaymentError::Blocked => {
+                message(StatusCode::FORBIDDEN, error)
+            }
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::user::VerifyEmailError>() {
+        return match error {
+            commands::user::VerifyEmailError::InvalidToken => {
+                message(StatusCode::BAD_REQUEST, error)
+            }
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::user::ResendVerificationEmailError>() {
+        return match error {
+            commands::user::ResendVerificationEmailError::AlreadyVerified => {
+                message(StatusCode::CONFLICT, error)
+            }
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::password_reset::ConfirmPasswordResetError>()
+    {
+        return match error {
+            commands::password_reset::ConfirmPasswordResetError::InvalidToken => {
+                message(StatusCode::BAD_REQUEST, error)
+            }
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::session::VerifyMfaError>() {
+        return match error {
+            commands::session::VerifyMfaError::InvalidMfaToken
+            | commands::session::VerifyMfaError::InvalidCode => {
+                message(StatusCode::UNAUTHORIZED, error)
+            }
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::mfa::EnrollTotpError>() {
+        return match error {
+            commands::mfa::EnrollTotpError::UserNotAllowedToEnableMfa => {
+                message(StatusCode::FORBIDDEN, error)
+            }
+            commands::mfa::EnrollTotpError::AlreadyEnabled => message(StatusCode::CONFLICT, error),
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::mfa::ConfirmTotpError>() {
+        return match error {
+            commands::mfa::ConfirmTotpError::NotEnrolled
+            | commands::mfa::ConfirmTotpError::AlreadyEnabled => {
+                message(StatusCode::CONFLICT, error)
+            }
+            commands::mfa::ConfirmTotpError::InvalidCode => message(StatusCode::BAD_REQUEST, error),
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::api_key::CreateApiKeyError>() {
+        return match error {
+            commands::api_key::CreateApiKeyError::UserNotAllowedToCreateApiKeys
+            | commands::api_key::CreateApiKeyError::MfaRequired => {
+                message(StatusCode::FORBIDDEN, error)
+            }
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::api_key::RevokeApiKeyError>() {
+        return match error {
+            commands::api_key::RevokeApiKeyError::NotFound => message(StatusCode::NOT_FOUND, error),
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::session::OidcLoginError>() {
+        return match error {
+            commands::session::OidcLoginError::NotConfigured => {
+                message(StatusCode::NOT_FOUND, error)
+            }
+            commands::session::OidcLoginError::InvalidState
+            | commands::session::OidcLoginError::NonceMismatch => {
+                message(StatusCode::UNAUTHORIZED, error)
+            }
+            commands::session::OidcLoginError::EmailNotVerified => {
+                message(StatusCode::FORBIDDEN, error)
+            }
+            commands::session::OidcLoginError::EmailAlreadyInUse
+            | commands::session::OidcLoginError::IdentityLinkedToAnotherUser => {
+                message(StatusCode::CONFLICT, error)
+            }
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::user::ProfileImageUploadError>() {
+        return match error {
+            commands::user::ProfileImageUploadError::NotFound => {
+                message(StatusCode::NOT_FOUND, error)
+            }
+            commands::user::ProfileImageUploadError::NotUploaded => {
+                message(StatusCode::CONFLICT, error)
+            }
+            commands::user::ProfileImageUploadError::InvalidImage => {
+                message(StatusCode::UNPROCESSABLE_ENTITY, error)
+            }
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::creator_tag::SetCreatorTagsError>() {
+        return match error {
+            commands::creator_tag::SetCreatorTagsError::UserNotAllowedToHaveTags => {
+                message(StatusCode::FORBIDDEN, error)
+            }
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::follow::FollowError>() {
+        return match error {
+            commands::follow::FollowError::NotFound => message(StatusCode::NOT_FOUND, error),
+            commands::follow::FollowError::NotACreator
+            | commands::follow::FollowError::CannotFollowYourself => {
+                message(StatusCode::UNPROCESSABLE_ENTITY, error)
+            }
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::relationship::RelationshipError>() {
+        return match error {
+            commands::relationship::RelationshipError::NotFound => {
+                message(StatusCode::NOT_FOUND, error)
+            }
+            commands::relationship::RelationshipError::CannotTargetYourself => {
+                message(StatusCode::UNPROCESSABLE_ENTITY, error)
+            }
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::moderation::BanUserError>() {
+        return match error {
+            commands::moderation::BanUserError::NotAllowed
+            | commands::moderation::BanUserError::CannotBanAdmins => {
+                message(StatusCode::FORBIDDEN, error)
+            }
+            commands::moderation::BanUserError::NotFound => message(StatusCode::NOT_FOUND, error),
+            commands::moderation::BanUserError::AlreadyBanned
+            | commands::moderation::BanUserError::NotBanned => message(StatusCode::CONFLICT, error),
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<IdentityProviderError>() {
+        return match error {
+            IdentityProviderError::CodeRejected | IdentityProviderError::InvalidIdToken(_) => {
+                message(StatusCode::UNAUTHORIZED, error)
+            }
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<queries::user::get_by_id::GetUserError>() {
+        return match error {
+            queries::user::get_by_id::GetUserError::NotFound => {
+                message(StatusCode::NOT_FOUND, error)
+            }
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<queries::post::get_media::GetPostMediaError>() {
+        return match error {
+            queries::post::get_media::GetPostMediaError::NotFound => {
+                message(StatusCode::NOT_FOUND, error)
+            }
+            queries::post::get_media::GetPostMediaError::Locked => {
+                message(StatusCode::FORBIDDEN, error)
+            }
+        };
+    }
+
+    if let Some(error) =
+        error.downcast_ref::<queries::user::get_personal_data_export::GetPersonalDataExportError>()
+    {
+        return match error {
+            queries::user::get_personal_data_export::GetPersonalDataExportError::NotFound => {
+                message(StatusCode::NOT_FOUND, error)
+            }
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::user::CreateUserError>() {
+        let error = ValidationError::from(error.clone());
+
+        return (
+            StatusCode::CONFLICT,
+            Json(view_models::ValidationError {
+                name: error.name,
+                message: error.message,
+            }),
+        )
+            .into_response();
+    }
+
+    if let Some(error) = error.downcast_ref::<commands::terms::AcceptTermsError>() {
+        return ValidationError::from(error.clone()).into();
+    }
+
+    if let Some(error) = error.downcast_ref::<queries::terms::get_current::GetCurrentTermsError>() {
+        return match error {
+            queries::terms::get_current::GetCurrentTermsError::NotFound => {
+                message(StatusCode::NOT_FOUND, error)
+            }
+        };
+    }
+
+    if let Some(error) = error.downcast_ref::<PasswordError>() {
+        return ValidationError::from(error.clone()).into();
+    }
+
     (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response()
 }
+
+fn message(status: StatusCode, error: &impl ToString) -> axum::response::Response {
+    (status, Json(json!({ "message": error.to_string() }))).into_response()
+}
@@ -0,0 +1,59 @@
+use std::{
+    convert::Infallible,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use axum::extract::{ConnectInfo, FromRequest, RequestParts};
+
+use crate::domain::{constants::X_FORWARDED_FOR_HEADER_NAME, contracts::deps::Deps};
+
+/// Address of the client that sent the request.
+/// None when the address is not known, requests built in tests for example.
+pub struct ExtractClientIp(pub Option<IpAddr>);
+
+#[async_trait]
+impl<B> FromRequest<B> for ExtractClientIp
+where
+    B: Send + 'static,
+{
+    type Rejection = Infallible;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let trusted_proxy_hops = req
+            .extensions()
+            .get::<Arc<Deps>>()
+            .map(|deps| deps.config.trusted_proxy_hops)
+            .unwrap_or(0);
+
+        if trusted_proxy_hops > 0 {
+            let forwarded_for = req
+                .headers()
+                .get(X_FORWARDED_FOR_HEADER_NAME)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| forwarded_client_ip(value, trusted_proxy_hops));
+
+            if let Some(ip) = forwarded_for {
+                return Ok(ExtractClientIp(Some(ip)));
+            }
+        }
+
+        let ip = req
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+
+        Ok(ExtractClientIp(ip))
+    }
+}
+
+/// Every proxy appends the address it received the request from, so the client
+/// address is the entry added by the outermost trusted proxy, `hops` entries from
+/// the right. Entries to the left of it are sent by the client.
+fn forwarded_client_ip(forwarded_for: &str, hops: usize) -> Option<IpAddr> {
+    forwarded_for
+        .rsplit(',')
+        .nth(hops - 1)
+        .and_then(|value| value.trim().parse::<IpAddr>().ok())
+}
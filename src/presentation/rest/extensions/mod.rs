@@ -1,2 +1,3 @@
+pub mod client_ip;
 pub mod context;
-pub mod user;
\ No newline at end of file
+pub mod user;
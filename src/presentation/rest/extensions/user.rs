@@ -1,20 +1,29 @@
-use std::{collections::HashMap, str::FromStr};
+use std::sync::Arc;
 
-use crate::domain::constants::AUTHORIZATION_HEADER_NAME;
-use crate::infra::uuid::Uuid;
+use crate::domain::constants::{AUTHORIZATION_HEADER_NAME, X_API_KEY_HEADER_NAME};
+use crate::domain::contracts::deps::Deps;
+use crate::domain::value_objects::{api_key_scope::ApiKeyScope, role::Role};
+use crate::domain::{commands, queries};
+use crate::infra::{jwt, uuid::Uuid};
 use async_trait::async_trait;
 use axum::{
     extract::{FromRequest, RequestParts},
     http::StatusCode,
 };
-use hmac::{Hmac, Mac};
-use jwt::VerifyWithKey;
 use serde_json::{json, Value};
-use sha2::Sha256;
+use tracing::error;
 
 #[derive(Debug)]
 pub struct Auth {
     pub user_id: Uuid,
+    /// Id of the session the access token belongs to.
+    pub session_id: Uuid,
+    pub role: Role,
+    pub email_verified: bool,
+    /// True when the user enabled two-factor authentication.
+    pub mfa_enabled: bool,
+    /// True when the user used a second factor to create the session.
+    pub mfa_authenticated: bool,
 }
 
 pub struct ExtractAuth(pub Auth);
@@ -27,22 +36,181 @@ where
     type Rejection = (StatusCode, axum::Json<Value>);
 
     async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
-        let token = match req.headers().get(AUTHORIZATION_HEADER_NAME) {
-            None => {
+        let (auth, pending_terms_version) = authenticate(req).await?;
+
+        if let Some(version) = pending_terms_version {
+            return Err(pending_terms_rejection(version));
+        }
+
+        Ok(ExtractAuth(auth))
+    }
+}
+
+/// Same as `ExtractAuth` but lets users that have not accepted
+/// the current terms through, so they can accept them.
+pub struct ExtractAuthPendingTerms(pub Auth);
+
+#[async_trait]
+impl<B> FromRequest<B> for ExtractAuthPendingTerms
+where
+    B: Send + 'static,
+{
+    type Rejection = (StatusCode, axum::Json<Value>);
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let (auth, _) = authenticate(req).await?;
+
+        Ok(ExtractAuthPendingTerms(auth))
+    }
+}
+
+/// Returns the user making the request and the version of the terms
+/// they have to accept, if any.
+async fn authenticate<B>(
+    req: &mut RequestParts<B>,
+) -> Result<(Auth, Option<String>), (StatusCode, axum::Json<Value>)>
+where
+    B: Send,
+{
+    let token = match req.headers().get(AUTHORIZATION_HEADER_NAME) {
+        None => {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                axum::Json(json!({
+                    "message":
+                        format!("missing authorization header: {AUTHORIZATION_HEADER_NAME}")
+                })),
+            ))
+        }
+        Some(v) => match v.to_str() {
+            Err(_err) => {
                 return Err((
                     StatusCode::UNAUTHORIZED,
                     axum::Json(json!({
-                        "message":
-                            format!("missing authorization header: {AUTHORIZATION_HEADER_NAME}")
+                        "message": format!("header Authorization value is not valid")
                     })),
                 ))
             }
+            Ok(v) => v.strip_prefix("Bearer ").unwrap_or(v).to_string(),
+        },
+    };
+
+    let deps = match req.extensions().get::<Arc<Deps>>() {
+        None => {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                axum::Json(json!({ "message": "Internal server error" })),
+            ))
+        }
+        Some(deps) => Arc::clone(deps),
+    };
+
+    let claims: jwt::Claims = match jwt::verify(&deps.config.jwt, &token) {
+        Err(err) => {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                axum::Json(json!({ "message": err.to_string() })),
+            ))
+        }
+        Ok(v) => v,
+    };
+
+    match queries::session::is_revoked::handle(&deps, claims.sid).await {
+        Err(error) => {
+            error!(?error, "unable to check if session is revoked");
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                axum::Json(json!({ "message": "Internal server error" })),
+            ));
+        }
+        Ok(true) => {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                axum::Json(json!({ "message": "session has been revoked" })),
+            ))
+        }
+        Ok(false) => {}
+    }
+
+    let user = match queries::session::get_authenticated_user::handle(&deps, claims.sub).await {
+        Err(error) => {
+            error!(?error, "unable to get authenticated user");
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                axum::Json(json!({ "message": "Internal server error" })),
+            ));
+        }
+        Ok(None) => {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                axum::Json(json!({ "message": "user does not exist" })),
+            ))
+        }
+        Ok(Some(user)) => user,
+    };
+
+    if user.banned {
+        return Err(banned_user_rejection());
+    }
+
+    Ok((
+        Auth {
+            user_id: user.id,
+            session_id: claims.sid,
+            role: user.role,
+            email_verified: user.email_verified,
+            mfa_enabled: user.mfa_enabled,
+            mfa_authenticated: claims.mfa,
+        },
+        user.pending_terms_version,
+    ))
+}
+
+/// Who made a request to an endpoint that accepts api keys.
+#[derive(Debug)]
+pub struct ApiAuth {
+    pub user_id: Uuid,
+    pub role: Role,
+    pub email_verified: bool,
+    pub mfa_enabled: bool,
+    /// Always true for api keys because they can only be created
+    /// by sessions that used a second factor.
+    pub mfa_authenticated: bool,
+    /// Scopes of the api key used in the request, None when an access token was used.
+    pub api_key_scopes: Option<Vec<ApiKeyScope>>,
+}
+
+/// Same as `ExtractAuth` but also accepts an api key in the X-Api-Key header,
+/// so the endpoint can be called from scripts.
+pub struct ExtractAuthOrApiKey(pub ApiAuth);
+
+#[async_trait]
+impl<B> FromRequest<B> for ExtractAuthOrApiKey
+where
+    B: Send + 'static,
+{
+    type Rejection = (StatusCode, axum::Json<Value>);
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let key = match req.headers().get(X_API_KEY_HEADER_NAME) {
+            None => {
+                let ExtractAuth(auth) = ExtractAuth::from_request(req).await?;
+
+                return Ok(ExtractAuthOrApiKey(ApiAuth {
+                    user_id: auth.user_id,
+                    role: auth.role,
+                    email_verified: auth.email_verified,
+                    mfa_enabled: auth.mfa_enabled,
+                    mfa_authenticated: auth.mfa_authenticated,
+                    api_key_scopes: None,
+                }));
+            }
             Some(v) => match v.to_str() {
                 Err(_err) => {
                     return Err((
                         StatusCode::UNAUTHORIZED,
                         axum::Json(json!({
-                            "message": format!("header Authorization value is not valid")
+                            "message": format!("header X-Api-Key value is not valid")
                         })),
                     ))
                 }
@@ -50,30 +218,90 @@ where
             },
         };
 
-        let key: Hmac<Sha256> = Hmac::new_from_slice(b"some-secret").unwrap();
-
-        let claims: HashMap<String, String> = match token.verify_with_key(&key) {
-            Err(_err) => {
+        let deps = match req.extensions().get::<Arc<Deps>>() {
+            None => {
                 return Err((
-                    StatusCode::UNAUTHORIZED,
-                    axum::Json(json!({ "message": format!("jwt token is invalid") })),
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    axum::Json(json!({ "message": "Internal server error" })),
                 ))
             }
-            Ok(v) => v,
+            Some(deps) => Arc::clone(deps),
         };
 
-        let user_id = match Uuid::from_str(&claims.get("user_id").unwrap()) {
-            Err(_err) => {
+        let owner = match queries::api_key::authenticate::handle(&deps, &key).await {
+            Err(error) => {
+                error!(?error, "unable to authenticate api key");
+                return Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    axum::Json(json!({ "message": "Internal server error" })),
+                ));
+            }
+            Ok(None) => {
                 return Err((
                     StatusCode::UNAUTHORIZED,
-                    axum::Json(json!({
-                        "message": format!("header Authorization value is not a valid uuid")
-                    })),
+                    axum::Json(json!({ "message": "api key is invalid or has been revoked" })),
                 ))
             }
-            Ok(v) => v,
+            Ok(Some(owner)) => owner,
         };
 
-        Ok(ExtractAuth(Auth { user_id }))
+        let user =
+            match queries::session::get_authenticated_user::handle(&deps, owner.user_id).await {
+                Err(error) => {
+                    error!(?error, "unable to get authenticated user");
+                    return Err((
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        axum::Json(json!({ "message": "Internal server error" })),
+                    ));
+                }
+                Ok(None) => {
+                    return Err((
+                        StatusCode::UNAUTHORIZED,
+                        axum::Json(json!({ "message": "user does not exist" })),
+                    ))
+                }
+                Ok(Some(user)) => user,
+            };
+
+        if user.banned {
+            return Err(banned_user_rejection());
+        }
+
+        if let Some(version) = user.pending_terms_version {
+            return Err(pending_terms_rejection(version));
+        }
+
+        // Not being able to record the usage should not stop the request.
+        if let Err(error) = commands::api_key::record_usage(&deps, owner.api_key_id).await {
+            error!(?error, "unable to record api key usage");
+        }
+
+        Ok(ExtractAuthOrApiKey(ApiAuth {
+            user_id: user.id,
+            role: user.role,
+            email_verified: user.email_verified,
+            mfa_enabled: user.mfa_enabled,
+            mfa_authenticated: true,
+            api_key_scopes: Some(owner.scopes),
+        }))
     }
 }
+
+fn banned_user_rejection() -> (StatusCode, axum::Json<Value>) {
+    (
+        StatusCode::FORBIDDEN,
+        axum::Json(json!({ "message": "user is banned" })),
+    )
+}
+
+/// The client should show the terms and accept them
+/// through `POST /v1/users/me/terms` before retrying.
+fn pending_terms_rejection(version: String) -> (StatusCode, axum::Json<Value>) {
+    (
+        StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS,
+        axum::Json(json!({
+            "message": "the current terms must be accepted",
+            "terms_version": version
+        })),
+    )
+}
@@ -3,14 +3,25 @@ mod extensions;
 mod middlewares;
 pub mod view_models;
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use axum::{
     http::header::HeaderName,
-    routing::{get, post},
+    routing::{delete, get, patch, post, put},
     Extension, Router,
 };
+use controllers::admin;
+use controllers::api_key;
+use controllers::creator;
+use controllers::follow;
 use controllers::health_check;
+use controllers::mfa;
+use controllers::oidc;
+use controllers::password_reset;
 use controllers::pix_payment;
+use controllers::post;
+use controllers::relationship;
+use controllers::session;
+use controllers::terms;
 use controllers::timeline;
 use controllers::user;
 use controllers::video;
@@ -18,45 +29,164 @@ use std::sync::Arc;
 use tower::ServiceBuilder;
 use tower_http::request_id::PropagateRequestIdLayer;
 pub mod errors;
+#[cfg(test)]
 pub mod traits;
 
 use crate::{
     config::Config,
-    domain::{constants::X_REQUEST_ID_HEADER_NAME, contracts::deps::Deps},
-    infra::{self, http::Http},
+    domain::{
+        constants::X_REQUEST_ID_HEADER_NAME,
+        contracts::{deps::Deps, http::Http, identity_provider::IdentityProvider, mailer::Mailer},
+    },
+    infra::{self, oidc::OidcIdentityProvider},
 };
 
+/// Builds the router with the dependencies configured in the env.
+#[cfg(test)]
 pub async fn router() -> Result<Router> {
-    let router = Router::new()
+    use anyhow::Context;
+
+    let deps = deps().await.context("instantiating dependencies")?;
+
+    Ok(router_with_deps(Arc::new(deps)))
+}
+
+/// Builds the router using `deps` as the dependencies of every handler.
+/// Tests use it to replace dependencies with test doubles.
+pub fn router_with_deps(deps: Arc<Deps>) -> Router {
+    Router::new()
         .route("/v1/health-check", get(health_check::handle))
+        .route("/v1/terms/current", get(terms::get_current_terms))
         .route("/v1/users", post(user::register))
+        .route("/v1/users/verify-email", post(user::verify_email))
+        .route("/v1/users/:id", get(user::get_user))
+        .route(
+            "/v1/users/:id/follow",
+            post(follow::follow).delete(follow::unfollow),
+        )
+        .route("/v1/users/:id/posts", get(timeline::get_creator_feed))
+        .route("/v1/users/:id/followers", get(follow::list_followers))
+        .route("/v1/users/:id/following", get(follow::list_following))
+        .route(
+            "/v1/users/:id/block",
+            post(relationship::block).delete(relationship::unblock),
+        )
+        .route(
+            "/v1/users/:id/mute",
+            post(relationship::mute).delete(relationship::unmute),
+        )
+        .route(
+            "/v1/users/me",
+            patch(user::update_profile).delete(user::delete_account),
+        )
+        .route(
+            "/v1/users/me/export",
+            post(user::request_personal_data_export),
+        )
+        .route(
+            "/v1/users/me/export/:id",
+            get(user::get_personal_data_export),
+        )
+        .route(
+            "/v1/users/me/verification-email",
+            post(user::resend_verification_email),
+        )
+        .route("/v1/users/me/terms", post(terms::accept_terms))
+        .route("/v1/users/me/tags", put(creator::set_tags))
+        .route("/v1/users/me/blocks", get(relationship::list_blocked))
+        .route("/v1/users/me/mutes", get(relationship::list_muted))
+        .route(
+            "/v1/users/me/images",
+            post(user::start_profile_image_upload),
+        )
+        .route(
+            "/v1/users/me/images/:id/confirm",
+            post(user::confirm_profile_image_upload),
+        )
+        .route(
+            "/v1/users/me/api-keys",
+            post(api_key::create_api_key).get(api_key::list_api_keys),
+        )
+        .route("/v1/users/me/api-keys/:id", delete(api_key::revoke_api_key))
+        .route("/v1/users/me/mfa/totp", post(mfa::enroll_totp))
+        .route("/v1/users/me/mfa/totp/confirm", post(mfa::confirm_totp))
+        .route("/v1/users/me/identities", post(oidc::start_identity_link))
+        .route(
+            "/v1/sessions",
+            post(session::create_session).delete(session::delete_all_sessions),
+        )
+        .route("/v1/sessions/refresh", post(session::refresh_session))
+        .route("/v1/sessions/mfa", post(session::verify_mfa))
+        .route("/v1/sessions/oidc", post(oidc::start_oidc_login))
+        .route(
+            "/v1/sessions/oidc/callback",
+            post(oidc::complete_oidc_login),
+        )
+        .route(
+            "/v1/sessions/current",
+            delete(session::delete_current_session),
+        )
+        .route(
+            "/v1/password-resets",
+            post(password_reset::request_password_reset),
+        )
+        .route(
+            "/v1/password-resets/confirm",
+            post(password_reset::confirm_password_reset),
+        )
+        .route("/v1/posts", post(post::create_post))
+        .route("/v1/posts/:id/media", get(post::get_post_media))
         .route("/v1/timeline", get(timeline::get_timeline))
+        .route(
+            "/v1/timeline/following",
+            get(timeline::get_following_timeline),
+        )
+        .route("/v1/creators", get(creator::list_creators))
+        .route("/v1/creators/tags", get(creator::list_tags))
+        .route("/v1/admin/users/:id/ban", post(admin::ban_user))
+        .route("/v1/admin/users/:id/unban", post(admin::unban_user))
         .route("/v1/payments/pix", post(pix_payment::start_pix_payment))
         .route("/v1/videos", post(video::start_video_upload))
         .route_layer(ServiceBuilder::new().layer(PropagateRequestIdLayer::new(
             HeaderName::from_static(X_REQUEST_ID_HEADER_NAME),
         )))
-        .layer(Extension(Arc::new(
-            deps().await.context("instantiating dependencies"),
-        )));
-
-    Ok(router)
+        .layer(Extension(deps))
 }
 
-async fn deps() -> Result<Deps> {
+pub async fn deps() -> Result<Deps> {
     let config = Arc::new(Config::from_env()?);
 
     let db = infra::repository::Database::new(infra::repository::Config::from(config.as_ref()))?;
 
-    let http = Http::new();
+    let http: Arc<dyn Http> = Arc::new(infra::http::Http::new());
 
     let s3 = infra::object_storage::s3::S3::new(Arc::clone(&config)).await?;
 
+    let mailer: Arc<dyn Mailer> = match &config.mailer.smtp {
+        Some(smtp) => Arc::new(infra::mailer::smtp::Smtp::new(
+            config.mailer.from.clone(),
+            smtp,
+        )?),
+        None => Arc::new(
+            infra::mailer::file_drop::FileDrop::new(
+                config.mailer.from.clone(),
+                config.mailer.drop_dir.clone(),
+            )
+            .await?,
+        ),
+    };
+
+    let identity_provider = config.oidc.clone().map(|oidc| {
+        Arc::new(OidcIdentityProvider::new(Arc::clone(&http), oidc)) as Arc<dyn IdentityProvider>
+    });
+
     Ok(Deps {
         config,
         object_storage: Arc::new(s3),
         db: Arc::new(db),
-        http: Arc::new(http),
+        http,
+        mailer,
+        identity_provider,
         repos: infra::repository::new(),
     })
 }
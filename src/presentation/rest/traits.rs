@@ -1,21 +1,27 @@
 //! Contains traits used to extend axum types in order
 //! to make it easier to write tests.
 
-use anyhow::{Context, Result};
+use anyhow::{ensure, Context, Result};
 use async_trait::async_trait;
-use hmac::{Hmac, Mac};
-use hyper::Body;
-use jwt::SignWithKey;
-use sha2::Sha256;
-use std::collections::HashMap;
+use chrono::Utc;
+use hyper::{Body, Method, StatusCode};
+use tower::Service;
 
-use crate::{domain::constants, infra::uuid::Uuid};
+use crate::{
+    config::Config,
+    domain::constants,
+    infra::{jwt, uuid::Uuid},
+};
 
 #[async_trait]
 pub trait RequestBuilderExt {
     /// Generates a JWT token containing the user id and adds it to the request.
     fn with_user_auth(self, user_id: Uuid) -> axum::http::request::Builder;
 
+    /// Same as `with_user_auth` but the session looks like it was created
+    /// using two-factor authentication.
+    fn with_mfa_user_auth(self, user_id: Uuid) -> axum::http::request::Builder;
+
     fn json<T>(self, value: T) -> Result<hyper::Request<Body>>
     where
         Self: Sized,
@@ -25,17 +31,17 @@ pub trait RequestBuilderExt {
 #[async_trait]
 impl RequestBuilderExt for axum::http::request::Builder {
     fn with_user_auth(self, user_id: Uuid) -> axum::http::request::Builder {
-        // TODO: hide the jwt library in a module.
-        let key: Hmac<Sha256> = Hmac::new_from_slice(b"some-secret").unwrap();
-
-        let mut claims = HashMap::new();
-        claims.insert("user_id", user_id.to_string());
-
-        let token_str = claims
-            .sign_with_key(&key)
-            .expect("signing user auth claims");
+        self.header(
+            constants::AUTHORIZATION_HEADER_NAME,
+            format!("Bearer {}", access_token(user_id, false)),
+        )
+    }
 
-        self.header(constants::AUTHORIZATION_HEADER_NAME, token_str)
+    fn with_mfa_user_auth(self, user_id: Uuid) -> axum::http::request::Builder {
+        self.header(
+            constants::AUTHORIZATION_HEADER_NAME,
+            format!("Bearer {}", access_token(user_id, true)),
+        )
     }
 
     fn json<T>(self, value: T) -> Result<hyper::Request<Body>>
@@ -53,6 +59,24 @@ impl RequestBuilderExt for axum::http::request::Builder {
     }
 }
 
+fn access_token(user_id: Uuid, mfa: bool) -> String {
+    let config = Config::from_env().expect("loading config");
+
+    let now = Utc::now().timestamp();
+
+    jwt::sign(
+        &config.jwt,
+        &jwt::Claims {
+            sub: user_id,
+            sid: Uuid::new_v4(),
+            iat: now,
+            exp: now + constants::ACCESS_TOKEN_EXPIRES_IN_SECS,
+            mfa,
+        },
+    )
+    .expect("signing user auth claims")
+}
+
 #[async_trait]
 pub trait ResponseExt {
     async fn json<T>(self) -> Result<T>
@@ -76,3 +100,52 @@ impl ResponseExt for axum::response::Response {
         Ok(t)
     }
 }
+
+#[async_trait]
+pub trait RouterExt {
+    /// Sends an unauthenticated GET request to `uri` and deserializes the response,
+    /// which is expected to be successful.
+    async fn get_json<T>(&mut self, uri: &str) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned;
+}
+
+#[async_trait]
+impl RouterExt for axum::Router {
+    async fn get_json<T>(&mut self, uri: &str) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let req = hyper::Request::builder()
+            .method(Method::GET)
+            .uri(uri)
+            .header(constants::X_REQUEST_ID_HEADER_NAME, 1)
+            .body(Body::empty())
+            .context("building request")?;
+
+        let response = self.call(req).await.context("sending request")?;
+
+        ensure!(
+            response.status() == StatusCode::OK,
+            "GET {uri} returned {}",
+            response.status()
+        );
+
+        response.json().await
+    }
+}
+
+/// Builds a request that makes `follower_id` follow (POST) or unfollow (DELETE) `followee_id`.
+pub fn follow_request(
+    method: Method,
+    follower_id: Uuid,
+    followee_id: Uuid,
+) -> Result<hyper::Request<Body>> {
+    hyper::Request::builder()
+        .method(method)
+        .uri(format!("/v1/users/{followee_id}/follow"))
+        .header(constants::X_REQUEST_ID_HEADER_NAME, 1)
+        .with_user_auth(follower_id)
+        .body(Body::empty())
+        .context("building follow request")
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::errors::ValidationError;
+
+const MAX_REASON_CHARS: usize = 500;
+
+/// Body of the ban and unban endpoints.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BanUserInput {
+    pub reason: String,
+}
+
+pub struct BanFields {
+    pub reason: String,
+}
+
+impl TryFrom<BanUserInput> for BanFields {
+    type Error = ValidationError;
+
+    fn try_from(input: BanUserInput) -> Result<Self, Self::Error> {
+        let reason = input.reason.trim().to_owned();
+
+        if reason.is_empty() || reason.chars().count() > MAX_REASON_CHARS {
+            return Err(ValidationError {
+                name: "reason".to_owned(),
+                message: format!("reason must have between 1 and {MAX_REASON_CHARS} characters"),
+            });
+        }
+
+        Ok(Self { reason })
+    }
+}
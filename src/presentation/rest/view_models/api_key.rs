@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::domain::{errors::ValidationError, queries, value_objects::api_key_scope::ApiKeyScope};
+use crate::infra::uuid::Uuid;
+
+const MAX_NAME_CHARS: usize = 100;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateApiKeyInput {
+    pub name: String,
+    /// The key can be used for every endpoint that accepts api keys when empty.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+/// The only response that contains the key.
+#[derive(Deserialize, Serialize)]
+pub struct CreateApiKeyOutput {
+    pub id: Uuid,
+    /// Sent in the X-Api-Key header.
+    pub key: String,
+    pub prefix: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ApiKeyOutput {
+    pub id: Uuid,
+    pub name: String,
+    pub prefix: String,
+    pub scopes: Vec<String>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The fields of the api key that the user chooses.
+pub struct ApiKeyFields {
+    pub name: String,
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+impl TryFrom<CreateApiKeyInput> for ApiKeyFields {
+    type Error = ValidationError;
+
+    fn try_from(input: CreateApiKeyInput) -> Result<Self, Self::Error> {
+        let name = input.name.trim().to_owned();
+
+        if name.is_empty() || name.chars().count() > MAX_NAME_CHARS {
+            return Err(ValidationError {
+                name: "name".to_owned(),
+                message: format!("name must have between 1 and {MAX_NAME_CHARS} characters"),
+            });
+        }
+
+        let mut scopes = Vec::with_capacity(input.scopes.len());
+
+        for scope in input.scopes.iter() {
+            let scope = ApiKeyScope::from_str(scope)?;
+
+            if !scopes.contains(&scope) {
+                scopes.push(scope);
+            }
+        }
+
+        Ok(Self { name, scopes })
+    }
+}
+
+impl From<queries::api_key::list::ApiKey> for ApiKeyOutput {
+    fn from(input: queries::api_key::list::ApiKey) -> Self {
+        Self {
+            id: input.id,
+            name: input.name,
+            prefix: input.prefix,
+            scopes: input
+                .scopes
+                .iter()
+                .map(|scope| scope.as_str().to_owned())
+                .collect(),
+            last_used_at: input.last_used_at,
+            created_at: input.created_at,
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::domain::{errors::ValidationError, queries, value_objects::creator_tag::CreatorTag};
+use crate::infra::uuid::Uuid;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SetCreatorTagsInput {
+    /// Replaces every tag of the creator, an empty list removes them.
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreatorTagsOutput {
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreatorOutput {
+    pub id: Uuid,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub followers_count: i64,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TagCountOutput {
+    pub name: String,
+    pub creators_count: i32,
+}
+
+/// The tags the creator chose, without duplicates.
+pub struct CreatorTagsFields {
+    pub tags: Vec<CreatorTag>,
+}
+
+impl TryFrom<SetCreatorTagsInput> for CreatorTagsFields {
+    type Error = ValidationError;
+
+    fn try_from(input: SetCreatorTagsInput) -> Result<Self, Self::Error> {
+        let mut tags = Vec::with_capacity(input.tags.len());
+
+        for tag in input.tags.iter() {
+            let tag = CreatorTag::from_str(tag)?;
+
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+
+        Ok(Self { tags })
+    }
+}
+
+impl From<Vec<CreatorTag>> for CreatorTagsOutput {
+    fn from(input: Vec<CreatorTag>) -> Self {
+        Self {
+            tags: input.iter().map(|tag| tag.as_str().to_owned()).collect(),
+        }
+    }
+}
+
+impl From<queries::creator::list_by_tag::Creator> for CreatorOutput {
+    fn from(input: queries::creator::list_by_tag::Creator) -> Self {
+        Self {
+            id: input.id,
+            username: input.username,
+            display_name: input.display_name,
+            avatar_url: input.avatar_url,
+            followers_count: input.followers_count,
+            tags: input
+                .tags
+                .iter()
+                .map(|tag| tag.as_str().to_owned())
+                .collect(),
+        }
+    }
+}
+
+impl From<queries::creator::list_tags::TagCount> for TagCountOutput {
+    fn from(input: queries::creator::list_tags::TagCount) -> Self {
+        Self {
+            name: input.tag.as_str().to_owned(),
+            creators_count: input.creators_count,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{domain::queries, infra::uuid::Uuid};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct FollowUserOutput {
+    pub id: Uuid,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub followed_at: DateTime<Utc>,
+}
+
+impl From<queries::follow::list_followers::FollowUser> for FollowUserOutput {
+    fn from(input: queries::follow::list_followers::FollowUser) -> Self {
+        Self {
+            id: input.id,
+            username: input.username,
+            display_name: input.display_name,
+            avatar_url: input.avatar_url,
+            followed_at: input.followed_at,
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::commands;
+
+#[derive(Deserialize, Serialize)]
+pub struct EnrollTotpOutput {
+    /// base32 secret, for users that cannot scan the qr code.
+    pub secret: String,
+    pub otpauth_uri: String,
+    pub qrcode_svg: String,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ConfirmTotpInput {
+    pub code: String,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ConfirmTotpOutput {
+    /// Each code can be used once instead of a totp code. They are not shown again.
+    pub recovery_codes: Vec<String>,
+}
+
+impl From<commands::mfa::EnrollTotpOutput> for EnrollTotpOutput {
+    fn from(input: commands::mfa::EnrollTotpOutput) -> Self {
+        Self {
+            secret: input.secret.to_base32(),
+            otpauth_uri: input.otpauth_uri,
+            qrcode_svg: input.qrcode_svg,
+        }
+    }
+}
+
+impl From<commands::mfa::ConfirmTotpOutput> for ConfirmTotpOutput {
+    fn from(input: commands::mfa::ConfirmTotpOutput) -> Self {
+        Self {
+            recovery_codes: input
+                .recovery_codes
+                .iter()
+                .map(|code| code.expose().to_owned())
+                .collect(),
+        }
+    }
+}
@@ -1,8 +1,20 @@
 use serde::{Deserialize, Serialize};
 
+pub mod admin;
+pub mod api_key;
+pub mod creator;
+pub mod follow;
+pub mod mfa;
+pub mod oidc;
+pub mod password_reset;
 pub mod pix_payment;
+pub mod post;
 pub mod register;
+pub mod relationship;
+pub mod session;
+pub mod terms;
 pub mod timeline;
+pub mod user;
 pub mod video;
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::commands;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct StartOidcLoginOutput {
+    /// The client must send the user to this url to log in at the issuer.
+    pub authorization_url: String,
+    /// The client must keep it, without sending it to the issuer,
+    /// and send it back along with the parameters of the callback.
+    pub verifier: String,
+    /// Number of seconds the user has to come back with the authorization code.
+    pub expires_in: i64,
+}
+
+/// Parameters the issuer added to the redirect uri.
+#[derive(Deserialize, Serialize)]
+pub struct CompleteOidcLoginInput {
+    pub state: String,
+    pub code: String,
+    /// Returned when the login was started.
+    pub verifier: String,
+}
+
+impl From<commands::session::StartOidcLoginOutput> for StartOidcLoginOutput {
+    fn from(input: commands::session::StartOidcLoginOutput) -> Self {
+        Self {
+            authorization_url: input.authorization_url,
+            verifier: input.verifier.expose().to_owned(),
+            expires_in: input.expires_in,
+        }
+    }
+}
+
+impl From<CompleteOidcLoginInput> for commands::session::CompleteOidcLoginInput {
+    fn from(input: CompleteOidcLoginInput) -> Self {
+        Self {
+            state: input.state.into(),
+            verifier: input.verifier.into(),
+            code: input.code,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{commands, value_objects::opaque_token::OpaqueToken};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RequestPasswordResetInput {
+    pub email: String,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ConfirmPasswordResetInput {
+    /// Token sent to the user by email.
+    pub token: String,
+    pub new_password: String,
+}
+
+impl From<RequestPasswordResetInput> for commands::password_reset::RequestPasswordResetInput {
+    fn from(input: RequestPasswordResetInput) -> Self {
+        Self { email: input.email }
+    }
+}
+
+impl From<ConfirmPasswordResetInput> for commands::password_reset::ConfirmPasswordResetInput {
+    fn from(input: ConfirmPasswordResetInput) -> Self {
+        Self {
+            token: OpaqueToken::from(input.token),
+            new_password: input.new_password,
+        }
+    }
+}
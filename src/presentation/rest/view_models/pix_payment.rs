@@ -19,13 +19,10 @@ pub struct StartPixPaymentOutput {
     pub qrcode: Vec<u8>,
 }
 
-impl From<StartPixPaymentInput> for commands::pix_payment::StartPixPaymentInput {
-    fn from(input: StartPixPaymentInput) -> Self {
-        Self {
-            creator_id: input.creator_id,
-            subscription: match input.subscription {
-                SubscriptionInput::Monthly => commands::pix_payment::SubscriptionInput::Monthly,
-            },
+impl From<SubscriptionInput> for commands::pix_payment::SubscriptionInput {
+    fn from(input: SubscriptionInput) -> Self {
+        match input {
+            SubscriptionInput::Monthly => commands::pix_payment::SubscriptionInput::Monthly,
         }
     }
 }
@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    domain::{errors::ValidationError, queries},
+    infra::uuid::Uuid,
+};
+
+const MAX_DESCRIPTION_CHARS: usize = 255;
+const MAX_DURATION_SECS: i32 = 60 * 60;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreatePostInput {
+    /// Returned by `POST /v1/videos`.
+    pub video_id: Uuid,
+    pub description: String,
+    #[serde(default)]
+    pub paid: bool,
+    pub duration_secs: i32,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreatePostOutput {
+    pub id: Uuid,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PostMediaOutput {
+    /// Where the video can be downloaded from until `expires_at`.
+    pub video_url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// The fields of the post that the creator chooses.
+pub struct PostFields {
+    pub video_id: Uuid,
+    pub description: String,
+    pub paid: bool,
+    pub duration_secs: i32,
+}
+
+impl TryFrom<CreatePostInput> for PostFields {
+    type Error = ValidationError;
+
+    fn try_from(input: CreatePostInput) -> Result<Self, Self::Error> {
+        let description = input.description.trim().to_owned();
+
+        if description.chars().count() > MAX_DESCRIPTION_CHARS {
+            return Err(ValidationError {
+                name: "description".to_owned(),
+                message: format!(
+                    "description must have at most {MAX_DESCRIPTION_CHARS} characters"
+                ),
+            });
+        }
+
+        if input.duration_secs < 1 || input.duration_secs > MAX_DURATION_SECS {
+            return Err(ValidationError {
+                name: "duration_secs".to_owned(),
+                message: format!("duration must be between 1 and {MAX_DURATION_SECS} seconds"),
+            });
+        }
+
+        Ok(Self {
+            video_id: input.video_id,
+            description,
+            paid: input.paid,
+            duration_secs: input.duration_secs,
+        })
+    }
+}
+
+impl From<queries::post::get_media::PostMedia> for PostMediaOutput {
+    fn from(input: queries::post::get_media::PostMedia) -> Self {
+        Self {
+            video_url: input.video_url,
+            expires_at: input.expires_at,
+        }
+    }
+}
@@ -6,4 +6,6 @@ pub struct RegisterInput {
     pub email: String,
     // TODO: password should not be printable by default
     pub password: String,
+    /// Must be the current version of the terms.
+    pub accepted_terms_version: String,
 }
@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{domain::queries, infra::uuid::Uuid};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RelatedUserOutput {
+    pub id: Uuid,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub since: DateTime<Utc>,
+}
+
+impl From<queries::relationship::list_blocked::RelatedUser> for RelatedUserOutput {
+    fn from(input: queries::relationship::list_blocked::RelatedUser) -> Self {
+        Self {
+            id: input.id,
+            username: input.username,
+            display_name: input.display_name,
+            avatar_url: input.avatar_url,
+            since: input.since,
+        }
+    }
+}
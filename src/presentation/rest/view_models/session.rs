@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{
+    commands,
+    errors::ValidationError,
+    value_objects::{opaque_token::OpaqueToken, recovery_code::RecoveryCode},
+};
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateSessionInput {
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateSessionOutput {
+    pub access_token: String,
+    pub token_type: String,
+    /// Number of seconds until the access token expires.
+    pub expires_in: i64,
+    pub refresh_token: String,
+}
+
+/// Returned instead of a session to users that have two-factor authentication enabled.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MfaChallengeOutput {
+    /// Sent along with the second factor to `POST /v1/sessions/mfa`.
+    pub mfa_token: String,
+    /// Number of seconds until the mfa token expires.
+    pub expires_in: i64,
+}
+
+/// Either `code` or `recovery_code` must be sent.
+#[derive(Deserialize, Serialize)]
+pub struct VerifyMfaInput {
+    pub mfa_token: String,
+    /// Code generated by the authenticator app.
+    pub code: Option<String>,
+    pub recovery_code: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct RefreshSessionInput {
+    pub refresh_token: String,
+}
+
+impl From<CreateSessionInput> for commands::session::CreateSessionInput {
+    fn from(input: CreateSessionInput) -> Self {
+        Self {
+            email: input.email,
+            password: input.password,
+            ip: None,
+        }
+    }
+}
+
+impl From<commands::session::CreateSessionOutput> for CreateSessionOutput {
+    fn from(input: commands::session::CreateSessionOutput) -> Self {
+        Self {
+            access_token: input.access_token,
+            token_type: "Bearer".to_owned(),
+            expires_in: input.expires_in,
+            refresh_token: input.refresh_token.expose().to_owned(),
+        }
+    }
+}
+
+impl From<commands::session::MfaChallenge> for MfaChallengeOutput {
+    fn from(input: commands::session::MfaChallenge) -> Self {
+        Self {
+            mfa_token: input.mfa_token,
+            expires_in: input.expires_in,
+        }
+    }
+}
+
+impl TryFrom<VerifyMfaInput> for commands::session::VerifyMfaInput {
+    type Error = ValidationError;
+
+    fn try_from(input: VerifyMfaInput) -> Result<Self, Self::Error> {
+        let code = match (input.code, input.recovery_code) {
+            (Some(code), None) => commands::session::MfaCode::Totp(code),
+            (None, Some(code)) => commands::session::MfaCode::Recovery(RecoveryCode::from(code)),
+            _ => {
+                return Err(ValidationError {
+                    name: "code".to_owned(),
+                    message: "either code or recovery_code must be sent".to_owned(),
+                })
+            }
+        };
+
+        Ok(Self {
+            mfa_token: input.mfa_token,
+            code,
+            ip: None,
+        })
+    }
+}
+
+impl From<RefreshSessionInput> for commands::session::RefreshSessionInput {
+    fn from(input: RefreshSessionInput) -> Self {
+        Self {
+            refresh_token: OpaqueToken::from(input.refresh_token),
+        }
+    }
+}
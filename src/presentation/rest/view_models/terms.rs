@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::queries;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TermsVersionOutput {
+    pub version: String,
+    pub published_at: DateTime<Utc>,
+}
+
+impl From<queries::terms::get_current::TermsVersion> for TermsVersionOutput {
+    fn from(input: queries::terms::get_current::TermsVersion) -> Self {
+        Self {
+            version: input.version,
+            published_at: input.published_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AcceptTermsInput {
+    pub accepted_terms_version: String,
+}
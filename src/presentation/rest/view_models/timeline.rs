@@ -1,29 +1,78 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::{domain::queries, infra::uuid::Uuid};
+use crate::{
+    domain::{errors::ValidationError, queries, value_objects::post_cursor::PostCursor},
+    infra::uuid::Uuid,
+};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct PostOutput {
     pub id: Uuid,
     pub creator_username: String,
     pub description: String,
-    pub video_url: String,
     pub likes: i32,
     pub paid: bool,
+    /// True when the post is paid and the viewer is not subscribed to the creator.
+    /// The video of posts that are not locked is watched through `GET /v1/posts/:id/media`.
+    pub locked: bool,
+    pub preview: PostPreviewOutput,
+    /// None for drafts, which only the creator sees.
+    pub published_at: Option<DateTime<Utc>>,
+    pub pinned: bool,
     pub created_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PostPreviewOutput {
+    pub thumbnail_url: Option<String>,
+    pub duration_secs: Option<i32>,
+}
+
 impl From<queries::timeline::get_timeline::Post> for PostOutput {
     fn from(input: queries::timeline::get_timeline::Post) -> Self {
         Self {
+            locked: input.locked,
             id: input.id,
             creator_username: input.creator_username,
             description: input.description,
-            video_url: input.video_url,
             likes: input.likes,
             paid: input.paid,
+            preview: PostPreviewOutput {
+                thumbnail_url: input.preview.thumbnail_url,
+                duration_secs: input.preview.duration_secs,
+            },
+            published_at: input.published_at,
+            pinned: input.pinned,
             created_at: input.created_at,
         }
     }
 }
+
+/// A page of the timeline. Pass `next_cursor` back as the `cursor`
+/// query param to get the next page, it is None on the last page.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TimelineOutput {
+    pub posts: Vec<PostOutput>,
+    pub next_cursor: Option<String>,
+}
+
+impl TimelineOutput {
+    pub fn new(input: queries::timeline::get_timeline::PostPage, cursor_secret: &str) -> Self {
+        Self {
+            posts: input.posts.into_iter().map(PostOutput::from).collect(),
+            next_cursor: input.next_cursor.map(|cursor| cursor.encode(cursor_secret)),
+        }
+    }
+}
+
+/// Decodes the `cursor` query param, None when it was not sent.
+pub fn decode_cursor(
+    cursor: Option<String>,
+    cursor_secret: &str,
+) -> Result<Option<PostCursor>, ValidationError> {
+    cursor
+        .map(|cursor| PostCursor::decode(&cursor, cursor_secret))
+        .transpose()
+        .map_err(ValidationError::from)
+}
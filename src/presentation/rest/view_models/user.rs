@@ -0,0 +1,277 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::{
+    domain::{
+        commands,
+        errors::ValidationError,
+        queries,
+        value_objects::{
+            opaque_token::OpaqueToken,
+            profile_image::{ImageContentType, ProfileImageKind},
+        },
+    },
+    infra::uuid::Uuid,
+};
+
+use super::video::PresignedPostUrlOutput;
+
+const MAX_DISPLAY_NAME_CHARS: usize = 50;
+const MAX_BIO_CHARS: usize = 500;
+const MAX_LINKS: usize = 5;
+const MAX_LINK_CHARS: usize = 255;
+
+#[derive(Deserialize, Serialize)]
+pub struct VerifyEmailInput {
+    /// Token sent to the user by email.
+    pub token: String,
+}
+
+/// What anyone can see about a user.
+/// Private information such as the email must never be added here.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UserProfileOutput {
+    pub id: Uuid,
+    pub username: String,
+    pub role: String,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub links: Vec<String>,
+    pub avatar_url: Option<String>,
+    pub banner_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub followers_count: i64,
+    pub following_count: i64,
+    pub tags: Vec<String>,
+}
+
+/// Fields that are not sent are left unchanged, empty strings clear them.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct UpdateProfileInput {
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    /// Replaces every link of the profile.
+    pub links: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct StartProfileImageUploadInput {
+    /// Either avatar or banner.
+    pub kind: String,
+    /// Content type of the image that is going to be uploaded.
+    pub content_type: String,
+}
+
+/// The fields of the profile image upload that the user chooses.
+pub struct ProfileImageFields {
+    pub kind: ProfileImageKind,
+    pub content_type: ImageContentType,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct StartProfileImageUploadOutput {
+    pub upload_id: Uuid,
+    /// The image must be sent with the same content type it was requested with.
+    pub presigned_url: PresignedPostUrlOutput,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ConfirmProfileImageUploadOutput {
+    pub kind: String,
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RequestPersonalDataExportOutput {
+    pub id: Uuid,
+    pub status: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PersonalDataExportOutput {
+    pub id: Uuid,
+    /// pending or ready.
+    pub status: String,
+    /// Zip archive with a json file and the media uploaded by the user, set once ready.
+    pub download_url: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    /// When the archive was built.
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<VerifyEmailInput> for commands::user::VerifyEmailInput {
+    fn from(input: VerifyEmailInput) -> Self {
+        Self {
+            token: OpaqueToken::from(input.token),
+        }
+    }
+}
+
+impl From<queries::user::get_by_id::User> for UserProfileOutput {
+    fn from(input: queries::user::get_by_id::User) -> Self {
+        Self {
+            id: input.id,
+            username: input.username,
+            role: input.role.as_str().to_owned(),
+            display_name: input.display_name,
+            bio: input.bio,
+            links: input.links,
+            avatar_url: input.avatar_url,
+            banner_url: input.banner_url,
+            created_at: input.created_at,
+            followers_count: input.followers_count,
+            following_count: input.following_count,
+            tags: input
+                .tags
+                .iter()
+                .map(|tag| tag.as_str().to_owned())
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<UpdateProfileInput> for commands::user::ProfileChanges {
+    type Error = ValidationError;
+
+    fn try_from(input: UpdateProfileInput) -> Result<Self, Self::Error> {
+        let display_name = input.display_name.map(|name| name.trim().to_owned());
+
+        if let Some(name) = &display_name {
+            if name.chars().count() > MAX_DISPLAY_NAME_CHARS {
+                return Err(ValidationError {
+                    name: "display_name".to_owned(),
+                    message: format!(
+                        "display name must have at most {MAX_DISPLAY_NAME_CHARS} characters"
+                    ),
+                });
+            }
+        }
+
+        let bio = input.bio.map(|bio| bio.trim().to_owned());
+
+        if let Some(bio) = &bio {
+            if bio.chars().count() > MAX_BIO_CHARS {
+                return Err(ValidationError {
+                    name: "bio".to_owned(),
+                    message: format!("bio must have at most {MAX_BIO_CHARS} characters"),
+                });
+            }
+        }
+
+        let links = match input.links {
+            None => None,
+            Some(links) => Some(validate_links(links)?),
+        };
+
+        Ok(Self {
+            display_name,
+            bio,
+            links,
+        })
+    }
+}
+
+/// Only http and https links are accepted so the profile
+/// cannot link to `javascript:` urls and the like.
+fn validate_links(links: Vec<String>) -> Result<Vec<String>, ValidationError> {
+    let error = |message: String| ValidationError {
+        name: "links".to_owned(),
+        message,
+    };
+
+    if links.len() > MAX_LINKS {
+        return Err(error(format!(
+            "profiles can have at most {MAX_LINKS} links"
+        )));
+    }
+
+    links
+        .into_iter()
+        .map(|link| link.trim().to_owned())
+        .map(|link| {
+            let lowercase = link.to_lowercase();
+
+            if link.len() > MAX_LINK_CHARS
+                || !(lowercase.starts_with("https://") || lowercase.starts_with("http://"))
+                || !validator::validate_url(&link)
+            {
+                return Err(error(format!(
+                    "links must be http or https urls with at most {MAX_LINK_CHARS} characters"
+                )));
+            }
+
+            Ok(link)
+        })
+        .collect()
+}
+
+impl TryFrom<StartProfileImageUploadInput> for ProfileImageFields {
+    type Error = ValidationError;
+
+    fn try_from(input: StartProfileImageUploadInput) -> Result<Self, Self::Error> {
+        Ok(Self {
+            kind: ProfileImageKind::from_str(&input.kind)?,
+            content_type: ImageContentType::from_str(&input.content_type)?,
+        })
+    }
+}
+
+impl From<commands::user::StartProfileImageUploadOutput> for StartProfileImageUploadOutput {
+    fn from(input: commands::user::StartProfileImageUploadOutput) -> Self {
+        Self {
+            upload_id: input.upload_id,
+            presigned_url: input.presigned_url.into(),
+        }
+    }
+}
+
+impl From<commands::user::ConfirmProfileImageUploadOutput> for ConfirmProfileImageUploadOutput {
+    fn from(input: commands::user::ConfirmProfileImageUploadOutput) -> Self {
+        Self {
+            kind: input.kind.as_str().to_owned(),
+            url: input.url,
+        }
+    }
+}
+
+impl From<commands::user::RequestPersonalDataExportOutput> for RequestPersonalDataExportOutput {
+    fn from(input: commands::user::RequestPersonalDataExportOutput) -> Self {
+        Self {
+            id: input.id,
+            status: "pending".to_owned(),
+        }
+    }
+}
+
+impl From<queries::user::get_personal_data_export::PersonalDataExport>
+    for PersonalDataExportOutput
+{
+    fn from(input: queries::user::get_personal_data_export::PersonalDataExport) -> Self {
+        use queries::user::get_personal_data_export::PersonalDataExportStatus;
+
+        match input.status {
+            PersonalDataExportStatus::Pending => Self {
+                id: input.id,
+                status: "pending".to_owned(),
+                download_url: None,
+                expires_at: None,
+                completed_at: None,
+                created_at: input.created_at,
+            },
+            PersonalDataExportStatus::Ready {
+                download_url,
+                expires_at,
+                completed_at,
+            } => Self {
+                id: input.id,
+                status: "ready".to_owned(),
+                download_url: Some(download_url),
+                expires_at: Some(expires_at),
+                completed_at: Some(completed_at),
+                created_at: input.created_at,
+            },
+        }
+    }
+}
@@ -1,10 +1,16 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{domain::commands, infra::uuid::Uuid};
+use crate::{
+    domain::{commands, contracts::object_storage::GetPresignedPostUrlOutput},
+    infra::uuid::Uuid,
+};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct StartVideoUploadOutput {
     pub presigned_url: PresignedPostUrlOutput,
+    /// The thumbnail must be a jpeg image.
+    pub thumbnail_presigned_url: PresignedPostUrlOutput,
+    /// Sent to `POST /v1/posts` once the video and the thumbnail have been uploaded.
     pub video_id: Uuid,
 }
 
@@ -23,21 +29,27 @@ pub struct FormDataField {
     pub value: String,
 }
 
+impl From<GetPresignedPostUrlOutput> for PresignedPostUrlOutput {
+    fn from(input: GetPresignedPostUrlOutput) -> Self {
+        Self {
+            endpoint: input.endpoint,
+            form_data_fields: input
+                .form_data_fields
+                .into_iter()
+                .map(|field| FormDataField {
+                    name: field.name,
+                    value: field.value,
+                })
+                .collect(),
+        }
+    }
+}
+
 impl From<commands::video::StartVideoUploadOutput> for StartVideoUploadOutput {
     fn from(input: commands::video::StartVideoUploadOutput) -> Self {
         Self {
-            presigned_url: PresignedPostUrlOutput {
-                endpoint: input.presigned_url.endpoint,
-                form_data_fields: input
-                    .presigned_url
-                    .form_data_fields
-                    .into_iter()
-                    .map(|field| FormDataField {
-                        name: field.name,
-                        value: field.value,
-                    })
-                    .collect(),
-            },
+            presigned_url: input.presigned_url.into(),
+            thumbnail_presigned_url: input.thumbnail_presigned_url.into(),
             video_id: input.video_id,
         }
     }